@@ -3,8 +3,8 @@
 //! Run with:
 //! `cargo run --example typed_quantities`
 
-use cheby::{evaluate, fit_from_fn};
-use qtty::Quantity;
+use cheby::{evaluate, fit_from_fn, ChebySegmentTable};
+use qtty::{Days, Quantity};
 
 type Km = qtty::Kilometer;
 type Kilometers = Quantity<Km>;
@@ -24,4 +24,14 @@ fn main() {
     // Evaluate at tau = 0.0 (the midpoint).
     let mid_value = evaluate(&coeffs, 0.0);
     println!("midpoint value = {:.6} km", mid_value.value());
+
+    // A table over a typed time axis: derivatives come out in km/d.
+    let table: ChebySegmentTable<Kilometers, N, Days> = ChebySegmentTable::from_fn(
+        |t: Days| Kilometers::new(7000.0 + 250.0 * t.value().sin()),
+        Days::new(0.0),
+        Days::new(10.0),
+        Days::new(2.0),
+    );
+    let rate = table.eval_derivative(Days::new(3.0)).unwrap();
+    println!("rate at t = 3 d = {:.6} km/d", rate.value());
 }
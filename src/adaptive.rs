@@ -0,0 +1,341 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! Adaptive segmentation to a requested error tolerance.
+//!
+//! [`fit_adaptive`] fits `N` coefficients on `[start, end]` and, while the
+//! estimated error exceeds the tolerance, bisects the interval and refits
+//! each half. The result is a non-uniform [`ChebyVarTable`] together with
+//! a per-segment [`SegmentReport`].
+//!
+//! Each fit is accepted when
+//!
+//! ```text
+//! max(tail, sample_error) ≤ abs_tol + rel_tol · scale
+//! ```
+//!
+//! where `tail` is the magnitude of the last two coefficients,
+//! `sample_error` the largest deviation from `f` at held-out points (the
+//! midpoints between adjacent nodes, plus both segment endpoints), and
+//! `scale` the largest magnitude of `f` seen on the segment.
+//!
+//! A non-finite error estimate, e.g. from a function that is undefined
+//! on part of the interval, stops the fit with an error rather than
+//! bisecting further.
+//!
+//! Like any sampling-based estimate, a feature much narrower than the
+//! node spacing of the initial interval can go unnoticed; start from a
+//! shorter interval (or several) when that is a concern.
+
+use crate::fit;
+use crate::nodes;
+use crate::scalar::ChebyNorm;
+use crate::segment::{ChebySegment, ChebyVarTable, VarTableError};
+
+/// Tolerances and limits for [`fit_adaptive`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveOptions {
+    /// Absolute error tolerance, in the units of the fitted values.
+    pub abs_tol: f64,
+    /// Relative error tolerance, scaled by the largest sampled magnitude.
+    pub rel_tol: f64,
+    /// Maximum number of bisections from the initial interval. Segments
+    /// still above tolerance at this depth are kept and flagged as not
+    /// converged. Each level can double the number of segments, so this
+    /// bounds the table at `2^max_depth` segments.
+    pub max_depth: u32,
+}
+
+impl AdaptiveOptions {
+    /// Options with the given absolute and relative tolerances and the
+    /// default maximum depth.
+    #[inline]
+    pub fn new(abs_tol: f64, rel_tol: f64) -> Self {
+        Self {
+            abs_tol,
+            rel_tol,
+            ..Self::default()
+        }
+    }
+
+    /// Set the maximum bisection depth.
+    #[inline]
+    pub fn with_max_depth(mut self, max_depth: u32) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+}
+
+impl Default for AdaptiveOptions {
+    fn default() -> Self {
+        Self {
+            abs_tol: 1e-12,
+            rel_tol: 0.0,
+            max_depth: 16,
+        }
+    }
+}
+
+/// Error estimate for one segment of an adaptive fit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SegmentReport {
+    /// Start of the segment.
+    pub start: f64,
+    /// End of the segment.
+    pub end: f64,
+    /// Magnitude of the last two coefficients.
+    pub tail: f64,
+    /// Largest deviation from `f` at the held-out sample points.
+    pub sample_error: f64,
+    /// Largest magnitude of `f` over all samples of the segment.
+    pub scale: f64,
+    /// Tolerance the segment was tested against.
+    pub tolerance: f64,
+    /// Whether the segment met the tolerance (`false` only when the
+    /// maximum depth was reached first).
+    pub converged: bool,
+}
+
+impl SegmentReport {
+    /// The error estimate, `max(tail, sample_error)`.
+    #[inline]
+    pub fn error(&self) -> f64 {
+        self.tail.max(self.sample_error)
+    }
+}
+
+/// Result of [`fit_adaptive`]: the fitted table and one report per segment.
+#[derive(Debug, Clone)]
+pub struct AdaptiveFit<T: ChebyNorm, const N: usize> {
+    /// The non-uniform segment table.
+    pub table: ChebyVarTable<T, N>,
+    /// Error reports, one per segment, in the same order as the table.
+    pub report: Vec<SegmentReport>,
+}
+
+impl<T: ChebyNorm, const N: usize> AdaptiveFit<T, N> {
+    /// Whether every segment met the tolerance.
+    pub fn converged(&self) -> bool {
+        self.report.iter().all(|r| r.converged)
+    }
+
+    /// The largest error estimate over all segments.
+    pub fn max_error(&self) -> f64 {
+        self.report
+            .iter()
+            .map(SegmentReport::error)
+            .fold(0.0, f64::max)
+    }
+}
+
+/// Fit `f` on `[start, end]` with `N` coefficients per segment, bisecting
+/// until every segment meets the tolerance in `options`.
+///
+/// # Errors
+///
+/// [`VarTableError::InvalidDomain`] if `start` or `end` is not finite or
+/// `start >= end`, and [`VarTableError::NonFinite`] if a segment's error
+/// estimate is not finite, e.g. where `f` returns NaN.
+///
+/// # Example
+///
+/// ```
+/// use cheby::adaptive::{fit_adaptive, AdaptiveOptions};
+///
+/// let fit = fit_adaptive::<f64, 12>(f64::sin, 0.0, 20.0, &AdaptiveOptions::new(1e-10, 0.0))?;
+/// assert!(fit.converged());
+/// assert!((fit.table.eval(7.3).unwrap() - 7.3_f64.sin()).abs() < 1e-10);
+/// # Ok::<(), cheby::VarTableError>(())
+/// ```
+pub fn fit_adaptive<T: ChebyNorm, const N: usize>(
+    f: impl Fn(f64) -> T,
+    start: f64,
+    end: f64,
+    options: &AdaptiveOptions,
+) -> Result<AdaptiveFit<T, N>, VarTableError> {
+    if !(start.is_finite() && end.is_finite() && start < end) {
+        return Err(VarTableError::InvalidDomain { start, end });
+    }
+    let mut segments = Vec::new();
+    let mut report = Vec::new();
+    bisect(&f, start, end, 0, options, &mut segments, &mut report)?;
+
+    let mut breakpoints: Vec<f64> = report.iter().map(|r| r.start).collect();
+    breakpoints.push(end);
+
+    Ok(AdaptiveFit {
+        table: ChebyVarTable::from_parts(segments, breakpoints),
+        report,
+    })
+}
+
+/// Fit `[start, end]`, recursing into both halves if the fit is rejected
+/// and the midpoint falls strictly between the ends. Fails on a
+/// non-finite error estimate, which no bisection would fix.
+fn bisect<T: ChebyNorm, const N: usize>(
+    f: &impl Fn(f64) -> T,
+    start: f64,
+    end: f64,
+    depth: u32,
+    options: &AdaptiveOptions,
+    segments: &mut Vec<ChebySegment<T, N>>,
+    report: &mut Vec<SegmentReport>,
+) -> Result<(), VarTableError> {
+    let (segment, mut entry) = fit_checked::<T, N>(f, start, end, options);
+    // `f64::max` skips NaN, so test the parts of `error()` directly.
+    if !(entry.tail.is_finite() && entry.sample_error.is_finite() && entry.scale.is_finite()) {
+        return Err(VarTableError::NonFinite {
+            index: segments.len(),
+        });
+    }
+    let accepted = entry.error() <= entry.tolerance;
+    let mid = 0.5 * (start + end);
+
+    if accepted || depth >= options.max_depth || !(start < mid && mid < end) {
+        entry.converged = accepted;
+        segments.push(segment);
+        report.push(entry);
+        return Ok(());
+    }
+
+    bisect(f, start, mid, depth + 1, options, segments, report)?;
+    bisect(f, mid, end, depth + 1, options, segments, report)
+}
+
+/// Fit a single segment and estimate its error.
+fn fit_checked<T: ChebyNorm, const N: usize>(
+    f: &impl Fn(f64) -> T,
+    start: f64,
+    end: f64,
+    options: &AdaptiveOptions,
+) -> (ChebySegment<T, N>, SegmentReport) {
+    let mid = 0.5 * (start + end);
+    let half = 0.5 * (end - start);
+
+    let xi: [f64; N] = nodes::nodes();
+    let values: [T; N] = std::array::from_fn(|k| f(mid + half * xi[k]));
+    let segment = ChebySegment::new(fit::fit_coeffs(&values), mid, half);
+
+    let tail = segment.coeffs[N.saturating_sub(2)..]
+        .iter()
+        .map(ChebyNorm::norm)
+        .sum();
+    let mut scale = values.iter().map(ChebyNorm::norm).fold(0.0, f64::max);
+
+    // Held-out points: both endpoints and the midpoints between nodes.
+    let held_out = [-1.0, 1.0]
+        .into_iter()
+        .chain(xi.windows(2).map(|w| 0.5 * (w[0] + w[1])));
+    let mut sample_error = 0.0_f64;
+    for tau in held_out {
+        let exact = f(mid + half * tau);
+        let approx = crate::eval::evaluate(&segment.coeffs, tau);
+        scale = scale.max(exact.norm());
+        sample_error = sample_error.max((approx - exact).norm());
+    }
+
+    let entry = SegmentReport {
+        start,
+        end,
+        tail,
+        sample_error,
+        scale,
+        tolerance: options.abs_tol + options.rel_tol * scale,
+        converged: false,
+    };
+    (segment, entry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_smooth_function_converges() {
+        let opts = AdaptiveOptions::new(1e-11, 0.0);
+        let fit = fit_adaptive::<f64, 14>(f64::sin, 0.0, 30.0, &opts).unwrap();
+        assert!(fit.converged());
+        assert_eq!(fit.table.len(), fit.report.len());
+        assert!(fit.max_error() <= 1e-11);
+
+        for i in 0..=300 {
+            let t = i as f64 * 0.0999;
+            let approx = fit.table.eval(t).unwrap();
+            assert!((approx - t.sin()).abs() < 1e-10, "t={t}: {approx}");
+        }
+    }
+
+    #[test]
+    fn test_segments_refine_near_feature() {
+        // A Gaussian bump at t = 2 forces short segments there only.
+        let f = |t: f64| (-(t - 2.0).powi(2) * 4.0).exp();
+        let fit = fit_adaptive::<f64, 10>(f, 0.0, 16.0, &AdaptiveOptions::new(1e-8, 0.0)).unwrap();
+        assert!(fit.converged());
+
+        let lens: Vec<f64> = fit.report.iter().map(|r| r.end - r.start).collect();
+        let shortest = lens.iter().cloned().fold(f64::INFINITY, f64::min);
+        let longest = lens.iter().cloned().fold(0.0, f64::max);
+        assert!(longest / shortest >= 8.0, "lens = {lens:?}");
+
+        // Breakpoints are contiguous.
+        for w in fit.report.windows(2) {
+            assert_eq!(w[0].end, w[1].start);
+        }
+        assert_eq!(fit.table.start(), 0.0);
+        assert_eq!(fit.table.end(), 16.0);
+    }
+
+    #[test]
+    fn test_relative_tolerance_and_max_depth() {
+        let f = |t: f64| 1e6 * t.exp();
+        let fit = fit_adaptive::<f64, 8>(f, 0.0, 4.0, &AdaptiveOptions::new(0.0, 1e-9)).unwrap();
+        assert!(fit.converged());
+        for r in &fit.report {
+            assert!(r.tolerance > 0.0 && r.error() <= r.tolerance);
+        }
+
+        // Impossible tolerance: stops at max depth and reports it.
+        let capped = fit_adaptive::<f64, 4>(
+            f64::sin,
+            0.0,
+            1.0,
+            &AdaptiveOptions::new(0.0, 0.0).with_max_depth(2),
+        )
+        .unwrap();
+        assert_eq!(capped.table.len(), 4);
+        assert!(!capped.converged());
+
+        // Bisection stops at segments a few ulps wide, whatever the depth.
+        let (start, end) = (1.0, 1.0 + 4.0 * f64::EPSILON);
+        let noise = |t: f64| (t.to_bits() % 2) as f64;
+        let opts = AdaptiveOptions::new(0.0, 0.0).with_max_depth(64);
+        let fit = fit_adaptive::<f64, 4>(noise, start, end, &opts).unwrap();
+        assert_eq!(fit.table.len(), 4);
+        assert!(fit.report.iter().all(|r| r.start < r.end));
+    }
+
+    #[test]
+    fn test_rejected_input() {
+        let opts = AdaptiveOptions::default();
+        for (start, end) in [
+            (1.0, 1.0),
+            (2.0, 1.0),
+            (f64::NAN, 1.0),
+            (0.0, f64::INFINITY),
+        ] {
+            let err = fit_adaptive::<f64, 8>(f64::sin, start, end, &opts).unwrap_err();
+            assert!(matches!(err, VarTableError::InvalidDomain { .. }), "{err}");
+        }
+        let err = fit_adaptive::<f64, 8>(f64::sin, 2.0, 1.0, &opts).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "domain [2, 1] is not finite and increasing"
+        );
+
+        // NaN on the left half fails at once instead of bisecting to the
+        // maximum depth.
+        let err = fit_adaptive::<f64, 8>(f64::sqrt, -1.0, 1.0, &opts).unwrap_err();
+        assert_eq!(err, VarTableError::NonFinite { index: 0 });
+        assert_eq!(err.to_string(), "segment 0 has non-finite values");
+    }
+}
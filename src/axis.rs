@@ -0,0 +1,262 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! Independent-variable types for segments and tables.
+//!
+//! [`ChebySegment`](crate::ChebySegment),
+//! [`ChebySegmentTable`](crate::ChebySegmentTable) and
+//! [`ChebyVarTable`](crate::ChebyVarTable) take an optional third
+//! parameter `X`, the type of their independent variable (domain, `mid`,
+//! `half` and every `t` argument). The default `f64` is unit-agnostic;
+//! a `qtty::Quantity<U>` such as `qtty::Days` ties the table to a unit.
+//!
+//! Lookup and evaluation methods accept any [`IntoAxis<X>`]: on a
+//! `Quantity<U>` axis that is a `Quantity<V>` of the same dimension,
+//! converted to `U` on the way in (hours on a table built in days), while
+//! a bare `f64` or a quantity of another dimension is rejected at compile
+//! time:
+//!
+//! ```compile_fail
+//! # use cheby::ChebySegmentTable;
+//! # use qtty::Days;
+//! let table: ChebySegmentTable<f64, 8, Days> =
+//!     ChebySegmentTable::from_fn(|t: Days| t.value(), Days::new(0.0), Days::new(2.0), Days::new(1.0));
+//! table.eval(1.5); // error: `f64` is not a time
+//! ```
+//!
+//! [`ChebyCalculus`] tells the table what differentiating or integrating
+//! its values with respect to `X` produces. On a `Quantity<V>` axis a
+//! `Quantity<U>` value has derivative `Quantity<Per<U, V>>` and integral
+//! `Quantity<Prod<U, V>>`; on an `f64` axis every scalar is its own
+//! derivative and integral type, as before.
+//!
+//! # Example
+//!
+//! ```
+//! use cheby::ChebySegmentTable;
+//! use qtty::{Day, Days, Kilometer, Kilometers, Per, Prod, Quantity};
+//!
+//! let table: ChebySegmentTable<Kilometers, 12, Days> = ChebySegmentTable::from_fn(
+//!     |t: Days| Kilometers::new(3.0 * t.value()),
+//!     Days::new(0.0),
+//!     Days::new(4.0),
+//!     Days::new(1.0),
+//! );
+//! let v: Quantity<Per<Kilometer, Day>> = table.eval_derivative(Days::new(2.5)).unwrap();
+//! assert!((v.value() - 3.0).abs() < 1e-12);
+//! let area: Quantity<Prod<Kilometer, Day>> =
+//!     table.integrate(Days::new(0.0), Days::new(2.0)).unwrap();
+//! assert!((area.value() - 6.0).abs() < 1e-12);
+//! ```
+
+use crate::scalar::{ChebyScalar, Vector};
+use qtty::{Per, Prod, Quantity, Unit, Unitless};
+use std::fmt::Debug;
+
+/// The independent variable of a segment or table: `f64` or a
+/// `qtty::Quantity<U>`.
+///
+/// Lookup and normalisation work on the plain value in the axis's own
+/// unit.
+pub trait ChebyAxis: Copy + Debug + PartialOrd + IntoAxis<Self> {
+    /// The value in the axis's own unit.
+    fn value(self) -> f64;
+
+    /// An axis value from a number in the axis's own unit.
+    fn from_value(value: f64) -> Self;
+}
+
+impl ChebyAxis for f64 {
+    #[inline]
+    fn value(self) -> f64 {
+        self
+    }
+
+    #[inline]
+    fn from_value(value: f64) -> Self {
+        value
+    }
+}
+
+impl<U: Unit> ChebyAxis for Quantity<U> {
+    #[inline]
+    fn value(self) -> f64 {
+        Quantity::value(self)
+    }
+
+    #[inline]
+    fn from_value(value: f64) -> Self {
+        Self::new(value)
+    }
+}
+
+/// A value that can be used as a time on an axis `X`.
+///
+/// Implemented by `f64` for `f64` axes and, for a `Quantity<U>` axis, by
+/// every `Quantity<V>` whose unit has the same dimension as `U`; the value
+/// is converted to `U`.
+pub trait IntoAxis<X>: Copy {
+    /// Convert to the axis type.
+    fn into_axis(self) -> X;
+}
+
+impl IntoAxis<f64> for f64 {
+    #[inline]
+    fn into_axis(self) -> f64 {
+        self
+    }
+}
+
+impl<U, V> IntoAxis<Quantity<U>> for Quantity<V>
+where
+    U: Unit,
+    V: Unit<Dim = U::Dim>,
+{
+    #[inline]
+    fn into_axis(self) -> Quantity<U> {
+        self.to::<U>()
+    }
+}
+
+/// The types of `df/dt` and `∫ f dt` for values of type `Self` on an
+/// axis `X`.
+///
+/// The numerical work (Clenshaw, chain rule, coefficient integration) is
+/// done in `Self`; the conversions only retag the finished value with its
+/// physical type.
+pub trait ChebyCalculus<X: ChebyAxis>: ChebyScalar {
+    /// Type of the derivative `df/dt`.
+    type Derivative: ChebyScalar<Field = Self::Field>;
+
+    /// Type of the integral `∫ f dt`.
+    type Integral: ChebyScalar<Field = Self::Field>;
+
+    /// Reinterpret a value already divided by `dt` as a derivative.
+    fn into_derivative(self) -> Self::Derivative;
+
+    /// Reinterpret a value already multiplied by `dt` as an integral.
+    fn into_integral(self) -> Self::Integral;
+}
+
+/// A unit-agnostic `f64` axis leaves every value type unchanged.
+impl<T: ChebyScalar> ChebyCalculus<f64> for T {
+    type Derivative = T;
+    type Integral = T;
+
+    #[inline]
+    fn into_derivative(self) -> T {
+        self
+    }
+
+    #[inline]
+    fn into_integral(self) -> T {
+        self
+    }
+}
+
+impl<U, V> ChebyCalculus<Quantity<V>> for Quantity<U>
+where
+    U: Unit,
+    V: Unit,
+    Per<U, V>: Unit,
+    Prod<U, V>: Unit,
+{
+    type Derivative = Quantity<Per<U, V>>;
+    type Integral = Quantity<Prod<U, V>>;
+
+    #[inline]
+    fn into_derivative(self) -> Self::Derivative {
+        Quantity::new(self.value())
+    }
+
+    #[inline]
+    fn into_integral(self) -> Self::Integral {
+        Quantity::new(self.value())
+    }
+}
+
+/// Plain numbers on a typed axis become rates (`1/V`) and integrals
+/// (`V`).
+impl<V> ChebyCalculus<Quantity<V>> for f64
+where
+    V: Unit,
+    Per<Unitless, V>: Unit,
+{
+    type Derivative = Quantity<Per<Unitless, V>>;
+    type Integral = Quantity<V>;
+
+    #[inline]
+    fn into_derivative(self) -> Self::Derivative {
+        Quantity::new(self)
+    }
+
+    #[inline]
+    fn into_integral(self) -> Self::Integral {
+        Quantity::new(self)
+    }
+}
+
+impl<T, V, const D: usize> ChebyCalculus<Quantity<V>> for Vector<T, D>
+where
+    T: ChebyCalculus<Quantity<V>>,
+    V: Unit,
+{
+    type Derivative = Vector<T::Derivative, D>;
+    type Integral = Vector<T::Integral, D>;
+
+    #[inline]
+    fn into_derivative(self) -> Self::Derivative {
+        Vector(self.0.map(T::into_derivative))
+    }
+
+    #[inline]
+    fn into_integral(self) -> Self::Integral {
+        Vector(self.0.map(T::into_integral))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qtty::{Day, Days, Kilometer, Kilometers, Second};
+
+    #[test]
+    fn test_axis_values() {
+        assert_eq!(ChebyAxis::value(2.5_f64), 2.5);
+        assert_eq!(<Days as ChebyAxis>::from_value(3.0), Days::new(3.0));
+        assert_eq!(ChebyAxis::value(Days::new(3.0)), 3.0);
+    }
+
+    #[test]
+    fn test_into_axis_converts_units() {
+        use qtty::{Hours, Minutes};
+
+        assert_eq!(IntoAxis::<f64>::into_axis(1.25), 1.25);
+        let d: Days = Hours::new(36.0).into_axis();
+        assert!((d.value() - 1.5).abs() < 1e-15);
+        let d: Days = Minutes::new(720.0).into_axis();
+        assert!((d.value() - 0.5).abs() < 1e-15);
+        assert_eq!(IntoAxis::<Days>::into_axis(Days::new(2.0)), Days::new(2.0));
+    }
+
+    #[test]
+    fn test_calculus_types() {
+        let v: Quantity<Per<Kilometer, Day>> =
+            ChebyCalculus::<Days>::into_derivative(Kilometers::new(2.0));
+        assert_eq!(v.value(), 2.0);
+        let a: Quantity<Prod<Kilometer, Second>> =
+            ChebyCalculus::<Quantity<Second>>::into_integral(Kilometers::new(4.0));
+        assert_eq!(a.value(), 4.0);
+        let rate: Quantity<Per<Unitless, Day>> = ChebyCalculus::<Days>::into_derivative(0.5);
+        assert_eq!(rate.value(), 0.5);
+
+        let p = Vector::new([Kilometers::new(1.0), Kilometers::new(-1.0)]);
+        let dp: Vector<Quantity<Per<Kilometer, Day>>, 2> =
+            ChebyCalculus::<Days>::into_derivative(p);
+        assert_eq!(dp[1].value(), -1.0);
+
+        // An f64 axis keeps the unit of the values.
+        let same: Kilometers = ChebyCalculus::<f64>::into_derivative(Kilometers::new(7.0));
+        assert_eq!(same, Kilometers::new(7.0));
+    }
+}
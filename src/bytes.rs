@@ -0,0 +1,88 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! Byte-order handling shared by the binary file readers.
+
+/// Byte order of a binary file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endian {
+    /// Least significant byte first (x86, ARM).
+    Little,
+    /// Most significant byte first (SPARC, POWER).
+    Big,
+}
+
+impl Endian {
+    /// The `u32` at byte `at`.
+    #[inline]
+    pub(crate) fn u32(self, bytes: &[u8], at: usize) -> u32 {
+        let b = bytes[at..at + 4].try_into().unwrap();
+        match self {
+            Endian::Little => u32::from_le_bytes(b),
+            Endian::Big => u32::from_be_bytes(b),
+        }
+    }
+
+    /// The `i32` at byte `at`.
+    #[cfg(feature = "spk")]
+    #[inline]
+    pub(crate) fn i32(self, bytes: &[u8], at: usize) -> i32 {
+        self.u32(bytes, at) as i32
+    }
+
+    /// The `f64` at byte `at`.
+    #[inline]
+    pub(crate) fn f64(self, bytes: &[u8], at: usize) -> f64 {
+        let b = bytes[at..at + 8].try_into().unwrap();
+        match self {
+            Endian::Little => f64::from_le_bytes(b),
+            Endian::Big => f64::from_be_bytes(b),
+        }
+    }
+
+    /// Append `x` to `out`.
+    #[cfg(feature = "spk")]
+    #[inline]
+    pub(crate) fn put_i32(self, out: &mut Vec<u8>, x: i32) {
+        out.extend(match self {
+            Endian::Little => x.to_le_bytes(),
+            Endian::Big => x.to_be_bytes(),
+        });
+    }
+
+    /// Append `x` to `out`.
+    #[cfg(feature = "spk")]
+    #[inline]
+    pub(crate) fn put_f64(self, out: &mut Vec<u8>, x: f64) {
+        out.extend(match self {
+            Endian::Little => x.to_le_bytes(),
+            Endian::Big => x.to_be_bytes(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endian_reads() {
+        let bytes = [1, 0, 0, 0, 0, 0, 0, 1];
+        assert_eq!(Endian::Little.u32(&bytes, 0), 1);
+        assert_eq!(Endian::Big.u32(&bytes, 0), 1 << 24);
+        assert_eq!(Endian::Big.u32(&bytes, 4), 1);
+        #[cfg(feature = "spk")]
+        assert_eq!(Endian::Little.i32(&(-6_i32).to_le_bytes(), 0), -6);
+        #[cfg(feature = "spk")]
+        {
+            let mut out = Vec::new();
+            Endian::Big.put_i32(&mut out, -6);
+            Endian::Big.put_f64(&mut out, 0.25);
+            assert_eq!(Endian::Big.i32(&out, 0), -6);
+            assert_eq!(Endian::Big.f64(&out, 4), 0.25);
+        }
+        let x = 2_451_545.0_f64;
+        assert_eq!(Endian::Little.f64(&x.to_le_bytes(), 0), x);
+        assert_eq!(Endian::Big.f64(&x.to_be_bytes(), 0), x);
+    }
+}
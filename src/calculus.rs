@@ -0,0 +1,266 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! Coefficient-space calculus on Chebyshev series.
+//!
+//! Differentiation uses the standard backward recurrence
+//!
+//! ```text
+//! c'_{k-1} = c'_{k+1} + 2k c_k        (k = n-1, …, 1),   c'_0 ← c'_0 / 2
+//! ```
+//!
+//! and integration maps a series with `n` coefficients to one with
+//! `n + 1` using the identities
+//!
+//! ```text
+//! ∫ T_0 dτ = T_1
+//! ∫ T_1 dτ = T_2 / 4
+//! ∫ T_k dτ = T_{k+1} / (2(k+1)) − T_{k-1} / (2(k-1))      (k ≥ 2)
+//! ```
+//!
+//! and definite integrals over `[-1, 1]` follow from the Clenshaw–Curtis
+//! weights `∫ T_k dτ = 2 / (1 − k²)` for even `k` (zero for odd `k`).
+//!
+//! Everything here works in the normalised variable `τ`; the segment and
+//! table types apply the `dt = half · dτ` scaling.
+
+use crate::cursor::SegmentLookup;
+use crate::scalar::{field, ChebyScalar};
+
+/// Write the derivative coefficients of `coeffs` into
+/// `out[..coeffs.len() - 1]`, zeroing the rest of `out`.
+fn derivative_into<T: ChebyScalar>(coeffs: &[T], out: &mut [T]) {
+    out.iter_mut().for_each(|d| *d = T::zero());
+    let n = coeffs.len();
+    if n < 2 {
+        return;
+    }
+    // `d_kp1` and `d_k` hold c'_{k+1} and c'_k.
+    let (mut d_kp1, mut d_k) = (T::zero(), T::zero());
+    for k in (1..n).rev() {
+        let d_km1 = d_kp1 + coeffs[k] * field::<T>((2 * k) as f64);
+        out[k - 1] = d_km1;
+        d_kp1 = d_k;
+        d_k = d_km1;
+    }
+    out[0] = out[0] * field::<T>(0.5);
+}
+
+/// Chebyshev coefficients of the derivative `df/dτ`.
+///
+/// The output keeps the length `N` so it can be stored alongside the
+/// input; its last coefficient is always zero.
+///
+/// # Example
+///
+/// ```
+/// use cheby::{derivative_coeffs, evaluate, evaluate_derivative};
+///
+/// let c = [0.5_f64, -1.0, 2.0, 0.25];
+/// let d = derivative_coeffs(&c);
+/// assert_eq!(d[3], 0.0);
+/// assert!((evaluate(&d, 0.3) - evaluate_derivative(&c, 0.3)).abs() < 1e-14);
+/// ```
+pub fn derivative_coeffs<T: ChebyScalar, const N: usize>(coeffs: &[T; N]) -> [T; N] {
+    let mut out = [T::zero(); N];
+    derivative_into(coeffs, &mut out);
+    out
+}
+
+/// Chebyshev coefficients of the derivative `df/dτ`, for a runtime-sized
+/// series.
+///
+/// Returns `coeffs.len() - 1` coefficients (none for an empty or constant
+/// series).
+pub fn derivative_coeffs_dyn<T: ChebyScalar>(coeffs: &[T]) -> Vec<T> {
+    let mut out = vec![T::zero(); coeffs.len().saturating_sub(1)];
+    derivative_into(coeffs, &mut out);
+    out
+}
+
+/// Coefficient `C_k` (`k ≥ 1`) of the antiderivative of `coeffs`.
+#[inline]
+fn integral_coeff<T: ChebyScalar>(coeffs: &[T], k: usize) -> T {
+    let c = |j: usize| coeffs.get(j).copied().unwrap_or_else(T::zero);
+    if k == 1 {
+        c(0) - c(2) * field::<T>(0.5)
+    } else {
+        (c(k - 1) - c(k + 1)) / field::<T>((2 * k) as f64)
+    }
+}
+
+/// Chebyshev coefficients of the antiderivative `F(τ) = ∫ f dτ`.
+///
+/// Returns `coeffs.len() + 1` coefficients, with the integration constant
+/// chosen so that `F(-1) = constant`.
+///
+/// # Example
+///
+/// ```
+/// use cheby::{evaluate, integral_coeffs};
+///
+/// // f(τ) = 3 (constant) → F(τ) = 3(τ + 1) + 2
+/// let big_f = integral_coeffs(&[3.0_f64], 2.0);
+/// assert!((evaluate(&big_f, -1.0) - 2.0).abs() < 1e-15);
+/// assert!((evaluate(&big_f, 1.0) - 8.0).abs() < 1e-15);
+/// ```
+pub fn integral_coeffs<T: ChebyScalar>(coeffs: &[T], constant: T) -> Vec<T> {
+    let n = coeffs.len();
+    let mut out = Vec::with_capacity(n + 1);
+    out.push(T::zero());
+    // T_k(-1) = (-1)^k, so F(-1) = C_0 + Σ (-1)^k C_k.
+    let mut at_minus_one = T::zero();
+    for k in 1..=n {
+        let ck = integral_coeff(coeffs, k);
+        at_minus_one = if k % 2 == 0 {
+            at_minus_one + ck
+        } else {
+            at_minus_one - ck
+        };
+        out.push(ck);
+    }
+    out[0] = constant - at_minus_one;
+    out
+}
+
+/// Definite integral `∫_{-1}^{1} f(τ) dτ` of a Chebyshev series.
+///
+/// # Example
+///
+/// ```
+/// use cheby::definite_integral;
+///
+/// // f(τ) = T_2(τ) = 2τ² − 1 integrates to -2/3 over [-1, 1].
+/// assert!((definite_integral(&[0.0_f64, 0.0, 1.0]) + 2.0 / 3.0).abs() < 1e-15);
+/// ```
+pub fn definite_integral<T: ChebyScalar>(coeffs: &[T]) -> T {
+    coeffs
+        .iter()
+        .enumerate()
+        .step_by(2)
+        .fold(T::zero(), |acc, (k, &c)| {
+            acc + c * field::<T>(2.0 / (1.0 - (k * k) as f64))
+        })
+}
+
+/// Evaluate the antiderivative of `coeffs` with `C_0 = 0` at `tau`,
+/// without materialising its coefficients.
+///
+/// Only differences of this function are meaningful.
+#[inline]
+pub(crate) fn evaluate_antiderivative<T: ChebyScalar>(coeffs: &[T], tau: T::Field) -> T {
+    let two_tau = tau + tau;
+    let mut b_kp1 = T::zero();
+    let mut b_kp2 = T::zero();
+    for k in (1..=coeffs.len()).rev() {
+        let b_k = b_kp1 * two_tau - b_kp2 + integral_coeff(coeffs, k);
+        b_kp2 = b_kp1;
+        b_kp1 = b_k;
+    }
+    b_kp1 * tau - b_kp2
+}
+
+/// `∫_a^b` over a table, summing per-segment integrals across boundaries.
+///
+/// Returns `None` if either limit lies outside `[start, end]` of the
+/// table. Reversed limits give the negated integral.
+pub(crate) fn integrate_table<L, T>(
+    table: &L,
+    a: f64,
+    b: f64,
+    integrate: impl Fn(&L::Segment, f64, f64) -> T,
+) -> Option<T>
+where
+    L: SegmentLookup,
+    T: ChebyScalar,
+{
+    if b < a {
+        return integrate_table(table, b, a, integrate).map(|v| T::zero() - v);
+    }
+    let segments = table.segments();
+    let last = segments.len().checked_sub(1)?;
+    let (lo, _) = table.segment_bounds(0);
+    let (_, hi) = table.segment_bounds(last);
+    if !(a >= lo && b <= hi) {
+        return None;
+    }
+
+    // The closed upper end belongs to the last segment.
+    let locate = |t: f64| table.segment_index(t).unwrap_or(last);
+    let (ia, ib) = (locate(a), locate(b));
+    if ia == ib {
+        return Some(integrate(&segments[ia], a, b));
+    }
+
+    let mut acc = integrate(&segments[ia], a, table.segment_bounds(ia).1);
+    for (i, seg) in segments.iter().enumerate().take(ib).skip(ia + 1) {
+        let (lo, hi) = table.segment_bounds(i);
+        acc = acc + integrate(seg, lo, hi);
+    }
+    Some(acc + integrate(&segments[ib], table.segment_bounds(ib).0, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::{evaluate, evaluate_derivative};
+
+    #[test]
+    fn test_derivative_coeffs() {
+        // f = 4τ⁴ − 6τ² + 2τ + 2.5, f' = 16τ³ − 12τ + 2 = 2 + 4T_3.
+        let coeffs = [1.0_f64, 2.0, -1.0, 0.0, 0.5];
+        let d = derivative_coeffs(&coeffs);
+        let want = [2.0, 0.0, 0.0, 4.0, 0.0];
+        for (got, want) in d.iter().zip(&want) {
+            assert!((got - want).abs() < 1e-14);
+        }
+        assert_eq!(derivative_coeffs_dyn(&coeffs), d[..4].to_vec());
+        assert!(derivative_coeffs_dyn::<f64>(&[3.0]).is_empty());
+        assert!(derivative_coeffs_dyn::<f64>(&[]).is_empty());
+        assert_eq!(derivative_coeffs(&[3.0]), [0.0]);
+    }
+
+    #[test]
+    fn test_derivative_inverts_integral() {
+        let coeffs = [0.7_f64, -1.2, 0.4, 2.5, -0.3, 0.05];
+        let back = derivative_coeffs_dyn(&integral_coeffs(&coeffs, 1.25));
+        for (got, want) in back.iter().zip(&coeffs) {
+            assert!((got - want).abs() < 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_integral_differentiates_back() {
+        let coeffs = [0.7_f64, -1.2, 0.4, 2.5, -0.3, 0.05];
+        let big_f = integral_coeffs(&coeffs, -4.0);
+        assert_eq!(big_f.len(), coeffs.len() + 1);
+        assert!((evaluate(&big_f, -1.0) + 4.0).abs() < 1e-14);
+        for &tau in &[-0.9, -0.3, 0.0, 0.45, 1.0] {
+            let d = evaluate_derivative(&big_f, tau);
+            assert!((d - evaluate(&coeffs, tau)).abs() < 1e-13, "tau={tau}");
+        }
+    }
+
+    #[test]
+    fn test_antiderivative_matches_coeffs() {
+        let coeffs = [1.5_f64, 0.25, -0.75, 0.1];
+        let big_f = integral_coeffs(&coeffs, 0.0);
+        for &tau in &[-1.0, -0.2, 0.6, 1.0] {
+            let via_coeffs = evaluate(&big_f, tau) - evaluate(&big_f, -1.0);
+            let direct =
+                evaluate_antiderivative(&coeffs, tau) - evaluate_antiderivative(&coeffs, -1.0);
+            assert!((via_coeffs - direct).abs() < 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_definite_integral() {
+        // ∫ (1 + τ + τ³) dτ over [-1, 1] = 2; τ³ = (3T_1 + T_3)/4.
+        let coeffs = [1.0_f64, 1.75, 0.0, 0.25];
+        assert!((definite_integral(&coeffs) - 2.0).abs() < 1e-15);
+        let big_f = integral_coeffs(&coeffs, 0.0);
+        assert!((evaluate(&big_f, 1.0) - 2.0).abs() < 1e-14);
+        assert_eq!(definite_integral::<f64>(&[]), 0.0);
+        assert_eq!(integral_coeffs(&[] as &[f64], 3.0), vec![3.0]);
+    }
+}
@@ -0,0 +1,446 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! Rust source generation for tables compiled into a binary.
+//!
+//! A [`RustModule`] turns fitted [`ChebySegmentTable`]s into the source of
+//! a Rust module, typically from a build script. Each table becomes a
+//! `static` array of coefficients and a `static`
+//! [`ChebySegmentTableView`] over it, so the
+//! program evaluates the table with no allocation, I/O or validation at
+//! run time.
+//!
+//! Only tables of `f64` numbers (`f64`, `f64` quantities and [`Vector`]s
+//! of them) can be embedded. Value and axis types are written with their
+//! public paths ([`RustType`]): this crate's items under
+//! [`with_crate_path`](RustModule::with_crate_path), and the unit of each
+//! quantity as given to [`with_unit`](RustModule::with_unit).
+//!
+//! # Example
+//!
+//! In `build.rs`:
+//!
+//! ```no_run
+//! use cheby::codegen::RustModule;
+//! use cheby::ChebySegmentTable;
+//!
+//! let table = ChebySegmentTable::<f64, 12>::from_fn(f64::sin, 0.0, 8.0, 0.5);
+//! let mut module = RustModule::new("tables").with_doc("Precomputed tables.");
+//! module.push_table("SINE", &table)?;
+//! let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("tables.rs");
+//! module.write(out)?;
+//! # Ok::<(), cheby::codegen::CodegenError>(())
+//! ```
+//!
+//! and in the crate:
+//!
+//! ```text
+//! include!(concat!(env!("OUT_DIR"), "/tables.rs"));
+//!
+//! let y = tables::SINE.eval(1.25).unwrap();
+//! ```
+//!
+//! [`Vector`]: crate::Vector
+
+use crate::scalar::Vector;
+use crate::segment::ChebySegmentTable;
+use crate::storage::{Storable, StorableAxis, StorageError};
+use crate::view::ChebySegmentTableView;
+use qtty::{Quantity, Unit};
+use std::any::TypeId;
+use std::fmt::{self, Write as _};
+use std::io;
+use std::path::Path;
+
+// ─────────────────────────────────────────────────────────────────────────
+// Errors
+// ─────────────────────────────────────────────────────────────────────────
+
+/// Why a table could not be added to a [`RustModule`] or the module
+/// could not be written.
+#[derive(Debug)]
+pub enum CodegenError {
+    /// Writing the source file failed.
+    Io(io::Error),
+    /// A module or table name is not a valid identifier of the expected
+    /// case, is a keyword, or clashes with a static already emitted.
+    Name(String),
+    /// No path was given for the unit with this type name; see
+    /// [`RustModule::with_unit`].
+    Unit(&'static str),
+    /// The table cannot be embedded: it holds `f32` numbers, an invalid
+    /// domain or non-finite coefficients.
+    Table(StorageError),
+}
+
+impl fmt::Display for CodegenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "cannot write generated module: {e}"),
+            Self::Name(message) => write!(f, "invalid name: {message}"),
+            Self::Unit(unit) => write!(
+                f,
+                "no path for unit {unit}; give one with RustModule::with_unit"
+            ),
+            Self::Table(e) => write!(f, "cannot embed table: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for CodegenError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Table(e) => Some(e),
+            Self::Name(_) | Self::Unit(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for CodegenError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// Module builder
+// ─────────────────────────────────────────────────────────────────────────
+
+/// The source of a Rust module embedding segment tables as static data.
+///
+/// For a table pushed as `NAME`, the module holds `NAME_DATA`, an
+/// `[f64; K]` array in the layout of
+/// [`ChebySegmentTableView::from_f64s`], and `NAME`, a
+/// `ChebySegmentTableView<'static, T, N, X>` over it.
+#[derive(Debug, Clone)]
+pub struct RustModule {
+    name: String,
+    doc: String,
+    crate_path: String,
+    units: Vec<(TypeId, String)>,
+    tables: Vec<String>,
+    items: String,
+}
+
+impl RustModule {
+    /// An empty module called `name`, which must be a lower snake case
+    /// identifier and not a keyword. Validated when the source is
+    /// produced.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            doc: String::new(),
+            crate_path: "::cheby".to_string(),
+            units: Vec::new(),
+            tables: Vec::new(),
+            items: String::new(),
+        }
+    }
+
+    /// Set the module's doc comment.
+    pub fn with_doc(mut self, doc: impl Into<String>) -> Self {
+        self.doc = doc.into();
+        self
+    }
+
+    /// Set the path the generated code uses for this crate, `::cheby` by
+    /// default; e.g. `::my_crate::cheby` when it is re-exported.
+    pub fn with_crate_path(mut self, path: impl Into<String>) -> Self {
+        self.crate_path = path.into();
+        self
+    }
+
+    /// Spell the unit `U` as `path` in the generated code, e.g.
+    /// `with_unit::<qtty::Kilometer>("::qtty::Kilometer")`. Tables of
+    /// quantities can only be pushed once their units have a path.
+    pub fn with_unit<U: Unit>(mut self, path: impl Into<String>) -> Self {
+        self.units.retain(|(id, _)| *id != TypeId::of::<U>());
+        self.units.push((TypeId::of::<U>(), path.into()));
+        self
+    }
+
+    /// The path the generated code uses for this crate.
+    #[inline]
+    pub fn crate_path(&self) -> &str {
+        &self.crate_path
+    }
+
+    /// The path given to [`with_unit`](Self::with_unit) for `U`, if any.
+    pub fn unit_path<U: Unit>(&self) -> Option<&str> {
+        self.units
+            .iter()
+            .find(|(id, _)| *id == TypeId::of::<U>())
+            .map(|(_, path)| path.as_str())
+    }
+
+    /// Number of tables in the module.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.tables.len()
+    }
+
+    /// Whether the module holds no tables.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// Add `table` as the statics `NAME_DATA` and `NAME`.
+    ///
+    /// `name` must be an upper snake case identifier, and neither `NAME`
+    /// nor `NAME_DATA` may clash with the statics of an earlier table. The
+    /// table must hold `f64` numbers, all finite, and pass the checks of
+    /// [`ChebySegmentTableView::from_f64s`], and the units of `T` and `X`
+    /// need a path (see [`with_unit`](Self::with_unit)).
+    pub fn push_table<T, const N: usize, X>(
+        &mut self,
+        name: &str,
+        table: &ChebySegmentTable<T, N, X>,
+    ) -> Result<(), CodegenError>
+    where
+        T: Storable + RustType,
+        X: StorableAxis + RustType,
+    {
+        check_name(name, char::is_ascii_uppercase, "upper")?;
+        let data_name = format!("{name}_DATA");
+        if let Some(used) = [name, &data_name].into_iter().find(|n| self.is_emitted(n)) {
+            return Err(CodegenError::Name(format!("{used} is already in use")));
+        }
+        let view = format!(
+            "{}::ChebySegmentTableView<'static, {}, {N}, {}>",
+            self.crate_path,
+            T::rust_type(self)?,
+            X::rust_type(self)?
+        );
+        let data = table.to_f64s();
+        ChebySegmentTableView::<T, N, X>::from_f64s(&data, table.start(), table.segment_len())
+            .map_err(CodegenError::Table)?;
+        if let Some(k) = data.iter().position(|x| !x.is_finite()) {
+            return Err(CodegenError::Table(StorageError::Invalid(format!(
+                "number {k} is {}",
+                data[k]
+            ))));
+        }
+
+        let stride = 2 + N * T::COMPONENTS;
+        let krate = &self.crate_path;
+        let out = &mut self.items;
+        // Writing to a `String` cannot fail.
+        let _ = writeln!(
+            out,
+            "\n    /// `mid`, `half` and the {N} values of each of the {} segments of [`{name}`].",
+            table.len()
+        );
+        let _ = writeln!(out, "    pub static {data_name}: [f64; {}] = [", data.len());
+        for segment in data.chunks(stride) {
+            let _ = writeln!(out, "        {:?}, {:?},", segment[0], segment[1]);
+            for value in segment[2..].chunks(T::COMPONENTS) {
+                let numbers: Vec<String> = value.iter().map(|x| format!("{x:?}")).collect();
+                let _ = writeln!(out, "        {},", numbers.join(", "));
+            }
+        }
+        let _ = writeln!(out, "    ];");
+        let _ = writeln!(
+            out,
+            "\n    /// {} segments of {N} coefficients from {:?} to {:?}.",
+            table.len(),
+            table.start().value(),
+            table.end().value()
+        );
+        let _ = writeln!(
+            out,
+            "    pub static {name}: {view} =\n        {krate}::ChebySegmentTableView::from_f64s_unchecked(&{name}_DATA, {:?}, {:?});",
+            table.start().value(),
+            table.segment_len().value()
+        );
+        self.tables.push(name.to_string());
+        Ok(())
+    }
+
+    /// Whether `ident` is one of the statics emitted for an earlier table.
+    fn is_emitted(&self, ident: &str) -> bool {
+        self.tables
+            .iter()
+            .any(|t| ident == t || ident.strip_suffix("_DATA") == Some(t))
+    }
+
+    /// The module source.
+    pub fn to_source(&self) -> Result<String, CodegenError> {
+        check_name(&self.name, char::is_ascii_lowercase, "lower")?;
+        let mut out = String::from("// @generated by cheby::codegen. Do not edit.\n\n");
+        for line in self.doc.lines() {
+            let _ = writeln!(out, "///{}{line}", if line.is_empty() { "" } else { " " });
+        }
+        let _ = writeln!(out, "pub mod {} {{{}}}", self.name, self.items);
+        Ok(out)
+    }
+
+    /// Write the module source to `path`.
+    ///
+    /// An existing file with the same contents is left untouched, so that
+    /// Cargo does not rebuild what depends on it.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), CodegenError> {
+        let source = self.to_source()?;
+        let path = path.as_ref();
+        if std::fs::read(path).is_ok_and(|old| old == source.as_bytes()) {
+            return Ok(());
+        }
+        std::fs::write(path, source)?;
+        Ok(())
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// Type names
+// ─────────────────────────────────────────────────────────────────────────
+
+/// A value or axis type that generated code can name.
+///
+/// Implemented for `f64`, `f32`, `qtty` quantities and [`Vector`]s of
+/// them.
+pub trait RustType {
+    /// The type spelled with public paths for `module`: this crate's items
+    /// under its [`crate_path`](RustModule::crate_path) and units as given
+    /// to [`with_unit`](RustModule::with_unit).
+    fn rust_type(module: &RustModule) -> Result<String, CodegenError>;
+}
+
+impl RustType for f64 {
+    #[inline]
+    fn rust_type(_module: &RustModule) -> Result<String, CodegenError> {
+        Ok("f64".to_string())
+    }
+}
+
+impl RustType for f32 {
+    #[inline]
+    fn rust_type(_module: &RustModule) -> Result<String, CodegenError> {
+        Ok("f32".to_string())
+    }
+}
+
+impl<U: Unit> RustType for Quantity<U> {
+    fn rust_type(module: &RustModule) -> Result<String, CodegenError> {
+        let unit = module
+            .unit_path::<U>()
+            .ok_or(CodegenError::Unit(std::any::type_name::<U>()))?;
+        Ok(format!("::qtty::Quantity<{unit}>"))
+    }
+}
+
+impl<T: RustType, const D: usize> RustType for Vector<T, D> {
+    fn rust_type(module: &RustModule) -> Result<String, CodegenError> {
+        Ok(format!(
+            "{}::Vector<{}, {D}>",
+            module.crate_path,
+            T::rust_type(module)?
+        ))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// Names
+// ─────────────────────────────────────────────────────────────────────────
+
+/// Strict and reserved keywords of the 2024 edition, which cannot name a
+/// module or a static.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Check that `name` is a snake case identifier whose letters satisfy
+/// `case`, and not a keyword.
+fn check_name(name: &str, case: fn(&char) -> bool, which: &str) -> Result<(), CodegenError> {
+    if KEYWORDS.contains(&name) {
+        return Err(CodegenError::Name(format!("{name:?} is a keyword")));
+    }
+    let valid = name.chars().next().is_some_and(|c| c == '_' || case(&c))
+        && name
+            .chars()
+            .all(|c| c == '_' || c.is_ascii_digit() || case(&c))
+        && name != "_";
+    if valid {
+        Ok(())
+    } else {
+        Err(CodegenError::Name(format!(
+            "{name:?} is not a {which} snake case identifier"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vec3;
+
+    #[test]
+    fn test_rust_type() {
+        let module = RustModule::new("tables")
+            .with_crate_path("crate::cheby")
+            .with_unit::<qtty::Kilometer>("::qtty::Kilometer")
+            .with_unit::<qtty::Per<qtty::Meter, qtty::Second>>("::qtty::MetersPerSecond");
+        assert_eq!(f64::rust_type(&module).unwrap(), "f64");
+        assert_eq!(
+            Vec3::<qtty::Kilometers>::rust_type(&module).unwrap(),
+            "crate::cheby::Vector<::qtty::Quantity<::qtty::Kilometer>, 3>"
+        );
+        assert_eq!(
+            Quantity::<qtty::Per<qtty::Meter, qtty::Second>>::rust_type(&module).unwrap(),
+            "::qtty::Quantity<::qtty::MetersPerSecond>"
+        );
+        assert_eq!(module.unit_path::<qtty::Meter>(), None);
+        let err = qtty::Days::rust_type(&module).unwrap_err();
+        assert!(matches!(err, CodegenError::Unit(_)), "{err}");
+
+        let module = module.with_unit::<qtty::Kilometer>("::qtty::length::Kilometer");
+        assert_eq!(
+            module.unit_path::<qtty::Kilometer>(),
+            Some("::qtty::length::Kilometer")
+        );
+    }
+
+    #[test]
+    fn test_rejected_input() {
+        let table = ChebySegmentTable::<f64, 3>::from_fn(|t| t, 0.0, 1.0, 1.0);
+        let mut module = RustModule::new("tables");
+        for name in ["", "_", "lower", "9LIVES", "A-B", "ÄPFEL"] {
+            let err = module.push_table(name, &table).unwrap_err();
+            assert!(matches!(err, CodegenError::Name(_)), "{name}: {err}");
+        }
+        module.push_table("_A1", &table).unwrap();
+        for name in ["_A1", "_A1_DATA"] {
+            let err = module.push_table(name, &table).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!("invalid name: {name} is already in use")
+            );
+        }
+        module.push_table("B_DATA", &table).unwrap();
+        let err = module.push_table("B", &table).unwrap_err();
+        assert_eq!(err.to_string(), "invalid name: B_DATA is already in use");
+
+        let single = ChebySegmentTable::<f32, 3>::from_fn(|t| t as f32, 0.0, 1.0, 1.0);
+        let err = module.push_table("SINGLE", &single).unwrap_err();
+        assert!(matches!(
+            err,
+            CodegenError::Table(StorageError::Shape { .. })
+        ));
+        let nan = ChebySegmentTable::<f64, 3>::from_fn(|_| f64::NAN, 0.0, 1.0, 1.0);
+        let err = module.push_table("NAN", &nan).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot embed table: invalid table: number 2 is NaN"
+        );
+        assert_eq!(module.len(), 2);
+
+        for name in ["Tables", "type", "mod", "self", "crate", "super"] {
+            let err = RustModule::new(name).to_source().unwrap_err();
+            assert!(matches!(err, CodegenError::Name(_)), "{name}: {err}");
+        }
+    }
+}
@@ -0,0 +1,388 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! Sequential-access cursors over segment tables.
+//!
+//! Every `eval` on a table locates the segment from scratch. When queries
+//! arrive in (mostly) monotonic order, as in a propagator stepping through
+//! time, a [`Cursor`] remembers the last segment used: a query inside it
+//! needs no lookup at all, a query in an adjacent segment (in either
+//! direction) steps by one, and anything further away falls back to the
+//! table's own lookup.
+//!
+//! For uniform tables the cached bounds are `start + i · segment_len`, so
+//! a time within rounding error of a boundary may be attributed to the
+//! segment on the other side of it than the table's own lookup picks;
+//! both segments are valid there.
+//!
+//! Cursors work with any table implementing [`SegmentLookup`]: the uniform
+//! [`ChebySegmentTable`], [`DynChebySegmentTable`] and
+//! [`ChebyVecSegmentTable`], and the non-uniform [`ChebyVarTable`]. Obtain
+//! one with the tables' `cursor()` method.
+
+use crate::axis::ChebyAxis;
+use crate::scalar::ChebyScalar;
+use crate::segment::{ChebySegment, ChebySegmentTable, ChebyVarTable};
+use crate::series::{DynChebySegment, DynChebySegmentTable};
+use crate::vector::{ChebyVecSegment, ChebyVecSegmentTable};
+
+/// A table of contiguous segments that can be searched by time.
+pub trait SegmentLookup {
+    /// The segment type stored in the table.
+    type Segment;
+
+    /// The segments, in chronological order.
+    fn segments(&self) -> &[Self::Segment];
+
+    /// The half-open interval `[lo, hi)` covered by segment `idx`.
+    fn segment_bounds(&self, idx: usize) -> (f64, f64);
+
+    /// Index of the segment containing `t`, or `None` if out of range.
+    fn segment_index(&self, t: f64) -> Option<usize>;
+}
+
+/// A segment that can be evaluated at a physical time `t`.
+pub trait SegmentEval {
+    /// The value type produced by evaluation.
+    type Output;
+
+    /// Evaluate at physical time `t`.
+    fn eval(&self, t: f64) -> Self::Output;
+
+    /// Evaluate the derivative `d/dt` at physical time `t`.
+    fn eval_derivative(&self, t: f64) -> Self::Output;
+
+    /// Evaluate value and derivative at `t` in one pass.
+    fn eval_both(&self, t: f64) -> (Self::Output, Self::Output);
+}
+
+/// A cursor caching the most recently used segment of a table.
+///
+/// Created by `cursor()` on [`ChebySegmentTable`], [`ChebyVarTable`],
+/// [`DynChebySegmentTable`] and [`ChebyVecSegmentTable`].
+///
+/// # Example
+///
+/// ```
+/// use cheby::ChebySegmentTable;
+///
+/// let table: ChebySegmentTable<f64, 11> = ChebySegmentTable::from_fn(f64::sin, 0.0, 10.0, 1.0);
+/// let mut cursor = table.cursor();
+/// for i in 0..1000 {
+///     let t = i as f64 * 0.01;
+///     assert_eq!(cursor.eval(t), table.eval(t));
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Cursor<'a, L: SegmentLookup> {
+    table: &'a L,
+    /// Cached segment index and its `[lo, hi)` bounds.
+    current: Option<(usize, f64, f64)>,
+}
+
+impl<L: SegmentLookup> Clone for Cursor<'_, L> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<L: SegmentLookup> Copy for Cursor<'_, L> {}
+
+impl<'a, L: SegmentLookup> Cursor<'a, L> {
+    /// Create a cursor over `table` with no cached segment.
+    #[inline]
+    pub fn new(table: &'a L) -> Self {
+        Self {
+            table,
+            current: None,
+        }
+    }
+
+    /// The table this cursor reads from.
+    #[inline]
+    pub fn table(&self) -> &'a L {
+        self.table
+    }
+
+    /// Index of the cached segment, if any.
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|(idx, _, _)| idx)
+    }
+
+    /// Forget the cached segment.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.current = None;
+    }
+
+    /// Move to the segment containing `t` and return its index, or `None`
+    /// (leaving the cache untouched) if `t` is outside the table.
+    #[inline]
+    pub fn seek_index(&mut self, t: f64) -> Option<usize> {
+        if let Some((idx, lo, hi)) = self.current {
+            if t >= lo && t < hi {
+                return Some(idx);
+            }
+            let len = self.table.segments().len();
+            let neighbour = if t >= hi && idx + 1 < len {
+                Some(idx + 1)
+            } else if t < lo && idx > 0 {
+                Some(idx - 1)
+            } else {
+                None
+            };
+            if let Some(next) = neighbour {
+                let (lo, hi) = self.table.segment_bounds(next);
+                if t >= lo && t < hi {
+                    self.current = Some((next, lo, hi));
+                    return Some(next);
+                }
+            }
+        }
+
+        let idx = self.table.segment_index(t)?;
+        let (lo, hi) = self.table.segment_bounds(idx);
+        self.current = Some((idx, lo, hi));
+        Some(idx)
+    }
+
+    /// Move to the segment containing `t` and return it, or `None` if `t`
+    /// is outside the table.
+    #[inline]
+    pub fn seek(&mut self, t: f64) -> Option<&'a L::Segment> {
+        let idx = self.seek_index(t)?;
+        Some(&self.table.segments()[idx])
+    }
+}
+
+/// The value type produced by evaluating a segment of table `L`.
+type Output<L> = <<L as SegmentLookup>::Segment as SegmentEval>::Output;
+
+impl<L> Cursor<'_, L>
+where
+    L: SegmentLookup,
+    L::Segment: SegmentEval,
+{
+    /// Evaluate at `t`, returning `None` if outside the table range.
+    #[inline]
+    pub fn eval(&mut self, t: f64) -> Option<Output<L>> {
+        self.seek(t).map(|s| s.eval(t))
+    }
+
+    /// Evaluate derivative at `t`, returning `None` if outside range.
+    #[inline]
+    pub fn eval_derivative(&mut self, t: f64) -> Option<Output<L>> {
+        self.seek(t).map(|s| s.eval_derivative(t))
+    }
+
+    /// Evaluate value and derivative at `t`, returning `None` if outside range.
+    #[inline]
+    pub fn eval_both(&mut self, t: f64) -> Option<(Output<L>, Output<L>)> {
+        self.seek(t).map(|s| s.eval_both(t))
+    }
+}
+
+// ── SegmentEval implementations ─────────────────────────────────────────
+
+impl<T: ChebyScalar, const N: usize> SegmentEval for ChebySegment<T, N> {
+    type Output = T;
+
+    #[inline]
+    fn eval(&self, t: f64) -> T {
+        self.eval(t)
+    }
+
+    #[inline]
+    fn eval_derivative(&self, t: f64) -> T {
+        self.eval_derivative(t)
+    }
+
+    #[inline]
+    fn eval_both(&self, t: f64) -> (T, T) {
+        self.eval_both(t)
+    }
+}
+
+impl<T: ChebyScalar> SegmentEval for DynChebySegment<T> {
+    type Output = T;
+
+    #[inline]
+    fn eval(&self, t: f64) -> T {
+        self.eval(t)
+    }
+
+    #[inline]
+    fn eval_derivative(&self, t: f64) -> T {
+        self.eval_derivative(t)
+    }
+
+    #[inline]
+    fn eval_both(&self, t: f64) -> (T, T) {
+        self.eval_both(t)
+    }
+}
+
+impl<T: ChebyScalar, const N: usize, const D: usize> SegmentEval for ChebyVecSegment<T, N, D> {
+    type Output = [T; D];
+
+    #[inline]
+    fn eval(&self, t: f64) -> [T; D] {
+        self.eval(t)
+    }
+
+    #[inline]
+    fn eval_derivative(&self, t: f64) -> [T; D] {
+        self.eval_derivative(t)
+    }
+
+    #[inline]
+    fn eval_both(&self, t: f64) -> ([T; D], [T; D]) {
+        self.eval_both(t)
+    }
+}
+
+// ── SegmentLookup implementations ───────────────────────────────────────
+
+/// Times are plain values in the unit of the table's axis `X`.
+impl<T: ChebyScalar, const N: usize, X: ChebyAxis> SegmentLookup for ChebySegmentTable<T, N, X> {
+    type Segment = ChebySegment<T, N, X>;
+
+    #[inline]
+    fn segments(&self) -> &[Self::Segment] {
+        self.segments()
+    }
+
+    #[inline]
+    fn segment_bounds(&self, idx: usize) -> (f64, f64) {
+        self.bounds(idx)
+    }
+
+    #[inline]
+    fn segment_index(&self, t: f64) -> Option<usize> {
+        self.index_of(t)
+    }
+}
+
+/// Times are plain values in the unit of the table's axis `X`.
+impl<T: ChebyScalar, const N: usize, X: ChebyAxis> SegmentLookup for ChebyVarTable<T, N, X> {
+    type Segment = ChebySegment<T, N, X>;
+
+    #[inline]
+    fn segments(&self) -> &[Self::Segment] {
+        self.segments()
+    }
+
+    #[inline]
+    fn segment_bounds(&self, idx: usize) -> (f64, f64) {
+        (self.breakpoints()[idx], self.breakpoints()[idx + 1])
+    }
+
+    #[inline]
+    fn segment_index(&self, t: f64) -> Option<usize> {
+        self.index_of(t)
+    }
+}
+
+impl<T: ChebyScalar> SegmentLookup for DynChebySegmentTable<T> {
+    type Segment = DynChebySegment<T>;
+
+    #[inline]
+    fn segments(&self) -> &[Self::Segment] {
+        self.segments()
+    }
+
+    #[inline]
+    fn segment_bounds(&self, idx: usize) -> (f64, f64) {
+        self.bounds(idx)
+    }
+
+    #[inline]
+    fn segment_index(&self, t: f64) -> Option<usize> {
+        self.segment_index(t)
+    }
+}
+
+impl<T: ChebyScalar, const N: usize, const D: usize> SegmentLookup
+    for ChebyVecSegmentTable<T, N, D>
+{
+    type Segment = ChebyVecSegment<T, N, D>;
+
+    #[inline]
+    fn segments(&self) -> &[Self::Segment] {
+        self.segments()
+    }
+
+    #[inline]
+    fn segment_bounds(&self, idx: usize) -> (f64, f64) {
+        self.bounds(idx)
+    }
+
+    #[inline]
+    fn segment_index(&self, t: f64) -> Option<usize> {
+        self.segment_index(t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uniform_forward_and_backward() {
+        let table: ChebySegmentTable<f64, 9> = ChebySegmentTable::from_fn(f64::cos, 0.0, 5.0, 0.5);
+        let mut cursor = table.cursor();
+        assert_eq!(cursor.index(), None);
+
+        for i in 0..500 {
+            let t = i as f64 * 0.01;
+            assert_eq!(cursor.eval(t), table.eval(t), "forward t={t}");
+            assert_eq!(cursor.index(), table.segment_index(t));
+        }
+        for i in (0..500).rev() {
+            let t = i as f64 * 0.01 + 0.005;
+            assert_eq!(cursor.eval_both(t), table.eval_both(t), "backward t={t}");
+        }
+    }
+
+    #[test]
+    fn test_var_table_jumps_and_out_of_range() {
+        let edges = [0.0, 0.1, 0.5, 2.0, 2.2, 4.0];
+        let segments = edges
+            .windows(2)
+            .map(|w| {
+                let coeffs = crate::fit_from_fn(f64::sin, w[0], w[1]);
+                ChebySegment::<f64, 12>::new(coeffs, 0.5 * (w[0] + w[1]), 0.5 * (w[1] - w[0]))
+            })
+            .collect();
+        let table = ChebyVarTable::from_segments(segments).unwrap();
+        let mut cursor = table.cursor();
+
+        assert_eq!(cursor.seek_index(0.05), Some(0));
+        assert_eq!(cursor.seek_index(0.3), Some(1));
+        assert_eq!(cursor.seek_index(3.0), Some(4)); // jump
+        assert_eq!(cursor.seek_index(2.1), Some(3)); // step back
+
+        // Out-of-range queries leave the cache alone.
+        assert_eq!(cursor.eval(4.0), None);
+        assert_eq!(cursor.eval_derivative(-1.0), None);
+        assert_eq!(cursor.index(), Some(3));
+
+        cursor.reset();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.eval(1.0), table.eval(1.0));
+    }
+
+    #[test]
+    fn test_dyn_table_cursor() {
+        let table = DynChebySegmentTable::from_fn(f64::exp, 0.0, 2.0, 0.25, 10);
+        let mut cursor = table.cursor();
+        for i in 0..200 {
+            let t = i as f64 * 0.01;
+            assert_eq!(cursor.eval(t), table.eval(t));
+            assert_eq!(cursor.eval_derivative(t), table.eval_derivative(t));
+            assert_eq!(cursor.eval_both(t), table.eval_both(t));
+        }
+        assert_eq!(cursor.eval(2.0), None);
+    }
+}
@@ -0,0 +1,298 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! Fast discrete cosine transforms (DCT-II and DCT-I) via the FFT.
+//!
+//! Used by [`fit`](crate::fit) to compute Chebyshev coefficients in
+//! O(N log N). The DCT-II (Gauss–Chebyshev nodes)
+//!
+//! ```text
+//! X_j = Σ_{k=0}^{N-1} x_k · cos(πj(2k+1) / (2N))
+//! ```
+//!
+//! is reduced to a single complex FFT of length `N` with Makhoul's
+//! even/odd reordering. The DCT-I (Chebyshev–Lobatto nodes)
+//!
+//! ```text
+//! X_j = Σ''_{k=0}^{N-1} x_k · cos(πjk / (N-1))
+//! ```
+//!
+//! (`Σ''` halving the first and last terms) is half the FFT of the
+//! length-`2(N-1)` even extension of `x`.
+//!
+//! Power-of-two lengths use an iterative radix-2 FFT; all other lengths
+//! go through Bluestein's chirp-z algorithm, which re-expresses the DFT
+//! as a power-of-two circular convolution.
+//!
+//! Twiddle factors are always computed in `f64` and rounded to the data's
+//! field when applied; the data may be any [`ChebyScalar`], since the
+//! transforms only ever add values together and scale them by real
+//! numbers.
+
+use crate::scalar::{field, ChebyScalar};
+use std::f64::consts::PI;
+
+/// A complex number whose components are [`ChebyScalar`] values.
+#[derive(Clone, Copy)]
+struct Complex<T> {
+    re: T,
+    im: T,
+}
+
+impl<T: ChebyScalar> Complex<T> {
+    #[inline]
+    fn zero() -> Self {
+        Self {
+            re: T::zero(),
+            im: T::zero(),
+        }
+    }
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            re: self.re + rhs.re,
+            im: self.im + rhs.im,
+        }
+    }
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            re: self.re - rhs.re,
+            im: self.im - rhs.im,
+        }
+    }
+
+    /// Multiply by the real-valued complex number `(c, s)`.
+    #[inline]
+    fn scale(self, (c, s): (f64, f64)) -> Self {
+        let (c, s) = (field::<T>(c), field::<T>(s));
+        Self {
+            re: self.re * c - self.im * s,
+            im: self.re * s + self.im * c,
+        }
+    }
+}
+
+/// Compute the unnormalised DCT-II of `input` into `output`.
+///
+/// Both slices must have the same length.
+pub(crate) fn dct2<T: ChebyScalar>(input: &[T], output: &mut [T]) {
+    debug_assert_eq!(input.len(), output.len());
+    let n = input.len();
+    if n == 0 {
+        return;
+    }
+
+    // Makhoul reordering: even-indexed samples ascending, then
+    // odd-indexed samples descending.
+    let mut v = vec![Complex::zero(); n];
+    for (k, &x) in input.iter().enumerate() {
+        let idx = if k % 2 == 0 { k / 2 } else { n - 1 - k / 2 };
+        v[idx].re = x;
+    }
+
+    fft(&mut v);
+
+    // X_j = Re(V_j · e^{-iπj/(2N)})
+    for (j, (out, vj)) in output.iter_mut().zip(&v).enumerate() {
+        let arg = PI * j as f64 / (2.0 * n as f64);
+        *out = vj.re * field::<T>(arg.cos()) + vj.im * field::<T>(arg.sin());
+    }
+}
+
+/// Compute the DCT-I of `input` into `output`, with the first and last
+/// terms of each sum halved.
+///
+/// Both slices must have the same length, of at least 2.
+pub(crate) fn dct1<T: ChebyScalar>(input: &[T], output: &mut [T]) {
+    debug_assert_eq!(input.len(), output.len());
+    let n = input.len();
+    debug_assert!(n >= 2);
+    let m = 2 * (n - 1);
+
+    // Even extension: x_0, …, x_{n-1}, x_{n-2}, …, x_1.
+    let mut v = vec![Complex::zero(); m];
+    for (k, &x) in input.iter().enumerate() {
+        v[k].re = x;
+        if k > 0 && k < n - 1 {
+            v[m - k].re = x;
+        }
+    }
+
+    fft(&mut v);
+
+    for (out, vj) in output.iter_mut().zip(&v) {
+        *out = vj.re * field::<T>(0.5);
+    }
+}
+
+/// Forward DFT of arbitrary length, in place.
+fn fft<T: ChebyScalar>(data: &mut [Complex<T>]) {
+    let n = data.len();
+    if n <= 1 {
+        return;
+    }
+    if n.is_power_of_two() {
+        fft_pow2(data, &twiddles(n), false);
+    } else {
+        bluestein(data);
+    }
+}
+
+/// Bluestein's algorithm: a length-`n` DFT as a power-of-two convolution.
+fn bluestein<T: ChebyScalar>(data: &mut [Complex<T>]) {
+    let n = data.len();
+    let m = (2 * n - 1).next_power_of_two();
+
+    // Chirp w_k = e^{-iπk²/n}; k² is reduced mod 2n to keep the argument small.
+    let chirp: Vec<(f64, f64)> = (0..n)
+        .map(|k| {
+            let k2 = (k * k) % (2 * n);
+            let arg = -PI * k2 as f64 / n as f64;
+            (arg.cos(), arg.sin())
+        })
+        .collect();
+
+    let mut a = vec![Complex::zero(); m];
+    for ((dst, &src), &w) in a.iter_mut().zip(data.iter()).zip(&chirp) {
+        *dst = src.scale(w);
+    }
+
+    let mut b = vec![Complex::<f64>::zero(); m];
+    b[0] = Complex {
+        re: chirp[0].0,
+        im: -chirp[0].1,
+    };
+    for k in 1..n {
+        let conj = Complex {
+            re: chirp[k].0,
+            im: -chirp[k].1,
+        };
+        b[k] = conj;
+        b[m - k] = conj;
+    }
+
+    let tw = twiddles(m);
+    fft_pow2(&mut a, &tw, false);
+    fft_pow2(&mut b, &tw, false);
+    for (x, y) in a.iter_mut().zip(&b) {
+        *x = x.scale((y.re, y.im));
+    }
+    fft_pow2(&mut a, &tw, true);
+
+    let inv_m = 1.0 / m as f64;
+    for ((dst, src), &w) in data.iter_mut().zip(&a).zip(&chirp) {
+        *dst = src.scale((w.0 * inv_m, w.1 * inv_m));
+    }
+}
+
+/// Forward twiddle factors `e^{-2πik/n}` for `k = 0, …, n/2 - 1`.
+fn twiddles(n: usize) -> Vec<(f64, f64)> {
+    (0..n / 2)
+        .map(|k| {
+            let arg = -2.0 * PI * k as f64 / n as f64;
+            (arg.cos(), arg.sin())
+        })
+        .collect()
+}
+
+/// Iterative radix-2 FFT, in place, using the forward twiddle table from
+/// [`twiddles`]. The inverse transform is unnormalised.
+fn fft_pow2<T: ChebyScalar>(data: &mut [Complex<T>], twiddles: &[(f64, f64)], inverse: bool) {
+    let n = data.len();
+    debug_assert!(n.is_power_of_two());
+    debug_assert_eq!(twiddles.len(), n / 2);
+
+    // Bit-reversal permutation.
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            data.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { -1.0 } else { 1.0 };
+    let mut len = 2;
+    while len <= n {
+        let stride = n / len;
+        for chunk in data.chunks_exact_mut(len) {
+            let (lo, hi) = chunk.split_at_mut(len / 2);
+            for (k, (a, b)) in lo.iter_mut().zip(hi.iter_mut()).enumerate() {
+                let (c, s) = twiddles[k * stride];
+                let t = b.scale((c, sign * s));
+                *b = a.sub(t);
+                *a = a.add(t);
+            }
+        }
+        len <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dct2_direct(input: &[f64]) -> Vec<f64> {
+        let n = input.len() as f64;
+        (0..input.len())
+            .map(|j| {
+                input
+                    .iter()
+                    .enumerate()
+                    .map(|(k, x)| x * (PI * j as f64 * (2.0 * k as f64 + 1.0) / (2.0 * n)).cos())
+                    .sum()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_dct2_matches_direct_sum() {
+        for n in [1, 2, 3, 5, 8, 12, 16, 31, 64, 97] {
+            let input: Vec<f64> = (0..n)
+                .map(|k| (0.3 * k as f64).sin() + 0.1 * k as f64)
+                .collect();
+            let mut fast = vec![0.0; n];
+            dct2(&input, &mut fast);
+            for (j, (a, b)) in fast.iter().zip(dct2_direct(&input)).enumerate() {
+                assert!((a - b).abs() < 1e-11, "n={n}, j={j}: fast={a}, direct={b}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_dct1_matches_direct_sum() {
+        for n in [2, 3, 5, 9, 17, 30, 65] {
+            let input: Vec<f64> = (0..n)
+                .map(|k| (0.7 * k as f64).cos() - 0.05 * k as f64)
+                .collect();
+            let mut fast = vec![0.0; n];
+            dct1(&input, &mut fast);
+            let m = (n - 1) as f64;
+            for (j, a) in fast.iter().enumerate() {
+                let b: f64 = input
+                    .iter()
+                    .enumerate()
+                    .map(|(k, x)| {
+                        let w = if k == 0 || k == n - 1 { 0.5 } else { 1.0 };
+                        w * x * (PI * (j * k) as f64 / m).cos()
+                    })
+                    .sum();
+                assert!((a - b).abs() < 1e-11, "n={n}, j={j}: fast={a}, direct={b}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_dct2_empty() {
+        let mut out: [f64; 0] = [];
+        dct2(&[], &mut out);
+    }
+}
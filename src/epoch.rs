@@ -0,0 +1,154 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! Two-part times for full precision over long time spans.
+//!
+//! A Julian date near 2.45 million stored in one `f64` has a resolution
+//! of about 40 µs, and `(t - mid) / half` cancels the leading digits only
+//! after that resolution is lost. A [`SplitTime`] keeps the time as an
+//! unevaluated sum `hi + lo`, e.g. an integer (or half-integer) Julian day
+//! plus the fraction of the day, as in the two-part JD interface of the
+//! SPICE and JPL ephemeris readers.
+//!
+//! The `*_split` methods of [`ChebySegment`](crate::ChebySegment) and
+//! [`ChebySegmentTable`](crate::ChebySegmentTable) subtract the segment
+//! origin from `hi` first, with the rounding error of that subtraction
+//! carried along, and only then add `lo`, so `τ` keeps the full precision
+//! of both parts.
+//!
+//! Both parts have the axis type `X` of the segment or table, so on a
+//! `qtty::Days` axis a split time is two `Days`. A pair of quantities of
+//! another unit of the same dimension converts part by part; a bare
+//! `f64` pair is rejected at compile time:
+//!
+//! ```compile_fail
+//! # use cheby::ChebySegmentTable;
+//! # use qtty::Days;
+//! let table: ChebySegmentTable<f64, 8, Days> =
+//!     ChebySegmentTable::from_fn(|t: Days| t.value(), Days::new(0.0), Days::new(2.0), Days::new(1.0));
+//! table.eval_split((1.0, 0.5)); // error: `f64` is not a time
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use cheby::{ChebySegment, SplitTime};
+//!
+//! // Seconds since J2000.0 on a 32-day segment starting there.
+//! let seg = ChebySegment::new([16.0 * 86_400.0, 16.0 * 86_400.0], 2_451_561.0, 16.0);
+//! let t = SplitTime::new(2_451_545.0, 0.5 + 1.0e-9); // 86.4 µs after noon
+//! let secs: f64 = seg.eval_split(t);
+//! assert!((secs - (43_200.0 + 86.4e-6)).abs() < 1e-9);
+//! ```
+
+use crate::axis::{ChebyAxis, IntoAxis};
+
+/// A time on an axis `X` held as the unevaluated sum `hi + lo`.
+///
+/// Put the large, coarse part (e.g. the Julian day number) in `hi` and
+/// the small remainder in `lo`. Any split is valid; precision is best
+/// when `|lo|` is small compared with `|hi|`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SplitTime<X = f64> {
+    /// Leading part.
+    pub hi: X,
+    /// Trailing part.
+    pub lo: X,
+}
+
+impl<X> SplitTime<X> {
+    /// A time `hi + lo`.
+    #[inline]
+    pub const fn new(hi: X, lo: X) -> Self {
+        Self { hi, lo }
+    }
+}
+
+impl<X: ChebyAxis> SplitTime<X> {
+    /// The exact sum `a + b` as a normalised pair: `hi` is the rounded sum
+    /// and `lo` its rounding error (Knuth's two-sum).
+    #[inline]
+    pub fn from_sum(a: X, b: X) -> Self {
+        let (hi, lo) = two_sum(a.value(), b.value());
+        Self::new(X::from_value(hi), X::from_value(lo))
+    }
+
+    /// The time rounded to a single `f64`, in the unit of `X`.
+    #[inline]
+    pub fn to_f64(self) -> f64 {
+        self.hi.value() + self.lo.value()
+    }
+
+    /// `self - origin`, rounded once at the end, in the unit of `X`.
+    ///
+    /// `hi - origin` is computed with its rounding error, which is added
+    /// back together with `lo`, so no precision is lost to cancellation
+    /// when the time is close to `origin`.
+    #[inline]
+    pub fn offset_from(self, origin: X) -> f64 {
+        let (d, err) = two_sum(self.hi.value(), -origin.value());
+        d + (err + self.lo.value())
+    }
+}
+
+impl From<f64> for SplitTime {
+    #[inline]
+    fn from(t: f64) -> Self {
+        Self::new(t, 0.0)
+    }
+}
+
+/// A pair `(hi, lo)` of times convertible to the axis, converted part by
+/// part. Give both parts in the unit of `X` for full precision.
+impl<X: ChebyAxis, A: IntoAxis<X>> From<(A, A)> for SplitTime<X> {
+    #[inline]
+    fn from((hi, lo): (A, A)) -> Self {
+        Self::new(hi.into_axis(), lo.into_axis())
+    }
+}
+
+/// `(fl(a + b), a + b - fl(a + b))`, both exact.
+#[inline]
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    let bb = s - a;
+    let err = (a - (s - bb)) + (b - bb);
+    (s, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_sum_is_exact() {
+        let t = SplitTime::from_sum(2_451_545.0, 1.0e-12);
+        assert_eq!(t.hi, 2_451_545.0);
+        assert_eq!(t.lo, 1.0e-12);
+        assert_eq!(SplitTime::from_sum(1.0, 2.0), SplitTime::new(3.0, 0.0));
+    }
+
+    #[test]
+    fn test_offset_keeps_both_parts() {
+        let t = SplitTime::new(2_451_545.0, 0.25 + 3.0e-11);
+        let offset = t.offset_from(2_451_544.5);
+        assert_eq!(offset, 0.75 + 3.0e-11);
+        // The single-f64 route cannot resolve the trailing 3e-11.
+        assert_ne!(t.to_f64() - 2_451_544.5, offset);
+
+        assert_eq!(SplitTime::from((3.0, 0.5)), SplitTime::new(3.0, 0.5));
+        assert_eq!(SplitTime::from(3.0).to_f64(), 3.0);
+    }
+
+    #[test]
+    fn test_typed_parts() {
+        use qtty::{Days, Hours};
+
+        let t: SplitTime<Days> = (Days::new(2.0), Hours::new(6.0).into_axis()).into();
+        assert_eq!(t, SplitTime::new(Days::new(2.0), Days::new(0.25)));
+        let t: SplitTime<Days> = (Hours::new(48.0), Hours::new(6.0)).into();
+        assert_eq!(t.offset_from(Days::new(1.5)), 0.75);
+        let t = SplitTime::from_sum(Days::new(2_451_545.0), Days::new(1.0e-12));
+        assert_eq!((t.hi.value(), t.lo.value()), (2_451_545.0, 1.0e-12));
+    }
+}
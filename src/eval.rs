@@ -12,9 +12,14 @@
 //!
 //! where `T_k` are Chebyshev polynomials of the first kind.
 //!
-//! All functions are generic over [`ChebyScalar`](crate::ChebyScalar).
+//! All functions are generic over [`ChebyScalar`](crate::ChebyScalar);
+//! `tau` and the recurrence run in the scalar's
+//! [`Field`](crate::ChebyScalar::Field), so `f32` coefficients are
+//! evaluated in single precision. [`evaluate_widened`] and
+//! [`evaluate_both_widened`] instead widen each coefficient as it is
+//! read (e.g. `f32` storage with `f64` accumulation).
 
-use crate::ChebyScalar;
+use crate::scalar::{ChebyField, ChebyScalar};
 
 /// Evaluate a Chebyshev polynomial using the Clenshaw algorithm.
 ///
@@ -23,13 +28,76 @@ use crate::ChebyScalar;
 ///
 /// Returns the polynomial value.
 #[inline]
-pub fn evaluate<T: ChebyScalar>(coeffs: &[T], tau: f64) -> T {
-    let n = coeffs.len();
+pub fn evaluate<T: ChebyScalar>(coeffs: &[T], tau: T::Field) -> T {
+    clenshaw(coeffs.len(), |k| coeffs[k], tau)
+}
+
+/// Evaluate the derivative of a Chebyshev polynomial.
+///
+/// Returns `df/dtau` — the derivative with respect to the normalised
+/// argument `tau`. To get `df/dt` in physical time units, multiply by
+/// `dtau/dt = 1/half_width`.
+///
+/// Uses the modified Clenshaw recurrence that tracks both value and
+/// derivative simultaneously.
+#[inline]
+pub fn evaluate_derivative<T: ChebyScalar>(coeffs: &[T], tau: T::Field) -> T {
+    clenshaw_both(coeffs.len(), |k| coeffs[k], tau).1
+}
+
+/// Evaluate both the Chebyshev polynomial and its derivative in one pass.
+///
+/// Returns `(value, d_value_d_tau)`.
+#[inline]
+pub fn evaluate_both<T: ChebyScalar>(coeffs: &[T], tau: T::Field) -> (T, T) {
+    clenshaw_both(coeffs.len(), |k| coeffs[k], tau)
+}
+
+/// Evaluate a series stored in a narrower type, widening each coefficient
+/// to `T` as it is read.
+///
+/// With `f32` coefficients and `T = f64` this halves the memory footprint
+/// of a table while keeping the recurrence in double precision, so the
+/// only error beyond `f64` rounding is that of storing the coefficients.
+///
+/// # Example
+///
+/// ```
+/// use cheby::{evaluate, evaluate_widened};
+///
+/// let c64 = [0.5, -0.25, 0.125, 1.0 / 3.0];
+/// let c32 = c64.map(|c| c as f32);
+/// let wide: f64 = evaluate_widened(&c32, 0.3);
+/// assert!((wide - evaluate(&c64, 0.3)).abs() < 1e-7);
+/// ```
+#[inline]
+pub fn evaluate_widened<S, T>(coeffs: &[S], tau: T::Field) -> T
+where
+    S: Copy + Into<T>,
+    T: ChebyScalar,
+{
+    clenshaw(coeffs.len(), |k| coeffs[k].into(), tau)
+}
+
+/// Evaluate value and `τ`-derivative of a series stored in a narrower
+/// type, widening each coefficient to `T` as it is read.
+#[inline]
+pub fn evaluate_both_widened<S, T>(coeffs: &[S], tau: T::Field) -> (T, T)
+where
+    S: Copy + Into<T>,
+    T: ChebyScalar,
+{
+    clenshaw_both(coeffs.len(), |k| coeffs[k].into(), tau)
+}
+
+/// Clenshaw recurrence over `n` coefficients read through `coeff`.
+#[inline(always)]
+pub(crate) fn clenshaw<T: ChebyScalar>(n: usize, coeff: impl Fn(usize) -> T, tau: T::Field) -> T {
     if n == 0 {
         return T::zero();
     }
     if n == 1 {
-        return coeffs[0];
+        return coeff(0);
     }
 
     // Clenshaw recurrence (backwards):
@@ -37,43 +105,42 @@ pub fn evaluate<T: ChebyScalar>(coeffs: &[T], tau: f64) -> T {
     //   b_n     = 0
     //   b_k     = 2*tau*b_{k+1} - b_{k+2} + c[k]   for k = n-1, ..., 1
     //   result  = c[0] + tau*b_1 - b_2
-    let two_tau = 2.0 * tau;
+    let two_tau = tau + tau;
     let mut b_kp1 = T::zero(); // b_{k+1}
     let mut b_kp2 = T::zero(); // b_{k+2}
 
     for k in (1..n).rev() {
-        let b_k = b_kp1 * two_tau - b_kp2 + coeffs[k];
+        let b_k = b_kp1 * two_tau - b_kp2 + coeff(k);
         b_kp2 = b_kp1;
         b_kp1 = b_k;
     }
 
-    coeffs[0] + b_kp1 * tau - b_kp2
+    coeff(0) + b_kp1 * tau - b_kp2
 }
 
-/// Evaluate the derivative of a Chebyshev polynomial.
-///
-/// Returns `df/dtau` — the derivative with respect to the normalised
-/// argument `tau`. To get `df/dt` in physical time units, multiply by
-/// `dtau/dt = 1/half_width`.
-///
-/// Uses the modified Clenshaw recurrence that tracks both value and
-/// derivative simultaneously.
-#[inline]
-pub fn evaluate_derivative<T: ChebyScalar>(coeffs: &[T], tau: f64) -> T {
-    let n = coeffs.len();
-    if n <= 1 {
-        return T::zero();
+/// Clenshaw recurrence for value and `τ`-derivative.
+#[inline(always)]
+pub(crate) fn clenshaw_both<T: ChebyScalar>(
+    n: usize,
+    coeff: impl Fn(usize) -> T,
+    tau: T::Field,
+) -> (T, T) {
+    if n == 0 {
+        return (T::zero(), T::zero());
+    }
+    if n == 1 {
+        return (coeff(0), T::zero());
     }
 
-    let two_tau = 2.0 * tau;
+    let two_tau = tau + tau;
     let mut b_kp1 = T::zero();
     let mut b_kp2 = T::zero();
     let mut db_kp1 = T::zero();
     let mut db_kp2 = T::zero();
 
     for k in (1..n).rev() {
-        let b_k = b_kp1 * two_tau - b_kp2 + coeffs[k];
-        let db_k = db_kp1 * two_tau - db_kp2 + b_kp1 * 2.0;
+        let b_k = b_kp1 * two_tau - b_kp2 + coeff(k);
+        let db_k = db_kp1 * two_tau - db_kp2 + b_kp1 + b_kp1;
         b_kp2 = b_kp1;
         b_kp1 = b_k;
         db_kp2 = db_kp1;
@@ -82,40 +149,103 @@ pub fn evaluate_derivative<T: ChebyScalar>(coeffs: &[T], tau: f64) -> T {
 
     // derivative of (c[0] + tau*b_1 - b_2) w.r.t. tau
     // = b_1 + tau*db_1 - db_2
-    b_kp1 + db_kp1 * tau - db_kp2
+    let value = coeff(0) + b_kp1 * tau - b_kp2;
+    let deriv = b_kp1 + db_kp1 * tau - db_kp2;
+    (value, deriv)
 }
 
-/// Evaluate both the Chebyshev polynomial and its derivative in one pass.
+/// Evaluate the `n`-th derivative `dⁿf/dτⁿ` of a Chebyshev polynomial.
 ///
-/// Returns `(value, d_value_d_tau)`.
+/// `n = 0` is the value itself. To get `dⁿf/dtⁿ` in physical time units,
+/// divide by `half_widthⁿ`.
+///
+/// # Example
+///
+/// ```
+/// use cheby::evaluate_nth_derivative;
+///
+/// // T_3(τ) = 4τ³ − 3τ, so T_3'' = 24τ and T_3''' = 24.
+/// let c = [0.0_f64, 0.0, 0.0, 1.0];
+/// assert!((evaluate_nth_derivative(&c, 0.5, 2) - 12.0).abs() < 1e-13);
+/// assert!((evaluate_nth_derivative(&c, 0.5, 3) - 24.0).abs() < 1e-13);
+/// assert_eq!(evaluate_nth_derivative(&c, 0.5, 4), 0.0);
+/// ```
+pub fn evaluate_nth_derivative<T: ChebyScalar>(coeffs: &[T], tau: T::Field, n: usize) -> T {
+    if n == 0 {
+        return evaluate(coeffs, tau);
+    }
+    if n >= coeffs.len() {
+        return T::zero();
+    }
+    let mut d1 = vec![T::zero(); n];
+    let mut d2 = vec![T::zero(); n];
+    clenshaw_up_to(coeffs, tau, &mut d1, &mut d2);
+    d1[n - 1]
+}
+
+/// Evaluate the value and the first `K` derivatives with respect to `τ`
+/// in one Clenshaw pass.
+///
+/// Returns `(f, [f', f'', …, f⁽ᴷ⁾])`.
+///
+/// # Example
+///
+/// ```
+/// use cheby::{evaluate, evaluate_nth_derivative, evaluate_up_to};
+///
+/// let c = [0.3, -1.0, 0.5, 0.25, 0.125];
+/// let (v, [d1, d2, d3]) = evaluate_up_to::<f64, 3>(&c, 0.4);
+/// assert!((v - evaluate(&c, 0.4)).abs() < 1e-14);
+/// assert!((d3 - evaluate_nth_derivative(&c, 0.4, 3)).abs() < 1e-13);
+/// ```
+#[inline]
+pub fn evaluate_up_to<T: ChebyScalar, const K: usize>(coeffs: &[T], tau: T::Field) -> (T, [T; K]) {
+    let mut d1 = [T::zero(); K];
+    let mut d2 = [T::zero(); K];
+    let value = clenshaw_up_to(coeffs, tau, &mut d1, &mut d2);
+    (value, d1)
+}
+
+/// Clenshaw recurrence differentiated `d1.len()` times.
+///
+/// Differentiating `b_k = 2τ b_{k+1} − b_{k+2} + c[k]` `j` times gives
+///
+/// ```text
+/// b_k⁽ʲ⁾ = 2τ b_{k+1}⁽ʲ⁾ + 2j b_{k+1}⁽ʲ⁻¹⁾ − b_{k+2}⁽ʲ⁾
+/// f⁽ʲ⁾   = τ b_1⁽ʲ⁾ + j b_1⁽ʲ⁻¹⁾ − b_2⁽ʲ⁾
+/// ```
+///
+/// Returns the value; on exit `d1[j]` holds the `(j+1)`-th derivative.
+/// `d1` and `d2` must be zeroed and of equal length.
 #[inline]
-pub fn evaluate_both<T: ChebyScalar>(coeffs: &[T], tau: f64) -> (T, T) {
+fn clenshaw_up_to<T: ChebyScalar>(coeffs: &[T], tau: T::Field, d1: &mut [T], d2: &mut [T]) -> T {
     let n = coeffs.len();
     if n == 0 {
-        return (T::zero(), T::zero());
-    }
-    if n == 1 {
-        return (coeffs[0], T::zero());
+        return T::zero();
     }
 
-    let two_tau = 2.0 * tau;
+    let two_tau = tau + tau;
     let mut b_kp1 = T::zero();
     let mut b_kp2 = T::zero();
-    let mut db_kp1 = T::zero();
-    let mut db_kp2 = T::zero();
 
     for k in (1..n).rev() {
+        // Highest order first, so lower orders still hold b_{k+1}.
+        for j in (0..d1.len()).rev() {
+            let lower = if j == 0 { b_kp1 } else { d1[j - 1] };
+            let d_k = d1[j] * two_tau - d2[j] + lower * T::Field::from_f64((2 * (j + 1)) as f64);
+            d2[j] = d1[j];
+            d1[j] = d_k;
+        }
         let b_k = b_kp1 * two_tau - b_kp2 + coeffs[k];
-        let db_k = db_kp1 * two_tau - db_kp2 + b_kp1 * 2.0;
         b_kp2 = b_kp1;
         b_kp1 = b_k;
-        db_kp2 = db_kp1;
-        db_kp1 = db_k;
     }
 
-    let value = coeffs[0] + b_kp1 * tau - b_kp2;
-    let deriv = b_kp1 + db_kp1 * tau - db_kp2;
-    (value, deriv)
+    for j in (0..d1.len()).rev() {
+        let lower = if j == 0 { b_kp1 } else { d1[j - 1] };
+        d1[j] = d1[j] * tau + lower * T::Field::from_f64((j + 1) as f64) - d2[j];
+    }
+    coeffs[0] + b_kp1 * tau - b_kp2
 }
 
 #[cfg(test)]
@@ -134,20 +264,20 @@ mod tests {
     #[test]
     fn test_constant() {
         // T_0(x) = 1, so [c] evaluates to c for any tau
-        assert!((evaluate(&[3.5], 0.0) - 3.5).abs() < 1e-15);
-        assert!((evaluate(&[3.5], 0.7) - 3.5).abs() < 1e-15);
-        assert!(evaluate_derivative(&[3.5], 0.7).abs() < 1e-15);
+        assert!((evaluate::<f64>(&[3.5], 0.0) - 3.5).abs() < 1e-15);
+        assert!((evaluate::<f64>(&[3.5], 0.7) - 3.5).abs() < 1e-15);
+        assert!(evaluate_derivative::<f64>(&[3.5], 0.7).abs() < 1e-15);
     }
 
     #[test]
     fn test_linear() {
         // c[0]*T_0 + c[1]*T_1 = 2 + 3*tau
         let coeffs = [2.0, 3.0];
-        assert!((evaluate(&coeffs, 0.0) - 2.0).abs() < 1e-15);
-        assert!((evaluate(&coeffs, 1.0) - 5.0).abs() < 1e-15);
-        assert!((evaluate(&coeffs, -1.0) - (-1.0)).abs() < 1e-15);
-        assert!((evaluate_derivative(&coeffs, 0.0) - 3.0).abs() < 1e-15);
-        assert!((evaluate_derivative(&coeffs, 1.0) - 3.0).abs() < 1e-15);
+        assert!((evaluate::<f64>(&coeffs, 0.0) - 2.0).abs() < 1e-15);
+        assert!((evaluate::<f64>(&coeffs, 1.0) - 5.0).abs() < 1e-15);
+        assert!((evaluate::<f64>(&coeffs, -1.0) - (-1.0)).abs() < 1e-15);
+        assert!((evaluate_derivative::<f64>(&coeffs, 0.0) - 3.0).abs() < 1e-15);
+        assert!((evaluate_derivative::<f64>(&coeffs, 1.0) - 3.0).abs() < 1e-15);
     }
 
     #[test]
@@ -155,19 +285,78 @@ mod tests {
         // c[0]*T_0 + c[1]*T_1 + c[2]*T_2 = 1 + 0*tau + 2*(2*tau^2 - 1)
         // = 1 + 4*tau^2 - 2 = -1 + 4*tau^2
         let coeffs = [1.0, 0.0, 2.0];
-        assert!((evaluate(&coeffs, 0.0) - (-1.0)).abs() < 1e-14);
-        assert!((evaluate(&coeffs, 1.0) - 3.0).abs() < 1e-14);
+        assert!((evaluate::<f64>(&coeffs, 0.0) - (-1.0)).abs() < 1e-14);
+        assert!((evaluate::<f64>(&coeffs, 1.0) - 3.0).abs() < 1e-14);
         // derivative: 8*tau
-        assert!((evaluate_derivative(&coeffs, 0.5) - 4.0).abs() < 1e-14);
+        assert!((evaluate_derivative::<f64>(&coeffs, 0.5) - 4.0).abs() < 1e-14);
     }
 
     #[test]
     fn test_evaluate_both_matches() {
         let coeffs = [1.0, 2.0, 3.0, 4.0, 5.0];
         let tau = 0.37;
-        let (val, deriv) = evaluate_both(&coeffs, tau);
-        assert!((val - evaluate(&coeffs, tau)).abs() < 1e-14);
-        assert!((deriv - evaluate_derivative(&coeffs, tau)).abs() < 1e-14);
+        let (val, deriv) = evaluate_both::<f64>(&coeffs, tau);
+        assert!((val - evaluate::<f64>(&coeffs, tau)).abs() < 1e-14);
+        assert!((deriv - evaluate_derivative::<f64>(&coeffs, tau)).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_higher_derivatives() {
+        // 1 + 2T_1 − T_2 + 0.5T_4 = 4τ⁴ − 6τ² + 2τ + 2.5
+        let coeffs = [1.0, 2.0, -1.0, 0.0, 0.5];
+        let tau = -0.3_f64;
+        let exact = [
+            4.0 * tau.powi(4) - 6.0 * tau * tau + 2.0 * tau + 2.5,
+            16.0 * tau.powi(3) - 12.0 * tau + 2.0,
+            48.0 * tau * tau - 12.0,
+            96.0 * tau,
+            96.0,
+            0.0,
+        ];
+        for (n, &want) in exact.iter().enumerate() {
+            let got = evaluate_nth_derivative(&coeffs, tau, n);
+            assert!((got - want).abs() < 1e-12, "n={n}: {got} vs {want}");
+        }
+
+        let (v, d) = evaluate_up_to::<f64, 5>(&coeffs, tau);
+        assert!((v - exact[0]).abs() < 1e-12);
+        for (got, want) in d.iter().zip(&exact[1..]) {
+            assert!((got - want).abs() < 1e-12);
+        }
+        let (v0, none) = evaluate_up_to::<f64, 0>(&coeffs, tau);
+        assert_eq!(v0, evaluate(&coeffs, tau));
+        assert_eq!(none, [0.0; 0]);
+        assert_eq!(evaluate_up_to::<f64, 2>(&[], tau), (0.0, [0.0, 0.0]));
+    }
+
+    #[test]
+    fn test_up_to_first_matches_evaluate_both() {
+        let coeffs = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let (v, [d]) = evaluate_up_to::<f64, 1>(&coeffs, 0.37);
+        assert_eq!((v, d), evaluate_both(&coeffs, 0.37));
+    }
+
+    #[test]
+    fn test_f32_matches_f64() {
+        let c64 = [0.75, -1.5, 0.25, 0.125, -0.0625];
+        let c32 = c64.map(|c| c as f32);
+        for &tau in &[-1.0, -0.4, 0.0, 0.37, 1.0] {
+            let (v32, d32) = evaluate_both(&c32, tau as f32);
+            let (v64, d64) = evaluate_both(&c64, tau);
+            assert!((v32 as f64 - v64).abs() < 1e-6);
+            assert!((d32 as f64 - d64).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_widened_matches_f64_of_stored_coeffs() {
+        let c32 = [0.1_f32, -0.7, 0.3, 1.0 / 3.0, 0.01];
+        let c64 = c32.map(f64::from);
+        let tau = -0.61;
+        let wide: f64 = evaluate_widened(&c32, tau);
+        assert_eq!(wide, evaluate(&c64, tau));
+        let both: (f64, f64) = evaluate_both_widened(&c32, tau);
+        assert_eq!(both, evaluate_both(&c64, tau));
     }
 
     #[test]
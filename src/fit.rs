@@ -11,9 +11,36 @@
 //! c_0 = (1/N) Σ_{k=0}^{N-1} f(ξ_k)
 //! c_j = (2/N) Σ_{k=0}^{N-1} f(ξ_k) · cos(jπ(2k+1) / (2N))   for j ≥ 1
 //! ```
+//!
+//! The sums form a DCT-II. [`fit_coeffs_direct`] evaluates them literally
+//! in O(N²) and is kept as the reference implementation;
+//! [`fit_coeffs_fast`] computes the same transform with an FFT in
+//! O(N log N). [`fit_coeffs`] picks between them based on
+//! [`FAST_FIT_THRESHOLD`].
+//!
+//! For values at the Chebyshev–Lobatto nodes `η_k = cos(πk/(N-1))`,
+//! [`fit_coeffs_lobatto`] uses the DCT-I instead:
+//!
+//! ```text
+//! c_j = (2/(N-1)) Σ''_{k=0}^{N-1} f(η_k) · cos(πjk / (N-1))
+//! ```
+//!
+//! where `Σ''` halves the `k = 0` and `k = N-1` terms, and `c_0` and
+//! `c_{N-1}` are halved as well.
+//!
+//! [`fit_hermite`] fits values *and* derivatives in the least-squares
+//! sense, JPL DE style, while matching both exactly at the interval ends.
 
+use crate::dct;
+use crate::linalg;
 use crate::nodes;
-use crate::scalar::ChebyScalar;
+use crate::scalar::{field, ChebyScalar};
+use std::fmt;
+
+/// Number of coefficients from which [`fit_coeffs`] (and every fitting
+/// routine built on it) switches from the direct sum to the FFT-based
+/// transform.
+pub const FAST_FIT_THRESHOLD: usize = 16;
 
 /// Compute Chebyshev coefficients from function values at the
 /// canonical Chebyshev nodes.
@@ -21,6 +48,9 @@ use crate::scalar::ChebyScalar;
 /// `values[k]` must correspond to the function evaluated at the `k`-th
 /// node returned by [`nodes::<N>()`](crate::nodes).
 ///
+/// Uses [`fit_coeffs_direct`] below [`FAST_FIT_THRESHOLD`] coefficients
+/// and [`fit_coeffs_fast`] from there on; both agree to rounding error.
+///
 /// # Example
 ///
 /// ```
@@ -32,17 +62,107 @@ use crate::scalar::ChebyScalar;
 #[inline]
 pub fn fit_coeffs<T: ChebyScalar, const N: usize>(values: &[T; N]) -> [T; N] {
     let mut coeffs = [T::zero(); N];
-    let n = N as f64;
+    fit_coeffs_into(values, &mut coeffs);
+    coeffs
+}
+
+/// Compute Chebyshev coefficients with the O(N²) direct DCT sum.
+///
+/// This is the reference implementation; it recomputes every cosine and
+/// is the most accurate for small `N`.
+pub fn fit_coeffs_direct<T: ChebyScalar, const N: usize>(values: &[T; N]) -> [T; N] {
+    let mut coeffs = [T::zero(); N];
+    fit_direct_into(values, &mut coeffs);
+    coeffs
+}
+
+/// Compute Chebyshev coefficients with an O(N log N) FFT-based DCT.
+///
+/// Gives the same coefficients as [`fit_coeffs_direct`] up to rounding
+/// error, for any `N` (not only powers of two).
+///
+/// # Example
+///
+/// ```
+/// let xi: [f64; 40] = cheby::nodes();
+/// let vals: [f64; 40] = std::array::from_fn(|k| xi[k].exp());
+/// let fast = cheby::fit_coeffs_fast(&vals);
+/// let direct = cheby::fit_coeffs_direct(&vals);
+/// for (a, b) in fast.iter().zip(&direct) {
+///     assert!((a - b).abs() < 1e-13);
+/// }
+/// ```
+pub fn fit_coeffs_fast<T: ChebyScalar, const N: usize>(values: &[T; N]) -> [T; N] {
+    let mut coeffs = [T::zero(); N];
+    fit_fast_into(values, &mut coeffs);
+    coeffs
+}
+
+/// Compute Chebyshev coefficients from values at the canonical nodes,
+/// with the number of values chosen at runtime.
+///
+/// `values[k]` must correspond to the `k`-th node returned by
+/// [`nodes_dyn(values.len())`](crate::nodes_dyn). Returns
+/// `values.len()` coefficients.
+///
+/// # Example
+///
+/// ```
+/// let xi = cheby::nodes_dyn(9);
+/// let vals: Vec<f64> = xi.iter().map(|x| x.sin()).collect();
+/// let coeffs = cheby::fit_coeffs_dyn(&vals);
+/// assert_eq!(coeffs.len(), 9);
+/// ```
+pub fn fit_coeffs_dyn<T: ChebyScalar>(values: &[T]) -> Vec<T> {
+    let mut coeffs = vec![T::zero(); values.len()];
+    fit_coeffs_into(values, &mut coeffs);
+    coeffs
+}
+
+/// Write the coefficients fitted from `values` into `coeffs`, choosing
+/// the algorithm by length.
+///
+/// Both slices must have the same length.
+#[inline]
+pub(crate) fn fit_coeffs_into<T: ChebyScalar>(values: &[T], coeffs: &mut [T]) {
+    if values.len() < FAST_FIT_THRESHOLD {
+        fit_direct_into(values, coeffs);
+    } else {
+        fit_fast_into(values, coeffs);
+    }
+}
+
+/// Direct O(N²) DCT sum.
+fn fit_direct_into<T: ChebyScalar>(values: &[T], coeffs: &mut [T]) {
+    debug_assert_eq!(values.len(), coeffs.len());
+    let n = values.len() as f64;
 
     for (j, coeff) in coeffs.iter_mut().enumerate() {
         let mut sum = T::zero();
         for (k, value) in values.iter().enumerate() {
             let arg = std::f64::consts::PI * (j as f64) * (2.0 * k as f64 + 1.0) / (2.0 * n);
-            sum = sum + *value * arg.cos();
+            sum = sum + *value * field::<T>(arg.cos());
         }
-        *coeff = if j == 0 { sum / n } else { sum * (2.0 / n) };
+        *coeff = if j == 0 {
+            sum / field::<T>(n)
+        } else {
+            sum * field::<T>(2.0 / n)
+        };
+    }
+}
+
+/// FFT-based O(N log N) DCT, followed by the same normalisation as the
+/// direct sum.
+fn fit_fast_into<T: ChebyScalar>(values: &[T], coeffs: &mut [T]) {
+    dct::dct2(values, coeffs);
+    let n = values.len() as f64;
+    for (j, coeff) in coeffs.iter_mut().enumerate() {
+        *coeff = if j == 0 {
+            *coeff / field::<T>(n)
+        } else {
+            *coeff * field::<T>(2.0 / n)
+        };
     }
-    coeffs
 }
 
 /// Sample a function at `N` Chebyshev nodes on `[start, end]` and fit
@@ -68,6 +188,219 @@ pub fn fit_from_fn<T: ChebyScalar, const N: usize>(
     fit_coeffs(&values)
 }
 
+/// Compute Chebyshev coefficients from function values at the
+/// Chebyshev–Lobatto nodes, using the DCT-I.
+///
+/// `values[k]` must correspond to the function evaluated at the `k`-th
+/// node returned by [`lobatto_nodes::<N>()`](crate::lobatto_nodes). The
+/// resulting series interpolates the values at every node, including
+/// `τ = ±1`. Switches to an FFT-based transform from
+/// [`FAST_FIT_THRESHOLD`] coefficients.
+///
+/// # Example
+///
+/// ```
+/// let eta: [f64; 9] = cheby::lobatto_nodes();
+/// let vals: [f64; 9] = std::array::from_fn(|k| eta[k].exp());
+/// let coeffs = cheby::fit_coeffs_lobatto(&vals);
+/// assert!((cheby::evaluate(&coeffs, 1.0) - 1.0_f64.exp()).abs() < 1e-14);
+/// ```
+pub fn fit_coeffs_lobatto<T: ChebyScalar, const N: usize>(values: &[T; N]) -> [T; N] {
+    let mut coeffs = [T::zero(); N];
+    if N < 2 {
+        coeffs.copy_from_slice(values);
+        return coeffs;
+    }
+
+    let m = (N - 1) as f64;
+    if N < FAST_FIT_THRESHOLD {
+        for (j, coeff) in coeffs.iter_mut().enumerate() {
+            let last = values[N - 1] * field::<T>(if j % 2 == 0 { 1.0 } else { -1.0 });
+            let mut sum = (values[0] + last) * field::<T>(0.5);
+            for (k, value) in values.iter().enumerate().take(N - 1).skip(1) {
+                let arg = std::f64::consts::PI * (j * k) as f64 / m;
+                sum = sum + *value * field::<T>(arg.cos());
+            }
+            *coeff = sum;
+        }
+    } else {
+        dct::dct1(values, &mut coeffs);
+    }
+
+    for (j, coeff) in coeffs.iter_mut().enumerate() {
+        let w = if j == 0 || j == N - 1 { 1.0 } else { 2.0 };
+        *coeff = *coeff * field::<T>(w / m);
+    }
+    coeffs
+}
+
+/// Sample a function at `N` Chebyshev–Lobatto nodes on `[start, end]` and
+/// fit Chebyshev coefficients with [`fit_coeffs_lobatto`].
+///
+/// `f` is called at exactly `start` and `end`, so segments fitted this
+/// way on adjacent intervals agree at their shared boundary.
+#[inline]
+pub fn fit_from_fn_lobatto<T: ChebyScalar, const N: usize>(
+    f: impl Fn(f64) -> T,
+    start: f64,
+    end: f64,
+) -> [T; N] {
+    let mapped: [f64; N] = nodes::lobatto_nodes_mapped(start, end);
+    let values: [T; N] = std::array::from_fn(|k| f(mapped[k]));
+    fit_coeffs_lobatto(&values)
+}
+
+/// Reasons [`fit_hermite`] and
+/// [`ChebySegmentTable::from_fn_hermite`](crate::ChebySegmentTable::from_fn_hermite)
+/// can fail.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HermiteError {
+    /// The domain is empty, reversed or not finite, or the segment length
+    /// is not positive and finite.
+    InvalidDomain,
+    /// The constrained least-squares system cannot be solved.
+    Singular,
+}
+
+impl fmt::Display for HermiteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidDomain => write!(f, "domain must be a finite interval with start < end"),
+            Self::Singular => write!(f, "the constrained fit cannot be solved"),
+        }
+    }
+}
+
+impl std::error::Error for HermiteError {}
+
+/// Fit Chebyshev coefficients to both values and derivatives of `f` on
+/// `[start, end]`, in the style of the JPL DE ephemerides.
+///
+/// `f(t)` returns `(value, d value / dt)`. It is sampled at the `N`
+/// Chebyshev–Lobatto nodes, and the coefficients minimise
+///
+/// ```text
+/// Σ_k |p(τ_k) − f(t_k)|² + |p'(τ_k) − half · f'(t_k)|²
+/// ```
+///
+/// (the derivative residuals are taken with respect to `τ`, so both terms
+/// share the units of `f`), subject to `p` and `dp/dt` equalling `f` and
+/// `f'` exactly at `start` and `end`. Segments fitted this way on adjacent
+/// intervals are therefore C¹-continuous at their shared boundary.
+///
+/// # Errors
+///
+/// [`HermiteError::InvalidDomain`] if `start` or `end` is not finite or
+/// `start >= end`, and [`HermiteError::Singular`] if the constrained
+/// system cannot be solved.
+///
+/// # Panics
+///
+/// Panics if `N < 4`: the four endpoint constraints need at least four
+/// coefficients.
+///
+/// # Example
+///
+/// ```
+/// let coeffs: [f64; 10] = cheby::fit_hermite(|t| (t.sin(), t.cos()), 0.0, 1.0)?;
+/// let half = 0.5;
+/// let (v, d) = cheby::evaluate_both(&coeffs, 1.0);
+/// assert!((v - 1.0_f64.sin()).abs() < 1e-15);
+/// assert!((d / half - 1.0_f64.cos()).abs() < 1e-13);
+/// # Ok::<(), cheby::HermiteError>(())
+/// ```
+pub fn fit_hermite<T: ChebyScalar<Field = f64>, const N: usize>(
+    f: impl Fn(f64) -> (T, T),
+    start: f64,
+    end: f64,
+) -> Result<[T; N], HermiteError> {
+    assert!(N >= 4, "fit_hermite needs at least 4 coefficients, got {N}");
+    if !(start.is_finite() && end.is_finite() && start < end) {
+        return Err(HermiteError::InvalidDomain);
+    }
+
+    let half = 0.5 * (end - start);
+    let tau: [f64; N] = nodes::lobatto_nodes();
+    let t: [f64; N] = nodes::lobatto_nodes_mapped(start, end);
+
+    // KKT system for equality-constrained least squares:
+    //   [AᵀA  Cᵀ] [c]   [Aᵀy]
+    //   [C    0 ] [λ] = [d  ]
+    let size = N + 4;
+    let mut kkt = vec![0.0_f64; size * size];
+    let mut rhs = vec![T::zero(); size];
+    let mut basis = vec![0.0_f64; N];
+    let mut dbasis = vec![0.0_f64; N];
+    let mut constraint = 0;
+
+    for (k, (&tau_k, &t_k)) in tau.iter().zip(&t).enumerate() {
+        let (value, rate) = f(t_k);
+        let slope = rate * half;
+        basis_with_derivative(tau_k, &mut basis, &mut dbasis);
+
+        for (row, y) in [(&basis, value), (&dbasis, slope)] {
+            for i in 0..N {
+                for j in 0..N {
+                    kkt[i * size + j] += row[i] * row[j];
+                }
+                rhs[i] = rhs[i] + y * row[i];
+            }
+        }
+
+        // Lobatto nodes k = 0 and k = N-1 are exactly τ = +1 and τ = −1.
+        if k == 0 || k == N - 1 {
+            for (row, y) in [(&basis, value), (&dbasis, slope)] {
+                let r = N + constraint;
+                for j in 0..N {
+                    kkt[r * size + j] = row[j];
+                    kkt[j * size + r] = row[j];
+                }
+                rhs[r] = y;
+                constraint += 1;
+            }
+        }
+    }
+
+    linalg::solve(&mut kkt, &mut rhs).ok_or(HermiteError::Singular)?;
+    Ok(std::array::from_fn(|j| rhs[j]))
+}
+
+/// Fill `t[j] = T_j(tau)` and `dt[j] = T_j'(tau)` for `j = 0, …, len-1`.
+pub(crate) fn basis_with_derivative(tau: f64, t: &mut [f64], dt: &mut [f64]) {
+    debug_assert_eq!(t.len(), dt.len());
+    let n = t.len();
+    if n == 0 {
+        return;
+    }
+    t[0] = 1.0;
+    dt[0] = 0.0;
+    if n > 1 {
+        t[1] = tau;
+        dt[1] = 1.0;
+    }
+    for j in 1..n.saturating_sub(1) {
+        t[j + 1] = 2.0 * tau * t[j] - t[j - 1];
+        dt[j + 1] = 2.0 * t[j] + 2.0 * tau * dt[j] - dt[j - 1];
+    }
+}
+
+/// Sample a function at `n` Chebyshev nodes on `[start, end]` and fit
+/// `n` Chebyshev coefficients, with `n` chosen at runtime.
+///
+/// Runtime-sized counterpart of [`fit_from_fn`].
+pub fn fit_from_fn_dyn<T: ChebyScalar>(
+    f: impl Fn(f64) -> T,
+    start: f64,
+    end: f64,
+    n: usize,
+) -> Vec<T> {
+    let values: Vec<T> = nodes::nodes_mapped_dyn(n, start, end)
+        .into_iter()
+        .map(f)
+        .collect();
+    fit_coeffs_dyn(&values)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -130,6 +463,115 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_fit_dyn_matches_const() {
+        let fixed: [f64; 12] = fit_from_fn(f64::cos, -0.5, 2.0);
+        let dynamic = fit_from_fn_dyn(f64::cos, -0.5, 2.0, 12);
+        assert_eq!(dynamic.len(), 12);
+        for (a, b) in fixed.iter().zip(&dynamic) {
+            assert_eq!(a, b);
+        }
+        assert!(fit_coeffs_dyn::<f64>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_fit_fast_matches_direct() {
+        let xi: [f64; 57] = crate::nodes();
+        let values: [f64; 57] = std::array::from_fn(|k| (3.0 * xi[k]).sin() / (1.5 + xi[k]));
+        let fast = fit_coeffs_fast(&values);
+        let direct = fit_coeffs_direct(&values);
+        for (j, (a, b)) in fast.iter().zip(&direct).enumerate() {
+            assert!((a - b).abs() < 1e-13, "c_{j}: fast={a}, direct={b}");
+        }
+        // Above the threshold, fit_coeffs goes through the fast path.
+        assert_eq!(fit_coeffs(&values), fast);
+
+        let small: [f64; 4] = [1.0, -2.0, 0.5, 3.0];
+        assert_eq!(fit_coeffs(&small), fit_coeffs_direct(&small));
+    }
+
+    #[test]
+    fn test_fit_lobatto_interpolates_nodes() {
+        fn check<const N: usize>() {
+            let eta: [f64; N] = crate::lobatto_nodes();
+            let values: [f64; N] = std::array::from_fn(|k| (2.0 * eta[k]).sin() + eta[k]);
+            let coeffs = fit_coeffs_lobatto(&values);
+            for k in 0..N {
+                let approx = evaluate(&coeffs, eta[k]);
+                assert!((approx - values[k]).abs() < 1e-13, "N={N}, k={k}: {approx}");
+            }
+        }
+        check::<2>();
+        check::<7>();
+        check::<15>();
+        check::<16>();
+        check::<40>();
+
+        assert_eq!(fit_coeffs_lobatto(&[4.0]), [4.0]);
+    }
+
+    #[test]
+    fn test_fit_from_fn_lobatto_accuracy() {
+        let coeffs: [f64; 15] = fit_from_fn_lobatto(f64::sin, -1.0, 1.0);
+        for &x in &[-1.0, -0.4, 0.0, 0.65, 1.0] {
+            assert!((evaluate(&coeffs, x) - x.sin()).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_fit_hermite_matches_endpoints() {
+        let f = |t: f64| ((2.0 * t).sin(), 2.0 * (2.0 * t).cos());
+        let (start, end) = (0.5, 2.0);
+        let half = 0.5 * (end - start);
+        let coeffs: [f64; 13] = fit_hermite(f, start, end).unwrap();
+
+        for (tau, t) in [(-1.0, start), (1.0, end)] {
+            let (v, d) = crate::evaluate_both(&coeffs, tau);
+            assert!((v - f(t).0).abs() < 1e-14, "value at {t}");
+            assert!((d / half - f(t).1).abs() < 1e-12, "rate at {t}");
+        }
+        for &tau in &[-0.7, 0.1, 0.55] {
+            let t = 1.25 + half * tau;
+            let (v, d) = crate::evaluate_both(&coeffs, tau);
+            assert!((v - f(t).0).abs() < 1e-9);
+            assert!((d / half - f(t).1).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 4")]
+    fn test_fit_hermite_too_few_coeffs() {
+        let _: Result<[f64; 3], _> = fit_hermite(|t| (t, 1.0), 0.0, 1.0);
+    }
+
+    #[test]
+    fn test_fit_hermite_invalid_domain() {
+        let f = |t: f64| (t, 1.0);
+        for (start, end) in [
+            (1.0, 1.0),
+            (1.0, 0.0),
+            (f64::NAN, 1.0),
+            (0.0, f64::INFINITY),
+        ] {
+            let result: Result<[f64; 6], _> = fit_hermite(f, start, end);
+            assert_eq!(result, Err(HermiteError::InvalidDomain));
+        }
+    }
+
+    #[test]
+    fn test_basis_with_derivative() {
+        let mut t = [0.0; 6];
+        let mut dt = [0.0; 6];
+        basis_with_derivative(0.3, &mut t, &mut dt);
+        for j in 0..6 {
+            let theta = 0.3_f64.acos();
+            assert!((t[j] - (j as f64 * theta).cos()).abs() < 1e-14);
+            // T_j'(x) = j sin(jθ) / sin(θ)
+            let exact = j as f64 * (j as f64 * theta).sin() / theta.sin();
+            assert!((dt[j] - exact).abs() < 1e-13);
+        }
+    }
+
     #[test]
     fn test_fit_quantity_type() {
         use qtty::Quantity;
@@ -0,0 +1,154 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! Uniform segment grids shared by the fixed-length tables.
+//!
+//! [`ChebySegmentTable`](crate::ChebySegmentTable),
+//! [`DynChebySegmentTable`](crate::DynChebySegmentTable),
+//! [`ChebyVecSegmentTable`](crate::ChebyVecSegmentTable) and
+//! [`ChebySegmentTableView`](crate::ChebySegmentTableView) all split their
+//! domain into equal segments from `start`. The arithmetic lives here, so
+//! that construction, lookup and cursors agree on every boundary, as does
+//! the check that stored segments cover their slots, shared by table
+//! files, views and SPK kernels.
+
+use crate::segment::CONTIGUITY_TOL;
+use std::fmt;
+
+/// Equal segments of `segment_len` from `start`, in plain values of the
+/// table axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct UniformGrid {
+    /// Start of the first segment.
+    pub start: f64,
+    /// Duration of each segment.
+    pub segment_len: f64,
+}
+
+impl UniformGrid {
+    /// Segments of `segment_len` from `start`.
+    #[inline]
+    pub const fn new(start: f64, segment_len: f64) -> Self {
+        Self { start, segment_len }
+    }
+
+    /// Number of segments needed to cover `[start, end]`, at least one.
+    #[inline]
+    pub fn count(&self, end: f64) -> usize {
+        (((end - self.start) / self.segment_len).ceil() as usize).max(1)
+    }
+
+    /// Bounds `[lo, hi]` of segment `i`. Both are computed from `start`,
+    /// so neighbours share their boundary exactly.
+    #[inline]
+    pub fn bounds(&self, i: usize) -> (f64, f64) {
+        (
+            self.start + i as f64 * self.segment_len,
+            self.start + (i + 1) as f64 * self.segment_len,
+        )
+    }
+
+    /// Bounds of the segments covering `[start, end]`, in order.
+    #[inline]
+    pub fn cover(self, end: f64) -> impl Iterator<Item = (f64, f64)> {
+        (0..self.count(end)).map(move |i| self.bounds(i))
+    }
+
+    /// End of the last of `len` segments.
+    #[inline]
+    pub fn end(&self, len: usize) -> f64 {
+        self.start + len as f64 * self.segment_len
+    }
+
+    /// Index of the segment of `len` containing `t`, or `None` outside
+    /// `[start, end)`.
+    #[inline]
+    pub fn index(&self, t: f64, len: usize) -> Option<usize> {
+        self.index_at_offset(t - self.start, len)
+    }
+
+    /// [`index`](Self::index) for a time given as its offset from
+    /// `start`.
+    #[inline]
+    pub fn index_at_offset(&self, offset: f64, len: usize) -> Option<usize> {
+        if offset < 0.0 || offset.is_nan() {
+            return None;
+        }
+        let idx = (offset / self.segment_len) as usize;
+        (idx < len).then_some(idx)
+    }
+
+    /// Check the grid's domain, and that each segment, given as
+    /// `(mid, half)`, covers its slot `[start + i·len, start + (i+1)·len]`
+    /// to within [`CONTIGUITY_TOL`].
+    pub fn check_slots(
+        self,
+        domains: impl IntoIterator<Item = (f64, f64)>,
+    ) -> Result<(), GridError> {
+        let (start, segment_len) = (self.start, self.segment_len);
+        if !(start.is_finite() && segment_len.is_finite() && segment_len > 0.0) {
+            return Err(GridError::Domain { start, segment_len });
+        }
+        for (index, (mid, half)) in domains.into_iter().enumerate() {
+            check_segment(index, mid, half)?;
+            let expected = self.bounds(index);
+            let found = (mid - half, mid + half);
+            let tol = CONTIGUITY_TOL * expected.0.abs().max(expected.1.abs()).max(segment_len);
+            if (found.0 - expected.0).abs() > tol || (found.1 - expected.1).abs() > tol {
+                return Err(GridError::Slot {
+                    index,
+                    found,
+                    expected,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Check that segment `index` has a finite `mid` and a positive, finite
+/// `half`.
+pub(crate) fn check_segment(index: usize, mid: f64, half: f64) -> Result<(), GridError> {
+    if !(mid.is_finite() && half.is_finite() && half > 0.0) {
+        return Err(GridError::Segment { index, mid, half });
+    }
+    Ok(())
+}
+
+/// Why segments do not fit a [`UniformGrid`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum GridError {
+    /// The start or segment length is not finite, or the length is not
+    /// positive.
+    Domain { start: f64, segment_len: f64 },
+    /// Segment `index` has a non-finite `mid` or a non-positive or
+    /// non-finite `half`.
+    Segment { index: usize, mid: f64, half: f64 },
+    /// Segment `index` covers `found` rather than its slot `expected`.
+    Slot {
+        index: usize,
+        found: (f64, f64),
+        expected: (f64, f64),
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Domain { start, segment_len } => {
+                write!(f, "domain start {start}, segment length {segment_len}")
+            }
+            Self::Segment { index, mid, half } => {
+                write!(f, "segment {index} has mid {mid}, half-width {half}")
+            }
+            Self::Slot {
+                index,
+                found: (lo, hi),
+                expected: (slot_lo, slot_hi),
+            } => write!(
+                f,
+                "segment {index} covers [{lo}, {hi}], expected [{slot_lo}, {slot_hi}]"
+            ),
+        }
+    }
+}
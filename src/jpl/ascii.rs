@@ -0,0 +1,320 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! The ASCII distribution format: `header.4xx` and `ascp*.4xx`.
+//!
+//! The header is a `KSIZE= … NCOEFF= …` line followed by `GROUP nnnn`
+//! blocks. A data file is a sequence of records, each a
+//! `<record number> <ncoeff>` line followed by `ncoeff` numbers, three per
+//! line, with the last line padded. Numbers use Fortran `D` exponents.
+
+use super::{DeEphemeris, DeError, DeHeader, DeLayout};
+use std::path::Path;
+
+/// Lines of `text` with their 1-based numbers.
+fn numbered(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.lines().enumerate().map(|(i, l)| (i + 1, l))
+}
+
+fn parse_error(line: usize, message: impl Into<String>) -> DeError {
+    DeError::Parse {
+        line,
+        message: message.into(),
+    }
+}
+
+/// A Fortran real such as `0.2451545D+07`.
+fn parse_real(line: usize, token: &str) -> Result<f64, DeError> {
+    token
+        .replace(['D', 'd'], "E")
+        .parse()
+        .map_err(|_| parse_error(line, format!("`{token}` is not a number")))
+}
+
+fn parse_count(line: usize, token: &str) -> Result<usize, DeError> {
+    token
+        .parse()
+        .map_err(|_| parse_error(line, format!("`{token}` is not a count")))
+}
+
+/// One `GROUP nnnn` block: its number, the line it starts on and its
+/// content lines.
+struct Group<'a> {
+    number: u32,
+    line: usize,
+    lines: Vec<(usize, &'a str)>,
+}
+
+impl<'a> Group<'a> {
+    /// Whitespace-separated tokens with their line numbers.
+    fn tokens(&self) -> impl Iterator<Item = (usize, &'a str)> + '_ {
+        self.lines
+            .iter()
+            .flat_map(|&(n, l)| l.split_whitespace().map(move |t| (n, t)))
+    }
+
+    /// A leading count followed by that many tokens.
+    fn counted(&self) -> Result<Vec<(usize, &'a str)>, DeError> {
+        let mut tokens = self.tokens();
+        let Some((line, count)) = tokens.next() else {
+            return Ok(Vec::new());
+        };
+        let count = parse_count(line, count)?;
+        let items: Vec<_> = tokens.take(count).collect();
+        if items.len() != count {
+            return Err(parse_error(
+                self.line,
+                format!(
+                    "group {} lists {count} items, found {}",
+                    self.number,
+                    items.len()
+                ),
+            ));
+        }
+        Ok(items)
+    }
+}
+
+impl DeHeader {
+    /// Parse an ASCII header (`header.4xx`).
+    ///
+    /// Groups 1030 (time span) and 1050 (layout) are required; the title
+    /// (1010) and constants (1040/1041) are read when present.
+    pub fn parse_ascii(text: &str) -> Result<Self, DeError> {
+        let mut lines = numbered(text).filter(|(_, l)| !l.trim().is_empty());
+        let (first, sizes) = lines.next().ok_or_else(|| parse_error(1, "empty header"))?;
+        let (ksize, ncoeff) = parse_sizes(first, sizes)?;
+
+        let mut groups: Vec<Group<'_>> = Vec::new();
+        for (n, l) in lines {
+            let mut words = l.split_whitespace();
+            if words.next() == Some("GROUP") {
+                let number = words
+                    .next()
+                    .and_then(|g| g.parse().ok())
+                    .ok_or_else(|| parse_error(n, "`GROUP` without a number"))?;
+                groups.push(Group {
+                    number,
+                    line: n,
+                    lines: Vec::new(),
+                });
+            } else if let Some(group) = groups.last_mut() {
+                group.lines.push((n, l));
+            } else {
+                return Err(parse_error(n, "text before the first group"));
+            }
+        }
+        let group = |number: u32| groups.iter().find(|g| g.number == number);
+        let required = |number: u32| group(number).ok_or(DeError::MissingGroup(number));
+
+        let title = group(1010)
+            .map(|g| g.lines.iter().map(|(_, l)| l.trim().to_string()).collect())
+            .unwrap_or_default();
+
+        let span = required(1030)?;
+        let span_values = span
+            .tokens()
+            .map(|(n, t)| parse_real(n, t))
+            .collect::<Result<Vec<_>, _>>()?;
+        let &[start, end, interval] = span_values.as_slice() else {
+            return Err(parse_error(
+                span.line,
+                "group 1030 needs start, end and interval",
+            ));
+        };
+
+        let mut constants = Vec::new();
+        if let (Some(names), Some(values)) = (group(1040), group(1041)) {
+            let (names, values) = (names.counted()?, values.counted()?);
+            if names.len() != values.len() {
+                return Err(parse_error(
+                    group(1041).map_or(0, |g| g.line),
+                    format!("{} constant names but {} values", names.len(), values.len()),
+                ));
+            }
+            for ((_, name), (n, value)) in names.into_iter().zip(values) {
+                constants.push((name.to_string(), parse_real(n, value)?));
+            }
+        }
+
+        let layout_group = required(1050)?;
+        let numbers = layout_group
+            .tokens()
+            .map(|(n, t)| parse_count(n, t))
+            .collect::<Result<Vec<_>, _>>()?;
+        if numbers.is_empty() || numbers.len() % 3 != 0 {
+            return Err(parse_error(
+                layout_group.line,
+                "group 1050 needs three equal rows",
+            ));
+        }
+        let columns = numbers.len() / 3;
+        let layout = (0..columns)
+            .map(|c| DeLayout {
+                offset: numbers[c].saturating_sub(1),
+                ncoeff: numbers[columns + c],
+                nsub: numbers[2 * columns + c],
+            })
+            .collect();
+
+        let header = Self {
+            ksize,
+            ncoeff,
+            title,
+            start,
+            end,
+            interval,
+            constants,
+            layout,
+        };
+        header.validate()?;
+        Ok(header)
+    }
+}
+
+/// `KSIZE= 2036    NCOEFF= 1018`.
+fn parse_sizes(line: usize, text: &str) -> Result<(usize, usize), DeError> {
+    let mut ksize = None;
+    let mut ncoeff = None;
+    let mut words = text
+        .split(|c: char| c.is_whitespace() || c == '=')
+        .filter(|w| !w.is_empty());
+    while let Some(word) = words.next() {
+        let slot = match word {
+            "KSIZE" => &mut ksize,
+            "NCOEFF" => &mut ncoeff,
+            _ => continue,
+        };
+        let value = words
+            .next()
+            .ok_or_else(|| parse_error(line, format!("`{word}` without a value")))?;
+        *slot = Some(parse_count(line, value)?);
+    }
+    match (ksize, ncoeff) {
+        (Some(k), Some(n)) => Ok((k, n)),
+        _ => Err(parse_error(line, "expected `KSIZE= … NCOEFF= …`")),
+    }
+}
+
+impl DeEphemeris {
+    /// Parse an ASCII header and any number of ASCII data files, in
+    /// chronological order.
+    pub fn from_ascii<S: AsRef<str>>(
+        header: &str,
+        data: impl IntoIterator<Item = S>,
+    ) -> Result<Self, DeError> {
+        let mut de = Self::new(DeHeader::parse_ascii(header)?)?;
+        for text in data {
+            de.push_ascii(text.as_ref())?;
+        }
+        Ok(de)
+    }
+
+    /// Read an ASCII header file and data files, in chronological order.
+    pub fn read_ascii<P: AsRef<Path>>(
+        header: impl AsRef<Path>,
+        data: impl IntoIterator<Item = P>,
+    ) -> Result<Self, DeError> {
+        let mut de = Self::new(DeHeader::parse_ascii(&std::fs::read_to_string(header)?)?)?;
+        for path in data {
+            de.push_ascii(&std::fs::read_to_string(path)?)?;
+        }
+        Ok(de)
+    }
+
+    /// Append the records of an ASCII data file (`ascp*.4xx`).
+    ///
+    /// The boundary record repeated at the start of the next file is
+    /// skipped; see [`push_record`](Self::push_record).
+    pub fn push_ascii(&mut self, text: &str) -> Result<(), DeError> {
+        let ncoeff = self.header.ncoeff;
+        let mut lines = numbered(text).filter(|(_, l)| !l.trim().is_empty());
+        let mut record = Vec::with_capacity(ncoeff);
+        while let Some((n, line)) = lines.next() {
+            let counts = line
+                .split_whitespace()
+                .map(|t| parse_count(n, t))
+                .collect::<Result<Vec<_>, _>>()?;
+            let &[_, count] = counts.as_slice() else {
+                return Err(parse_error(n, "expected `<record> <ncoeff>`"));
+            };
+            if count != ncoeff {
+                return Err(DeError::RecordSize {
+                    record: self.len(),
+                    expected: ncoeff,
+                    found: count,
+                });
+            }
+
+            record.clear();
+            let mut last = n;
+            while record.len() < ncoeff {
+                let (n, line) = lines
+                    .next()
+                    .ok_or_else(|| parse_error(last, "record ends early"))?;
+                last = n;
+                // Values past `ncoeff` on the last line are padding.
+                for token in line.split_whitespace().take(ncoeff - record.len()) {
+                    record.push(parse_real(n, token)?);
+                }
+            }
+            self.push_record(&record)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str =
+        "KSIZE= 16 NCOEFF= 8\n\nGROUP 1030\n 0.0 64.0 32.\n\nGROUP 1050\n 3\n 2\n 1\n";
+
+    #[test]
+    fn test_parse_minimal_header() {
+        let header = DeHeader::parse_ascii(HEADER).unwrap();
+        assert_eq!((header.ksize, header.ncoeff), (16, 8));
+        assert_eq!(
+            (header.start, header.end, header.interval),
+            (0.0, 64.0, 32.0)
+        );
+        assert!(header.title.is_empty() && header.constants.is_empty());
+        let layout = DeLayout {
+            offset: 2,
+            ncoeff: 2,
+            nsub: 1,
+        };
+        assert_eq!(header.layout, vec![layout]);
+    }
+
+    #[test]
+    fn test_header_errors() {
+        let err = DeHeader::parse_ascii("KSIZE= 16\nGROUP 1030\n").unwrap_err();
+        assert!(matches!(err, DeError::Parse { line: 1, .. }), "{err}");
+        let err = DeHeader::parse_ascii(&HEADER.replace("GROUP 1050", "GROUP 1051")).unwrap_err();
+        assert!(matches!(err, DeError::MissingGroup(1050)), "{err}");
+        let err = DeHeader::parse_ascii(&HEADER.replace("64.0", "6x.0")).unwrap_err();
+        assert!(matches!(err, DeError::Parse { line: 4, .. }), "{err}");
+        assert_eq!(err.to_string(), "line 4: `6x.0` is not a number");
+        // Two sub-intervals of two coefficients do not fit in 8 values.
+        let err = DeHeader::parse_ascii(&HEADER.replace(" 1\n", " 2\n")).unwrap_err();
+        assert!(matches!(err, DeError::Layout { .. }), "{err}");
+    }
+
+    #[test]
+    fn test_data_records() {
+        let mut de = DeEphemeris::new(DeHeader::parse_ascii(HEADER).unwrap()).unwrap();
+        let data = "1 8\n 0.0D+00 0.32D+02 1.0\n 2.0 3.0 4.0\n 5.0 6.0 0.0\n";
+        de.push_ascii(data).unwrap();
+        assert_eq!(de.len(), 1);
+        assert_eq!(de.record(0), &[0.0, 32.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        let err = de.push_ascii("1 9\n").unwrap_err();
+        assert!(matches!(err, DeError::RecordSize { found: 9, .. }), "{err}");
+        let err = de.push_ascii("2 8\n 0.32D+02 0.64D+02 1.0\n").unwrap_err();
+        assert!(matches!(err, DeError::Parse { line: 2, .. }), "{err}");
+        let err = de.push_ascii("2 8 7\n").unwrap_err();
+        assert!(matches!(err, DeError::Parse { line: 1, .. }), "{err}");
+    }
+}
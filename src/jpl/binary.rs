@@ -0,0 +1,510 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! The binary format written by JPL's Fortran tools (`linux_p1550p2650.440`,
+//! `lnxp1900p2053.405`, …), read in place from a memory map.
+//!
+//! The file is a sequence of records of `ncoeff` `f64` values in the byte
+//! order of the machine that wrote it. Record 1 is the header (title,
+//! constant names, time span, `AU`, `EMRAT`, the coefficient layout and the
+//! DE number), record 2 the constant values, and every further record one
+//! interval of coefficients, as in the ASCII files. The record size is not
+//! stored; like the reference readers, it is derived from the layout.
+
+use super::{DeBody, DeEphemeris, DeError, DeHeader, DeLayout};
+use crate::bytes::Endian;
+use crate::segment::ChebySegment;
+use crate::vector::ChebyVecSegment;
+use memmap2::Mmap;
+use std::fs::File;
+use std::path::Path;
+
+/// Bytes of the three 84-character title lines.
+const TITLE: usize = 3 * 84;
+/// Constant names stored before the time span.
+const NAMES: usize = 400;
+/// Characters per constant name.
+const NAME_LEN: usize = 6;
+/// Byte offsets of the header fields in record 1.
+const SPAN: usize = TITLE + NAMES * NAME_LEN;
+const NCON: usize = SPAN + 3 * 8;
+const IPT: usize = NCON + 4 + 2 * 8;
+const NUMDE: usize = IPT + 12 * 3 * 4;
+const LPT: usize = NUMDE + 4;
+const EXTRA_NAMES: usize = LPT + 3 * 4;
+
+/// The byte order in which the header holds a plausible DE number and a
+/// positive record interval.
+fn detect(bytes: &[u8]) -> Option<Endian> {
+    [Endian::Little, Endian::Big].into_iter().find(|e| {
+        let step = e.f64(bytes, SPAN + 16);
+        (1..=9999).contains(&e.u32(bytes, NUMDE)) && step.is_finite() && step > 0.0
+    })
+}
+
+/// The `(offset, ncoeff, nsub)` triple at byte `at`.
+fn layout_at(endian: Endian, bytes: &[u8], at: usize) -> DeLayout {
+    let word = |i: usize| endian.u32(bytes, at + 4 * i) as usize;
+    DeLayout {
+        offset: word(0).saturating_sub(1),
+        ncoeff: word(1),
+        nsub: word(2),
+    }
+}
+
+fn format_error(message: &str) -> DeError {
+    DeError::Format(message.to_string())
+}
+
+/// Values per record implied by `layout`: one past its last coefficient.
+fn record_len(layout: &[DeLayout]) -> usize {
+    layout
+        .iter()
+        .zip(DeBody::ALL)
+        .filter(|(l, _)| !l.is_empty())
+        .map(|(l, body)| l.offset + l.len(body.components()))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Fixed-width text with trailing blanks removed.
+fn text(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).trim().to_string()
+}
+
+/// Parse records 1 and 2.
+fn parse_header(bytes: &[u8]) -> Result<(DeHeader, Endian), DeError> {
+    if bytes.len() < EXTRA_NAMES {
+        return Err(format_error("file is shorter than its header"));
+    }
+    let endian = detect(bytes).ok_or_else(|| format_error("cannot detect byte order"))?;
+
+    let mut layout: Vec<DeLayout> = (0..12)
+        .map(|i| layout_at(endian, bytes, IPT + 12 * i))
+        .collect();
+    layout.push(layout_at(endian, bytes, LPT));
+    // Files with more than 400 constants continue the names here, and newer
+    // ephemerides add the lunar mantle and TT − TDB columns after them.
+    let ncon = endian.u32(bytes, NCON) as usize;
+    let extra = EXTRA_NAMES + ncon.saturating_sub(NAMES) * NAME_LEN;
+    if extra + 24 <= 8 * record_len(&layout) && extra + 24 <= bytes.len() {
+        layout.push(layout_at(endian, bytes, extra));
+        layout.push(layout_at(endian, bytes, extra + 12));
+    }
+    while layout.last().is_some_and(DeLayout::is_empty) {
+        layout.pop();
+    }
+
+    let ncoeff = record_len(&layout);
+    let record_bytes = 8 * ncoeff;
+    if record_bytes < extra || ncon * 8 > record_bytes {
+        return Err(format_error(
+            "layout gives records too short for the header",
+        ));
+    }
+    if bytes.len() < 2 * record_bytes {
+        return Err(format_error("file is shorter than its header"));
+    }
+
+    let title = bytes[..TITLE]
+        .chunks(84)
+        .map(text)
+        .filter(|l| !l.is_empty())
+        .collect();
+    let name = |i: usize| {
+        let at = if i < NAMES {
+            TITLE + i * NAME_LEN
+        } else {
+            EXTRA_NAMES + (i - NAMES) * NAME_LEN
+        };
+        text(&bytes[at..at + NAME_LEN])
+    };
+    let constants = (0..ncon)
+        .map(|i| (name(i), endian.f64(bytes, record_bytes + 8 * i)))
+        .collect();
+
+    let header = DeHeader {
+        ksize: 2 * ncoeff,
+        ncoeff,
+        title,
+        start: endian.f64(bytes, SPAN),
+        end: endian.f64(bytes, SPAN + 8),
+        interval: endian.f64(bytes, SPAN + 16),
+        constants,
+        layout,
+    };
+    header.validate()?;
+    Ok((header, endian))
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// Records
+// ─────────────────────────────────────────────────────────────────────────
+
+/// One data record, read in place.
+#[derive(Debug, Clone, Copy)]
+pub struct DeRecord<'a> {
+    bytes: &'a [u8],
+    endian: Endian,
+}
+
+impl<'a> DeRecord<'a> {
+    /// Number of values, including the two dates.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bytes.len() / 8
+    }
+
+    /// `true` if the record holds no values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Value `i` (0 and 1 are the start and end Julian days).
+    ///
+    /// # Panics
+    ///
+    /// If `i >= self.len()`.
+    #[inline]
+    pub fn get(&self, i: usize) -> f64 {
+        self.endian.f64(self.bytes, 8 * i)
+    }
+
+    /// First Julian day covered.
+    #[inline]
+    pub fn start(&self) -> f64 {
+        self.get(0)
+    }
+
+    /// Last Julian day covered.
+    #[inline]
+    pub fn end(&self) -> f64 {
+        self.get(1)
+    }
+
+    /// All values, in order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = f64> + 'a {
+        let endian = self.endian;
+        self.bytes.chunks_exact(8).map(move |b| endian.f64(b, 0))
+    }
+}
+
+/// The data records of a file, located by time.
+#[derive(Debug, Clone, Copy)]
+struct Records<'a> {
+    /// Records 3 onwards.
+    bytes: &'a [u8],
+    endian: Endian,
+    /// Bytes per record.
+    record_bytes: usize,
+    /// Start of the first record.
+    start: f64,
+    /// Days per record.
+    interval: f64,
+}
+
+impl<'a> Records<'a> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.bytes.len() / self.record_bytes
+    }
+
+    #[inline]
+    fn get(&self, i: usize) -> DeRecord<'a> {
+        let at = i * self.record_bytes;
+        DeRecord {
+            bytes: &self.bytes[at..at + self.record_bytes],
+            endian: self.endian,
+        }
+    }
+
+    #[inline]
+    fn end(&self) -> f64 {
+        self.get(self.len() - 1).end()
+    }
+
+    /// Index of the record covering `t`; the end of the last record
+    /// belongs to it.
+    #[inline]
+    fn index(&self, t: f64) -> Option<usize> {
+        if !(t >= self.start && t <= self.end()) {
+            return None;
+        }
+        let i = ((t - self.start) / self.interval) as usize;
+        Some(i.min(self.len() - 1))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// DeBinary — the file
+// ─────────────────────────────────────────────────────────────────────────
+
+/// A binary DE ephemeris read in place from `B`, a memory map by default.
+///
+/// Only the header is decoded up front; records are located by time and
+/// their coefficients read on demand, so opening a multi-gigabyte file
+/// costs no more than reading its first two records.
+///
+/// # Example
+///
+/// ```no_run
+/// use cheby::jpl::{DeBinary, DeBody};
+///
+/// let de = DeBinary::open("linux_p1550p2650.440")?;
+/// let moon = de.body::<13, 3>(DeBody::Moon)?;
+/// let (position, velocity) = moon.eval_both(2_451_545.0).unwrap();
+/// # let _ = (position, velocity);
+/// # Ok::<(), cheby::jpl::DeError>(())
+/// ```
+#[derive(Debug)]
+pub struct DeBinary<B = Mmap> {
+    /// The file contents.
+    bytes: B,
+    /// The header, from records 1 and 2.
+    header: DeHeader,
+    /// Byte order of the file.
+    endian: Endian,
+}
+
+impl DeBinary<Mmap> {
+    /// Memory-map the file at `path`.
+    ///
+    /// The file must not be truncated or modified while it is open: like
+    /// every memory map, the contents are read from the file on access.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, DeError> {
+        let file = File::open(path)?;
+        // SAFETY: the map is read-only and only exposed as `&[u8]`; the
+        // caller is told above not to change the file underneath it.
+        let map = unsafe { Mmap::map(&file)? };
+        Self::from_bytes(map)
+    }
+}
+
+impl<B: AsRef<[u8]>> DeBinary<B> {
+    /// Read the ephemeris from the complete contents of a file.
+    ///
+    /// The byte order is detected from the header. The file must hold a
+    /// whole number of records and at least one data record, and its
+    /// first and last records must be consistent with the header interval.
+    pub fn from_bytes(bytes: B) -> Result<Self, DeError> {
+        let (header, endian) = parse_header(bytes.as_ref())?;
+        let de = Self {
+            bytes,
+            header,
+            endian,
+        };
+        let len = de.bytes.as_ref().len();
+        let record_bytes = 8 * de.header.ncoeff;
+        if len % record_bytes != 0 {
+            return Err(format_error("file is not a whole number of records"));
+        }
+        if len / record_bytes < 3 {
+            return Err(DeError::NoRecords);
+        }
+        let records = de.records();
+        let last = records.len() - 1;
+        let expected = records.start + last as f64 * records.interval;
+        let found = records.get(last).start();
+        if found != expected {
+            return Err(DeError::NotContiguous {
+                record: last,
+                expected,
+                found,
+            });
+        }
+        Ok(de)
+    }
+
+    /// The header.
+    #[inline]
+    pub fn header(&self) -> &DeHeader {
+        &self.header
+    }
+
+    /// Byte order of the file.
+    #[inline]
+    pub fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    fn records(&self) -> Records<'_> {
+        let record_bytes = 8 * self.header.ncoeff;
+        let bytes = &self.bytes.as_ref()[2 * record_bytes..];
+        Records {
+            bytes,
+            endian: self.endian,
+            record_bytes,
+            start: self.endian.f64(bytes, 0),
+            interval: self.header.interval,
+        }
+    }
+
+    /// Number of data records.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.records().len()
+    }
+
+    /// Always `false`: a file without data records is rejected on opening.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Start of the first data record.
+    #[inline]
+    pub fn start(&self) -> f64 {
+        self.records().start
+    }
+
+    /// End of the last data record.
+    #[inline]
+    pub fn end(&self) -> f64 {
+        self.records().end()
+    }
+
+    /// Data record `i`.
+    ///
+    /// # Panics
+    ///
+    /// If `i >= self.len()`.
+    #[inline]
+    pub fn record(&self, i: usize) -> DeRecord<'_> {
+        self.records().get(i)
+    }
+
+    /// Index of the data record covering `t`, or `None` if out of range.
+    #[inline]
+    pub fn record_index(&self, t: f64) -> Option<usize> {
+        self.records().index(t)
+    }
+
+    /// A view of `body` for evaluation with `N` coefficients and `D`
+    /// components, which must match the file.
+    pub fn body<const N: usize, const D: usize>(
+        &self,
+        body: DeBody,
+    ) -> Result<DeBodyView<'_, N, D>, DeError> {
+        let layout = self.header.checked_layout(body, Some(N), Some(D))?;
+        Ok(DeBodyView {
+            records: self.records(),
+            body,
+            layout,
+        })
+    }
+
+    /// Copy every record into an in-memory [`DeEphemeris`].
+    pub fn to_ephemeris(&self) -> Result<DeEphemeris, DeError> {
+        let mut de = DeEphemeris::new(self.header.clone())?;
+        let mut values = Vec::with_capacity(self.header.ncoeff);
+        let records = self.records();
+        for i in 0..records.len() {
+            values.clear();
+            values.extend(records.get(i).iter());
+            de.push_record(&values)?;
+        }
+        Ok(de)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// DeBodyView — one body, evaluated in place
+// ─────────────────────────────────────────────────────────────────────────
+
+/// One body of a [`DeBinary`], with `N` coefficients and `D` components.
+///
+/// Each query locates the record and sub-interval covering `t` and builds
+/// the [`ChebyVecSegment`] for it from the mapped bytes.
+#[derive(Debug, Clone, Copy)]
+pub struct DeBodyView<'a, const N: usize, const D: usize> {
+    records: Records<'a>,
+    body: DeBody,
+    layout: DeLayout,
+}
+
+impl<const N: usize, const D: usize> DeBodyView<'_, N, D> {
+    /// The body.
+    #[inline]
+    pub fn body(&self) -> DeBody {
+        self.body
+    }
+
+    /// The body's layout in a record.
+    #[inline]
+    pub fn layout(&self) -> DeLayout {
+        self.layout
+    }
+
+    /// The sub-interval segment covering `t`, or `None` if out of range.
+    pub fn segment(&self, t: f64) -> Option<ChebyVecSegment<f64, N, D>> {
+        let record = self.records.get(self.records.index(t)?);
+        let nsub = self.layout.nsub;
+        let sub_len = self.records.interval / nsub as f64;
+        let j = (((t - record.start()) / sub_len) as usize).min(nsub - 1);
+        Some(
+            self.layout
+                .segment(self.records.interval, |i| record.get(i), j),
+        )
+    }
+
+    /// Component `d` of the segment covering `t`.
+    ///
+    /// # Panics
+    ///
+    /// If `d >= D`.
+    pub fn component(&self, d: usize, t: f64) -> Option<ChebySegment<f64, N>> {
+        self.segment(t).map(|s| s.component(d))
+    }
+
+    /// Value at `t` (position in km for the bodies).
+    #[inline]
+    pub fn eval(&self, t: f64) -> Option<[f64; D]> {
+        self.segment(t).map(|s| s.eval(t))
+    }
+
+    /// Derivative at `t`, per day.
+    #[inline]
+    pub fn eval_derivative(&self, t: f64) -> Option<[f64; D]> {
+        self.segment(t).map(|s| s.eval_derivative(t))
+    }
+
+    /// Value and derivative at `t` in one pass.
+    #[inline]
+    pub fn eval_both(&self, t: f64) -> Option<([f64; D], [f64; D])> {
+        self.segment(t).map(|s| s.eval_both(t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_len_from_layout() {
+        let layout = |offset, ncoeff, nsub| DeLayout {
+            offset,
+            ncoeff,
+            nsub,
+        };
+        // Three components of 4 × 2, then 2 nutation components of 3 × 1
+        // in column 11.
+        let mut columns = vec![layout(2, 4, 2)];
+        assert_eq!(record_len(&columns), 26);
+        columns.resize(11, layout(0, 0, 0));
+        columns.push(layout(26, 3, 1));
+        assert_eq!(record_len(&columns), 32);
+        assert_eq!(record_len(&[]), 0);
+    }
+
+    #[test]
+    fn test_short_input() {
+        let err = DeBinary::from_bytes(vec![0; 100]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "malformed binary ephemeris: file is shorter than its header"
+        );
+        let err = DeBinary::from_bytes(vec![0; 4000]).unwrap_err();
+        assert!(matches!(err, DeError::Format(_)), "{err}");
+    }
+}
@@ -0,0 +1,737 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! Readers for JPL DE planetary and lunar ephemerides (feature `jpl`).
+//!
+//! A DE ephemeris is a sequence of fixed-size records, each covering the
+//! same interval of TDB Julian days (32 days for DE440/DE441). A record
+//! starts with its two boundary dates and then holds, for every body, the
+//! Chebyshev coefficients of `nsub` consecutive sub-intervals, each with
+//! one series of `ncoeff` coefficients per component, component-major.
+//! Where each body's block starts, its `ncoeff` and its `nsub` are listed
+//! in group 1050 of the header ([`DeLayout`]).
+//!
+//! [`DeEphemeris`] holds the header and the records, and turns any body
+//! into a table of its sub-intervals: a [`ChebyVecSegmentTable`] when the
+//! degree is known at compile time ([`DeEphemeris::body_table`]), or one
+//! [`DynChebySegmentTable`] per component
+//! ([`DeEphemeris::component_table`]). Times are TDB Julian days;
+//! positions are in km, so derivatives are in km/day.
+//!
+//! The ASCII files are parsed into a [`DeEphemeris`]; the much larger
+//! binary files are read in place by [`DeBinary`], which memory-maps the
+//! file and builds the segment for a body and epoch on demand
+//! ([`DeBodyView`]).
+//!
+//! # Example
+//!
+//! ```no_run
+//! use cheby::jpl::{DeBody, DeEphemeris};
+//!
+//! let de = DeEphemeris::read_ascii("header.440", ["ascp01950.440", "ascp02050.440"])?;
+//! let mars = de.body_table::<11, 3>(DeBody::Mars)?;
+//! let ([x, y, z], [vx, vy, vz]) = mars.eval_both(2_451_545.0).unwrap();
+//! # let _ = (x, y, z, vx, vy, vz);
+//! # Ok::<(), cheby::jpl::DeError>(())
+//! ```
+
+mod ascii;
+mod binary;
+
+use crate::series::{DynChebySegment, DynChebySegmentTable};
+use crate::vector::{ChebyVecSegment, ChebyVecSegmentTable};
+use std::fmt;
+
+pub use crate::bytes::Endian;
+pub use binary::{DeBinary, DeBodyView, DeRecord};
+
+// ─────────────────────────────────────────────────────────────────────────
+// Bodies and layout
+// ─────────────────────────────────────────────────────────────────────────
+
+/// A quantity tabulated in a DE ephemeris, in the column order of header
+/// group 1050.
+///
+/// Older ephemerides (e.g. DE405) stop after [`Librations`](Self::Librations).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeBody {
+    /// Mercury, solar-system barycentric.
+    Mercury,
+    /// Venus, solar-system barycentric.
+    Venus,
+    /// Earth–Moon barycentre, solar-system barycentric.
+    EarthMoonBarycenter,
+    /// Mars, solar-system barycentric.
+    Mars,
+    /// Jupiter, solar-system barycentric.
+    Jupiter,
+    /// Saturn, solar-system barycentric.
+    Saturn,
+    /// Uranus, solar-system barycentric.
+    Uranus,
+    /// Neptune, solar-system barycentric.
+    Neptune,
+    /// Pluto, solar-system barycentric.
+    Pluto,
+    /// The Moon, geocentric.
+    Moon,
+    /// The Sun, solar-system barycentric.
+    Sun,
+    /// Earth nutation in longitude and obliquity (radians).
+    Nutations,
+    /// Lunar mantle libration Euler angles (radians).
+    Librations,
+    /// Lunar mantle angular velocity (radians/day).
+    LunarMantleVelocity,
+    /// TT − TDB at the geocentre (seconds).
+    TtMinusTdb,
+}
+
+impl DeBody {
+    /// Every body, in column order.
+    pub const ALL: [DeBody; 15] = [
+        DeBody::Mercury,
+        DeBody::Venus,
+        DeBody::EarthMoonBarycenter,
+        DeBody::Mars,
+        DeBody::Jupiter,
+        DeBody::Saturn,
+        DeBody::Uranus,
+        DeBody::Neptune,
+        DeBody::Pluto,
+        DeBody::Moon,
+        DeBody::Sun,
+        DeBody::Nutations,
+        DeBody::Librations,
+        DeBody::LunarMantleVelocity,
+        DeBody::TtMinusTdb,
+    ];
+
+    /// Column of the body in header group 1050 (0-based).
+    #[inline]
+    pub fn column(self) -> usize {
+        self as usize
+    }
+
+    /// Number of components tabulated for the body.
+    #[inline]
+    pub fn components(self) -> usize {
+        match self {
+            DeBody::Nutations => 2,
+            DeBody::TtMinusTdb => 1,
+            _ => 3,
+        }
+    }
+}
+
+/// Where a body's coefficients sit in a record (one column of header
+/// group 1050).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeLayout {
+    /// Index of the body's first coefficient within a record, 0-based
+    /// (the header lists it 1-based).
+    pub offset: usize,
+    /// Coefficients per component and sub-interval (degree + 1).
+    pub ncoeff: usize,
+    /// Number of sub-intervals a record is split into for this body.
+    pub nsub: usize,
+}
+
+impl DeLayout {
+    /// Number of record values the body occupies with `components`
+    /// components.
+    #[inline]
+    pub fn len(&self, components: usize) -> usize {
+        self.ncoeff * components * self.nsub
+    }
+
+    /// `true` if the body is absent from the ephemeris.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ncoeff == 0 || self.nsub == 0
+    }
+
+    /// `(mid, half)` of sub-interval `j` of a record starting at `start`.
+    #[inline]
+    fn sub_interval(&self, interval: f64, start: f64, j: usize) -> (f64, f64) {
+        let len = interval / self.nsub as f64;
+        (start + (j as f64 + 0.5) * len, 0.5 * len)
+    }
+
+    /// Sub-interval `j` of a record whose `i`-th value is `value(i)`.
+    #[inline]
+    fn segment<const N: usize, const D: usize>(
+        &self,
+        interval: f64,
+        value: impl Fn(usize) -> f64,
+        j: usize,
+    ) -> ChebyVecSegment<f64, N, D> {
+        let base = self.offset + j * D * N;
+        let coeffs = std::array::from_fn(|d| std::array::from_fn(|k| value(base + d * N + k)));
+        let (mid, half) = self.sub_interval(interval, value(0), j);
+        ChebyVecSegment::new(coeffs, mid, half)
+    }
+}
+
+/// The header of a DE ephemeris.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeHeader {
+    /// Record size in 4-byte words (twice [`ncoeff`](Self::ncoeff)).
+    pub ksize: usize,
+    /// Number of `f64` values per record, including the two dates.
+    pub ncoeff: usize,
+    /// Title lines (group 1010).
+    pub title: Vec<String>,
+    /// First Julian day covered (group 1030).
+    pub start: f64,
+    /// Last Julian day covered (group 1030).
+    pub end: f64,
+    /// Days covered by each record (group 1030).
+    pub interval: f64,
+    /// Named constants such as `AU` and `EMRAT` (groups 1040/1041).
+    pub constants: Vec<(String, f64)>,
+    /// Coefficient layout, one entry per [`DeBody`] column (group 1050).
+    pub layout: Vec<DeLayout>,
+}
+
+impl DeHeader {
+    /// The value of the constant `name`, if present.
+    pub fn constant(&self, name: &str) -> Option<f64> {
+        self.constants
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|&(_, v)| v)
+    }
+
+    /// The layout of `body`, or `None` if the ephemeris does not contain it.
+    pub fn body_layout(&self, body: DeBody) -> Option<DeLayout> {
+        self.layout
+            .get(body.column())
+            .copied()
+            .filter(|l| !l.is_empty())
+    }
+
+    /// The layout of `body`, checked against a requested number of
+    /// coefficients and components.
+    fn checked_layout(
+        &self,
+        body: DeBody,
+        ncoeff: Option<usize>,
+        components: Option<usize>,
+    ) -> Result<DeLayout, DeError> {
+        if let Some(requested) = components.filter(|&d| d != body.components()) {
+            return Err(DeError::Shape {
+                body,
+                what: "components",
+                stored: body.components(),
+                requested,
+            });
+        }
+        let layout = self.body_layout(body).ok_or(DeError::MissingBody(body))?;
+        if let Some(requested) = ncoeff.filter(|&n| n != layout.ncoeff) {
+            return Err(DeError::Shape {
+                body,
+                what: "coefficients",
+                stored: layout.ncoeff,
+                requested,
+            });
+        }
+        Ok(layout)
+    }
+
+    /// Check that a record holds its two dates and that every body fits
+    /// inside it.
+    fn validate(&self) -> Result<(), DeError> {
+        if self.ncoeff < 2 {
+            return Err(DeError::RecordLength(self.ncoeff));
+        }
+        for (column, layout) in self.layout.iter().enumerate() {
+            let Some(&body) = DeBody::ALL.get(column) else {
+                continue;
+            };
+            if !layout.is_empty()
+                && (layout.offset < 2
+                    || layout.offset + layout.len(body.components()) > self.ncoeff)
+            {
+                return Err(DeError::Layout {
+                    body,
+                    record_len: self.ncoeff,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// Errors
+// ─────────────────────────────────────────────────────────────────────────
+
+/// Why a DE ephemeris could not be read or a table built from it.
+#[derive(Debug)]
+pub enum DeError {
+    /// The file could not be read.
+    Io(std::io::Error),
+    /// Malformed text at `line` (1-based) of an ASCII file.
+    Parse {
+        /// Line number within the file.
+        line: usize,
+        /// What was wrong.
+        message: String,
+    },
+    /// A required header group is missing.
+    MissingGroup(u32),
+    /// A binary file is malformed.
+    Format(String),
+    /// The header places `body` outside a record of `record_len` values.
+    Layout {
+        /// The offending body.
+        body: DeBody,
+        /// Values per record.
+        record_len: usize,
+    },
+    /// The header's `ncoeff` is too small for a record's two dates.
+    RecordLength(usize),
+    /// Record `record` does not have the header's `ncoeff` values.
+    RecordSize {
+        /// Index of the record.
+        record: usize,
+        /// `ncoeff` from the header.
+        expected: usize,
+        /// Values found.
+        found: usize,
+    },
+    /// Record `record` does not start where the previous one ends, or does
+    /// not span the header's interval.
+    NotContiguous {
+        /// Index of the record.
+        record: usize,
+        /// Where the record should start (or end).
+        expected: f64,
+        /// Where it starts (or ends).
+        found: f64,
+    },
+    /// The ephemeris has no records.
+    NoRecords,
+    /// The ephemeris does not contain `body`.
+    MissingBody(DeBody),
+    /// A table of `requested` coefficients or components was asked for a
+    /// body stored with `stored`.
+    Shape {
+        /// The body.
+        body: DeBody,
+        /// `"coefficients"` or `"components"`.
+        what: &'static str,
+        /// What the file holds.
+        stored: usize,
+        /// What was requested.
+        requested: usize,
+    },
+    /// A component beyond the body's [`components`](DeBody::components)
+    /// was asked for.
+    Component {
+        /// The body.
+        body: DeBody,
+        /// The requested component.
+        component: usize,
+    },
+}
+
+impl fmt::Display for DeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "cannot read ephemeris: {e}"),
+            Self::Parse { line, message } => write!(f, "line {line}: {message}"),
+            Self::MissingGroup(group) => write!(f, "header has no group {group}"),
+            Self::Format(message) => write!(f, "malformed binary ephemeris: {message}"),
+            Self::Layout { body, record_len } => write!(
+                f,
+                "{body:?} coefficients do not fit in a record of {record_len} values"
+            ),
+            Self::RecordLength(ncoeff) => write!(
+                f,
+                "records of {ncoeff} values cannot hold their start and end dates"
+            ),
+            Self::RecordSize {
+                record,
+                expected,
+                found,
+            } => write!(f, "record {record} has {found} values, expected {expected}"),
+            Self::NotContiguous {
+                record,
+                expected,
+                found,
+            } => write!(
+                f,
+                "record {record} has boundary {found}, expected {expected}"
+            ),
+            Self::NoRecords => write!(f, "ephemeris has no records"),
+            Self::MissingBody(body) => write!(f, "ephemeris does not contain {body:?}"),
+            Self::Shape {
+                body,
+                what,
+                stored,
+                requested,
+            } => write!(
+                f,
+                "{body:?} is stored with {stored} {what}, requested {requested}"
+            ),
+            Self::Component { body, component } => write!(
+                f,
+                "{body:?} has {} components, requested component {component}",
+                body.components()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for DeError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// DeEphemeris — header plus records
+// ─────────────────────────────────────────────────────────────────────────
+
+/// A DE ephemeris in memory: the header and a contiguous run of records.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeEphemeris {
+    /// The header.
+    header: DeHeader,
+    /// Records back to back, `header.ncoeff` values each.
+    data: Vec<f64>,
+}
+
+impl DeEphemeris {
+    /// An ephemeris with no records yet.
+    ///
+    /// Fails with [`DeError::RecordLength`] or [`DeError::Layout`] if
+    /// the header's records cannot hold their dates or its bodies.
+    pub fn new(header: DeHeader) -> Result<Self, DeError> {
+        header.validate()?;
+        Ok(Self {
+            header,
+            data: Vec::new(),
+        })
+    }
+
+    /// The header.
+    #[inline]
+    pub fn header(&self) -> &DeHeader {
+        &self.header
+    }
+
+    /// Number of records.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.data.len() / self.header.ncoeff.max(1)
+    }
+
+    /// `true` if there are no records.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Record `i`: its start and end Julian days followed by the
+    /// coefficients.
+    ///
+    /// # Panics
+    ///
+    /// If `i >= self.len()`.
+    #[inline]
+    pub fn record(&self, i: usize) -> &[f64] {
+        assert!(i < self.len(), "record {i} out of range");
+        let n = self.header.ncoeff;
+        &self.data[i * n..(i + 1) * n]
+    }
+
+    /// The records, in chronological order.
+    pub fn records(&self) -> impl ExactSizeIterator<Item = &[f64]> + '_ {
+        self.data.chunks_exact(self.header.ncoeff.max(1))
+    }
+
+    /// Start of the first record, or `None` if there are no records.
+    pub fn start(&self) -> Option<f64> {
+        self.data.first().copied()
+    }
+
+    /// End of the last record, or `None` if there are no records.
+    pub fn end(&self) -> Option<f64> {
+        (!self.is_empty()).then(|| self.record(self.len() - 1)[1])
+    }
+
+    /// Append a record.
+    ///
+    /// A record with the same dates as the current last record is skipped,
+    /// since consecutive DE data files repeat their boundary record.
+    /// Otherwise it must start where the last record ends and span the
+    /// header's interval.
+    pub fn push_record(&mut self, record: &[f64]) -> Result<(), DeError> {
+        let index = self.len();
+        if record.len() != self.header.ncoeff {
+            return Err(DeError::RecordSize {
+                record: index,
+                expected: self.header.ncoeff,
+                found: record.len(),
+            });
+        }
+        let (start, end) = (record[0], record[1]);
+        if let Some(last_end) = self.end() {
+            if (start, end) == (self.record(index - 1)[0], last_end) {
+                return Ok(());
+            }
+            if start != last_end {
+                return Err(DeError::NotContiguous {
+                    record: index,
+                    expected: last_end,
+                    found: start,
+                });
+            }
+        }
+        if end - start != self.header.interval {
+            return Err(DeError::NotContiguous {
+                record: index,
+                expected: start + self.header.interval,
+                found: end,
+            });
+        }
+        self.data.extend_from_slice(record);
+        Ok(())
+    }
+
+    /// The layout of `body`, checked against a requested shape and for
+    /// records to build a table from.
+    fn layout_for(
+        &self,
+        body: DeBody,
+        ncoeff: Option<usize>,
+        components: Option<usize>,
+    ) -> Result<DeLayout, DeError> {
+        let layout = self.header.checked_layout(body, ncoeff, components)?;
+        if self.is_empty() {
+            return Err(DeError::NoRecords);
+        }
+        Ok(layout)
+    }
+
+    /// The body as a vector table with one segment per sub-interval.
+    ///
+    /// `N` must equal the body's `ncoeff` and `D` its number of
+    /// components ([`DeBody::components`]).
+    pub fn body_table<const N: usize, const D: usize>(
+        &self,
+        body: DeBody,
+    ) -> Result<ChebyVecSegmentTable<f64, N, D>, DeError> {
+        let layout = self.layout_for(body, Some(N), Some(D))?;
+        let interval = self.header.interval;
+        let mut segments = Vec::with_capacity(self.len() * layout.nsub);
+        for record in self.records() {
+            for j in 0..layout.nsub {
+                segments.push(layout.segment(interval, |i| record[i], j));
+            }
+        }
+        let segment_len = self.header.interval / layout.nsub as f64;
+        Ok(ChebyVecSegmentTable::from_segments(
+            segments,
+            self.data[0],
+            segment_len,
+        ))
+    }
+
+    /// Component `component` of the body as a runtime-sized table with one
+    /// segment per sub-interval.
+    ///
+    /// Fails with [`DeError::Component`] if `component >=
+    /// body.components()`.
+    pub fn component_table(
+        &self,
+        body: DeBody,
+        component: usize,
+    ) -> Result<DynChebySegmentTable<f64>, DeError> {
+        if component >= body.components() {
+            return Err(DeError::Component { body, component });
+        }
+        let layout = self.layout_for(body, None, None)?;
+        let (n, dims) = (layout.ncoeff, body.components());
+        let mut segments = Vec::with_capacity(self.len() * layout.nsub);
+        for record in self.records() {
+            for j in 0..layout.nsub {
+                let base = layout.offset + (j * dims + component) * n;
+                let (mid, half) = layout.sub_interval(self.header.interval, record[0], j);
+                segments.push(DynChebySegment::new(
+                    record[base..base + n].to_vec(),
+                    mid,
+                    half,
+                ));
+            }
+        }
+        let segment_len = self.header.interval / layout.nsub as f64;
+        Ok(DynChebySegmentTable::from_segments(
+            segments,
+            self.data[0],
+            segment_len,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header() -> DeHeader {
+        DeHeader {
+            ksize: 20,
+            ncoeff: 10,
+            title: Vec::new(),
+            start: 0.0,
+            end: 64.0,
+            interval: 32.0,
+            constants: vec![("AU".to_string(), 1.5e8)],
+            layout: vec![
+                DeLayout {
+                    offset: 2,
+                    ncoeff: 2,
+                    nsub: 1,
+                },
+                DeLayout {
+                    offset: 0,
+                    ncoeff: 0,
+                    nsub: 0,
+                },
+            ],
+        }
+    }
+
+    fn record(start: f64) -> Vec<f64> {
+        vec![start, start + 32.0, 1.0, 0.5, 2.0, 0.0, 3.0, -1.0, 9.0, 9.0]
+    }
+
+    #[test]
+    fn test_push_record_checks_continuity() {
+        let mut de = DeEphemeris::new(header()).unwrap();
+        assert_eq!((de.start(), de.end()), (None, None));
+        de.push_record(&record(0.0)).unwrap();
+        // A repeated boundary record is skipped.
+        de.push_record(&record(0.0)).unwrap();
+        assert_eq!(de.len(), 1);
+
+        let err = de.push_record(&record(64.0)).unwrap_err();
+        assert!(
+            matches!(err, DeError::NotContiguous { record: 1, .. }),
+            "{err}"
+        );
+        let mut short = record(32.0);
+        short[1] = 48.0;
+        let err = de.push_record(&short).unwrap_err();
+        assert!(
+            matches!(err, DeError::NotContiguous { found: 48.0, .. }),
+            "{err}"
+        );
+        let err = de.push_record(&[32.0, 64.0]).unwrap_err();
+        assert!(matches!(err, DeError::RecordSize { found: 2, .. }), "{err}");
+
+        de.push_record(&record(32.0)).unwrap();
+        assert_eq!((de.start(), de.end()), (Some(0.0), Some(64.0)));
+        assert_eq!(de.records().len(), 2);
+        assert_eq!(de.header().constant("AU"), Some(1.5e8));
+        assert_eq!(de.header().constant("EMRAT"), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "record 1 out of range")]
+    fn test_record_out_of_range() {
+        let mut de = DeEphemeris::new(header()).unwrap();
+        de.push_record(&record(0.0)).unwrap();
+        assert_eq!(de.record(0), record(0.0));
+        de.record(1);
+    }
+
+    #[test]
+    fn test_tables_and_shape_errors() {
+        let mut de = DeEphemeris::new(header()).unwrap();
+        let err = de.body_table::<2, 3>(DeBody::Mercury).unwrap_err();
+        assert!(matches!(err, DeError::NoRecords), "{err}");
+        de.push_record(&record(0.0)).unwrap();
+        de.push_record(&record(32.0)).unwrap();
+
+        let table = de.body_table::<2, 3>(DeBody::Mercury).unwrap();
+        assert_eq!((table.len(), table.start(), table.end()), (2, 0.0, 64.0));
+        // c0 + c1 τ at τ = 0.5 in the first record.
+        assert_eq!(table.eval(24.0), Some([1.25, 2.0, 2.5]));
+        let y = de.component_table(DeBody::Mercury, 1).unwrap();
+        assert_eq!(y.eval(48.0), Some(2.0));
+
+        let err = de.body_table::<3, 3>(DeBody::Mercury).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Mercury is stored with 2 coefficients, requested 3"
+        );
+        let err = de.body_table::<2, 2>(DeBody::Mercury).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                DeError::Shape {
+                    what: "components",
+                    ..
+                }
+            ),
+            "{err}"
+        );
+        for body in [DeBody::Venus, DeBody::Mars] {
+            let err = de.component_table(body, 0).unwrap_err();
+            assert!(matches!(err, DeError::MissingBody(b) if b == body), "{err}");
+        }
+        let err = de.component_table(DeBody::Mercury, 3).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Mercury has 3 components, requested component 3"
+        );
+    }
+
+    #[test]
+    fn test_new_validates_header() {
+        let empty = DeHeader {
+            ncoeff: 0,
+            layout: Vec::new(),
+            ..header()
+        };
+        let err = DeEphemeris::new(empty).unwrap_err();
+        assert!(matches!(err, DeError::RecordLength(0)), "{err}");
+
+        let mut overflow = header();
+        overflow.layout[0].nsub = 2;
+        let err = DeEphemeris::new(overflow).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                DeError::Layout {
+                    body: DeBody::Mercury,
+                    record_len: 10
+                }
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_body_columns() {
+        for (i, body) in DeBody::ALL.into_iter().enumerate() {
+            assert_eq!(body.column(), i);
+        }
+        assert_eq!(DeBody::Nutations.components(), 2);
+        assert_eq!(DeBody::TtMinusTdb.components(), 1);
+        assert_eq!(DeBody::Moon.components(), 3);
+    }
+}
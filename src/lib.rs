@@ -10,26 +10,99 @@
 //! interpolation, as used in JPL DE-series ephemerides and cached
 //! lunar/planetary position evaluators:
 //!
-//! 1. **[`nodes`]** — Chebyshev node generation on `[-1, 1]` or mapped to
-//!    an arbitrary interval.
+//! 1. **[`nodes`]** — Chebyshev (Gauss or Lobatto) node generation on
+//!    `[-1, 1]` or mapped to an arbitrary interval.
 //! 2. **[`fit`]** — DCT-based coefficient computation from function values
 //!    at Chebyshev nodes.
 //! 3. **[`eval`]** — Clenshaw-recurrence evaluation of a Chebyshev series
-//!    (value, derivative, or both in one pass).
-//! 4. **[`segment`]** — Piecewise Chebyshev approximation over uniform time
-//!    segments, with automatic lookup and `t → τ` normalisation.
+//!    (value, derivatives of any order, or several at once in one pass),
+//!    plus coefficient-space differentiation ([`derivative_coeffs`]) and
+//!    integration ([`integral_coeffs`], [`definite_integral`]).
+//! 4. **[`segment`]** — Piecewise Chebyshev approximation over uniform or
+//!    variable-length time segments, with automatic lookup and `t → τ`
+//!    normalisation.
+//! 5. **[`cursor`]** — Stateful [`Cursor`]s that cache the current segment
+//!    for fast sequential (forward or backward) table queries.
+//! 6. **[`adaptive`]** — Error-controlled segmentation: bisect until every
+//!    segment meets a requested tolerance, yielding a [`ChebyVarTable`].
+//! 7. **[`lstsq`]** — Weighted, optionally ridge-regularised least-squares
+//!    fitting from irregularly spaced samples.
+//! 8. **[`series`]** — Runtime-sized counterparts of the above
+//!    ([`ChebySeries`], [`DynChebySegment`], [`DynChebySegmentTable`]) for
+//!    when the degree is only known at runtime.
+//! 9. **[`vector`]** — Vector-valued segments ([`ChebyVecSegment`],
+//!    [`ChebyVecSegmentTable`]) evaluating `D` components that share a
+//!    domain with one lookup and one interleaved Clenshaw pass.
+//! 10. **[`axis`]** — Typed independent variables: segments and tables
+//!     over a `qtty` time axis return derivatives and integrals in the
+//!     matching units ([`ChebyAxis`], [`ChebyCalculus`]).
+//! 11. **[`epoch`]** — Two-part times ([`SplitTime`]) for evaluating
+//!     long-span tables (e.g. Julian dates) at full precision.
+//! 12. **`jpl`** (feature `jpl`) — Readers for JPL DE ephemerides, giving
+//!     per-body vector tables.
+//! 13. **`spk`** (feature `spk`) — Reader and writer for SPICE SPK kernels
+//!     with Chebyshev (type 2 and 3) segments.
+//! 14. **[`storage`]** — A versioned, checksummed binary file format for
+//!     saving and loading segment tables.
+//! 15. **[`view`]** — [`ChebySegmentTableView`], a table evaluated in place
+//!     from a borrowed table file or `f64` slice, without copying.
+//! 16. **`serialize`** (feature `serde`) — `serde` support for segments,
+//!     tables and series, recording units and validating on load.
+//! 17. **[`codegen`]** — Rust source generation, e.g. from a build script,
+//!     embedding tables as `static` data evaluated without allocation.
 //!
 //! All core functions are generic over [`ChebyScalar`], so they work with
-//! raw `f64` as well as typed quantities (`qtty::Quantity<U>`).
+//! raw `f64` or `f32` as well as typed quantities (`qtty::Quantity<U>`) and
+//! small fixed-size vectors of any of them ([`Vector`], [`Vec3`]). The
+//! arithmetic runs in the scalar's [`ChebyField`]; `f32` tables can also
+//! be evaluated with `f64` accumulation ([`evaluate_widened`]).
 
+pub mod adaptive;
+pub mod axis;
+#[cfg(any(feature = "jpl", feature = "spk"))]
+mod bytes;
+mod calculus;
+pub mod codegen;
+pub mod cursor;
+mod dct;
+pub mod epoch;
 mod eval;
 mod fit;
+mod grid;
+#[cfg(feature = "jpl")]
+pub mod jpl;
+mod linalg;
+pub mod lstsq;
 mod nodes;
 pub mod scalar;
 pub mod segment;
+#[cfg(feature = "serde")]
+pub mod serialize;
+pub mod series;
+#[cfg(feature = "spk")]
+pub mod spk;
+pub mod storage;
+pub mod vector;
+pub mod view;
 
-pub use eval::{evaluate, evaluate_both, evaluate_derivative};
-pub use fit::{fit_coeffs, fit_from_fn};
-pub use nodes::{nodes, nodes_mapped};
-pub use scalar::ChebyScalar;
-pub use segment::{ChebySegment, ChebySegmentTable};
+pub use axis::{ChebyAxis, ChebyCalculus};
+pub use calculus::{definite_integral, derivative_coeffs, derivative_coeffs_dyn, integral_coeffs};
+pub use cursor::{Cursor, SegmentEval, SegmentLookup};
+pub use epoch::SplitTime;
+pub use eval::{
+    evaluate, evaluate_both, evaluate_both_widened, evaluate_derivative, evaluate_nth_derivative,
+    evaluate_up_to, evaluate_widened,
+};
+pub use fit::{
+    fit_coeffs, fit_coeffs_direct, fit_coeffs_dyn, fit_coeffs_fast, fit_coeffs_lobatto,
+    fit_from_fn, fit_from_fn_dyn, fit_from_fn_lobatto, fit_hermite, HermiteError,
+    FAST_FIT_THRESHOLD,
+};
+pub use nodes::{
+    lobatto_nodes, lobatto_nodes_mapped, nodes, nodes_dyn, nodes_mapped, nodes_mapped_dyn,
+};
+pub use scalar::{ChebyField, ChebyNorm, ChebyScalar, Vec3, Vector};
+pub use segment::{ChebySegment, ChebySegmentTable, ChebyVarTable, VarTableError};
+pub use series::{ChebySeries, DynChebySegment, DynChebySegmentTable};
+pub use vector::{ChebyVecSegment, ChebyVecSegmentTable, ComponentError};
+pub use view::ChebySegmentTableView;
@@ -0,0 +1,179 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! Small dense linear solvers used by the least-squares fitting routines.
+//!
+//! Matrices are real (`f64`, row-major) while right-hand sides may be any
+//! [`ChebyScalar`]: every elimination step only adds right-hand side
+//! entries together and scales them by matrix entries, so fitted
+//! coefficients keep the type (and units) of the sampled values.
+
+use crate::scalar::ChebyScalar;
+
+/// Solve the square system `a · x = b` in place by Gaussian elimination
+/// with partial pivoting.
+///
+/// `a` is `n × n`, row-major, with `n = b.len()`; it is destroyed. On
+/// success `b` holds `x`. Returns `None` if `a` is numerically singular.
+pub(crate) fn solve<T: ChebyScalar<Field = f64>>(a: &mut [f64], b: &mut [T]) -> Option<()> {
+    let n = b.len();
+    debug_assert_eq!(a.len(), n * n);
+
+    let scale = a.iter().fold(0.0_f64, |m, x| m.max(x.abs()));
+    let tiny = scale * n as f64 * f64::EPSILON;
+
+    for col in 0..n {
+        let pivot =
+            (col..n).max_by(|&i, &j| a[i * n + col].abs().total_cmp(&a[j * n + col].abs()))?;
+        let pivot_abs = a[pivot * n + col].abs();
+        if pivot_abs <= tiny || pivot_abs.is_nan() {
+            return None;
+        }
+        if pivot != col {
+            for k in 0..n {
+                a.swap(col * n + k, pivot * n + k);
+            }
+            b.swap(col, pivot);
+        }
+
+        let inv = 1.0 / a[col * n + col];
+        for row in col + 1..n {
+            let factor = a[row * n + col] * inv;
+            if factor == 0.0 {
+                continue;
+            }
+            for k in col..n {
+                a[row * n + k] -= factor * a[col * n + k];
+            }
+            b[row] = b[row] - b[col] * factor;
+        }
+    }
+
+    for row in (0..n).rev() {
+        let mut acc = b[row];
+        for k in row + 1..n {
+            acc = acc - b[k] * a[row * n + k];
+        }
+        b[row] = acc / a[row * n + row];
+    }
+    Some(())
+}
+
+/// Solve the least-squares problem `min ‖a · x − b‖` in place by
+/// Householder QR.
+///
+/// `a` is `m × n`, row-major, with `m = b.len() ≥ n`; it is destroyed. On
+/// success the first `n` entries of `b` hold `x`. Returns `None` if `a`
+/// is numerically rank-deficient.
+pub(crate) fn least_squares<T: ChebyScalar<Field = f64>>(
+    a: &mut [f64],
+    n: usize,
+    b: &mut [T],
+) -> Option<()> {
+    let m = b.len();
+    debug_assert_eq!(a.len(), m * n);
+    if m < n {
+        return None;
+    }
+
+    let scale = a.iter().fold(0.0_f64, |s, x| s.max(x.abs()));
+    let tiny = scale * m.max(n) as f64 * f64::EPSILON;
+    let mut v = vec![0.0_f64; m];
+
+    for col in 0..n {
+        // Householder vector for a[col.., col].
+        let norm = (col..m).map(|i| a[i * n + col].powi(2)).sum::<f64>().sqrt();
+        if norm <= tiny || norm.is_nan() {
+            return None;
+        }
+        let alpha = if a[col * n + col] > 0.0 { -norm } else { norm };
+        for i in col..m {
+            v[i] = a[i * n + col];
+        }
+        v[col] -= alpha;
+        let vtv: f64 = (col..m).map(|i| v[i] * v[i]).sum();
+
+        // Apply H = I − 2vvᵀ/(vᵀv) to the remaining columns and to b.
+        a[col * n + col] = alpha;
+        for i in col + 1..m {
+            a[i * n + col] = 0.0;
+        }
+        for k in col + 1..n {
+            let dot: f64 = (col..m).map(|i| v[i] * a[i * n + k]).sum();
+            let f = 2.0 * dot / vtv;
+            for i in col..m {
+                a[i * n + k] -= f * v[i];
+            }
+        }
+        let mut dot = T::zero();
+        for i in col..m {
+            dot = dot + b[i] * v[i];
+        }
+        let f = 2.0 / vtv;
+        for i in col..m {
+            b[i] = b[i] - dot * (f * v[i]);
+        }
+    }
+
+    // Back-substitution with the upper-triangular R.
+    for row in (0..n).rev() {
+        let mut acc = b[row];
+        for k in row + 1..n {
+            acc = acc - b[k] * a[row * n + k];
+        }
+        b[row] = acc / a[row * n + row];
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_with_pivoting() {
+        // First pivot is zero, forcing a row swap.
+        let mut a = vec![0.0, 2.0, 1.0, 1.0, 1.0, 0.0, 3.0, 0.0, 1.0];
+        let x = [1.0, -2.0, 0.5];
+        let mut b: Vec<f64> = (0..3)
+            .map(|i| (0..3).map(|j| a[i * 3 + j] * x[j]).sum())
+            .collect();
+        solve(&mut a, &mut b).unwrap();
+        for (got, want) in b.iter().zip(&x) {
+            assert!((got - want).abs() < 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_solve_singular() {
+        let mut a = vec![1.0, 2.0, 2.0, 4.0];
+        let mut b = vec![1.0, 2.0];
+        assert!(solve(&mut a, &mut b).is_none());
+    }
+
+    #[test]
+    fn test_least_squares_line_fit() {
+        // y = 2 + 3x with alternating ±0.1 noise, which is orthogonal to
+        // the basis at these symmetric points.
+        let xs = [-1.5, -0.5, 0.5, 1.5];
+        let noise = [0.1, -0.1, -0.1, 0.1];
+        let mut a: Vec<f64> = xs.iter().flat_map(|&x| [1.0, x]).collect();
+        let mut b: Vec<f64> = xs
+            .iter()
+            .zip(&noise)
+            .map(|(x, e)| 2.0 + 3.0 * x + e)
+            .collect();
+        least_squares(&mut a, 2, &mut b).unwrap();
+        assert!((b[0] - 2.0).abs() < 1e-14);
+        assert!((b[1] - 3.0).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_least_squares_rank_deficient() {
+        let mut a = vec![1.0, 2.0, 2.0, 4.0, 3.0, 6.0];
+        let mut b = vec![1.0, 2.0, 3.0];
+        assert!(least_squares(&mut a, 2, &mut b).is_none());
+        let mut short = vec![1.0, 2.0];
+        assert!(least_squares(&mut short, 2, &mut [1.0]).is_none());
+    }
+}
@@ -0,0 +1,290 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! Least-squares fitting from arbitrary, irregularly spaced samples.
+//!
+//! The DCT-based routines in [`fit`](crate::fit_coeffs) need values at
+//! the canonical Chebyshev nodes. [`fit_least_squares`] instead accepts
+//! samples at any times inside the domain — observations, or tabulated
+//! ephemerides at uniform epochs — and minimises
+//!
+//! ```text
+//! Σ_i w_i |p(t_i) − y_i|² + λ Σ_j |c_j|²
+//! ```
+//!
+//! over the coefficients `c_j` of a degree-`d` series `p`. The optional
+//! ridge term `λ` (Tikhonov regularisation) damps the coefficients when
+//! the data are noisy or sparse. The problem is solved by Householder QR
+//! on the weighted, augmented design matrix.
+
+use crate::fit;
+use crate::linalg;
+use crate::scalar::ChebyNorm;
+use crate::series::DynChebySegment;
+use std::fmt;
+
+/// Reasons [`fit_least_squares`] can fail.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LeastSquaresError {
+    /// `times`, `values` (and `weights`, if given) differ in length.
+    LengthMismatch {
+        /// Number of times.
+        times: usize,
+        /// Number of values (or weights).
+        other: usize,
+    },
+    /// The domain is empty, reversed or not finite.
+    InvalidDomain,
+    /// Sample `index` lies outside the domain.
+    OutOfDomain {
+        /// Index of the offending sample.
+        index: usize,
+    },
+    /// Weight `index` is negative or not finite.
+    InvalidWeight {
+        /// Index of the offending weight.
+        index: usize,
+    },
+    /// The ridge strength is negative or not finite.
+    InvalidRidge,
+    /// The samples cannot determine the coefficients (too few distinct
+    /// times for the degree, or all weights zero) and no ridge term was
+    /// given.
+    Underdetermined,
+}
+
+impl fmt::Display for LeastSquaresError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LengthMismatch { times, other } => {
+                write!(f, "{times} times but {other} values or weights")
+            }
+            Self::InvalidDomain => write!(f, "domain must be a finite interval with start < end"),
+            Self::OutOfDomain { index } => write!(f, "sample {index} lies outside the domain"),
+            Self::InvalidWeight { index } => {
+                write!(f, "weight {index} is negative or not finite")
+            }
+            Self::InvalidRidge => write!(f, "ridge strength must be finite and non-negative"),
+            Self::Underdetermined => write!(f, "samples do not determine the coefficients"),
+        }
+    }
+}
+
+impl std::error::Error for LeastSquaresError {}
+
+/// Residual statistics of a least-squares fit, in the units of the values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResidualStats {
+    /// Number of samples.
+    pub count: usize,
+    /// Root-mean-square residual, unweighted.
+    pub rms: f64,
+    /// Root-mean-square residual, weighted by the sample weights.
+    pub weighted_rms: f64,
+    /// Largest absolute residual.
+    pub max_abs: f64,
+}
+
+/// Result of [`fit_least_squares`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeastSquaresFit<T: ChebyNorm> {
+    /// The fitted segment, with `degree + 1` coefficients. Convert with
+    /// `try_into()` to get a const-generic [`ChebySegment`](crate::ChebySegment).
+    pub segment: DynChebySegment<T>,
+    /// Residual statistics over the input samples.
+    pub stats: ResidualStats,
+}
+
+/// Fit a degree-`degree` Chebyshev series on `domain = (start, end)` to
+/// samples `values[i]` taken at `times[i]`.
+///
+/// - `weights` — optional non-negative per-sample weights (default 1).
+/// - `ridge` — Tikhonov regularisation strength `λ ≥ 0`; `0` disables it.
+///
+/// Every time must lie in `[start, end]`.
+///
+/// # Example
+///
+/// ```
+/// use cheby::lstsq::fit_least_squares;
+///
+/// let times: Vec<f64> = (0..50).map(|i| i as f64 * 0.1).collect();
+/// let values: Vec<f64> = times.iter().map(|t| t.sin()).collect();
+/// let fit = fit_least_squares(&times, &values, None, 12, (0.0, 4.9), 0.0).unwrap();
+/// assert!(fit.stats.max_abs < 1e-8);
+/// assert!((fit.segment.eval(2.25) - 2.25_f64.sin()).abs() < 1e-8);
+/// ```
+pub fn fit_least_squares<T: ChebyNorm>(
+    times: &[f64],
+    values: &[T],
+    weights: Option<&[f64]>,
+    degree: usize,
+    domain: (f64, f64),
+    ridge: f64,
+) -> Result<LeastSquaresFit<T>, LeastSquaresError> {
+    let m = times.len();
+    for other in [Some(values.len()), weights.map(<[f64]>::len)]
+        .into_iter()
+        .flatten()
+    {
+        if other != m {
+            return Err(LeastSquaresError::LengthMismatch { times: m, other });
+        }
+    }
+    let (start, end) = domain;
+    if !(start < end && (end - start).is_finite()) {
+        return Err(LeastSquaresError::InvalidDomain);
+    }
+    if let Some(index) = times.iter().position(|t| !(start..=end).contains(t)) {
+        return Err(LeastSquaresError::OutOfDomain { index });
+    }
+    if let Some(index) = weights.and_then(|w| w.iter().position(|w| !(*w >= 0.0 && w.is_finite())))
+    {
+        return Err(LeastSquaresError::InvalidWeight { index });
+    }
+    if !(ridge >= 0.0 && ridge.is_finite()) {
+        return Err(LeastSquaresError::InvalidRidge);
+    }
+
+    let n = degree + 1;
+    let mid = 0.5 * (start + end);
+    let half = 0.5 * (end - start);
+    let weight = |i: usize| weights.map_or(1.0, |w| w[i]);
+
+    // Augmented system [√W·A; √λ·I] c = [√W·y; 0].
+    let rows = if ridge > 0.0 { m + n } else { m };
+    let mut design = vec![0.0_f64; rows * n];
+    let mut rhs = vec![T::zero(); rows];
+    let mut dbasis = vec![0.0_f64; n];
+    for (i, (&t, &y)) in times.iter().zip(values).enumerate() {
+        let sw = weight(i).sqrt();
+        let row = &mut design[i * n..(i + 1) * n];
+        fit::basis_with_derivative((t - mid) / half, row, &mut dbasis);
+        row.iter_mut().for_each(|a| *a *= sw);
+        rhs[i] = y * sw;
+    }
+    if ridge > 0.0 {
+        let sl = ridge.sqrt();
+        for j in 0..n {
+            design[(m + j) * n + j] = sl;
+        }
+    }
+
+    linalg::least_squares(&mut design, n, &mut rhs).ok_or(LeastSquaresError::Underdetermined)?;
+    rhs.truncate(n);
+    let segment = DynChebySegment::new(rhs, mid, half);
+
+    let mut sum_sq = 0.0;
+    let mut sum_wsq = 0.0;
+    let mut sum_w = 0.0;
+    let mut max_abs = 0.0_f64;
+    for (i, (&t, &y)) in times.iter().zip(values).enumerate() {
+        let r = (segment.eval(t) - y).norm();
+        sum_sq += r * r;
+        sum_wsq += weight(i) * r * r;
+        sum_w += weight(i);
+        max_abs = max_abs.max(r);
+    }
+    let stats = ResidualStats {
+        count: m,
+        rms: if m > 0 {
+            (sum_sq / m as f64).sqrt()
+        } else {
+            0.0
+        },
+        weighted_rms: if sum_w > 0.0 {
+            (sum_wsq / sum_w).sqrt()
+        } else {
+            0.0
+        },
+        max_abs,
+    };
+
+    Ok(LeastSquaresFit { segment, stats })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_polynomial_recovered() {
+        // Irregular epochs; a cubic is reproduced exactly at degree 3.
+        let times = [0.0, 0.13, 0.4, 0.41, 1.2, 1.9, 2.5, 2.51, 3.0];
+        let f = |t: f64| 1.0 - 2.0 * t + 0.5 * t * t * t;
+        let values: Vec<f64> = times.iter().map(|&t| f(t)).collect();
+        let fit = fit_least_squares(&times, &values, None, 3, (0.0, 3.0), 0.0).unwrap();
+
+        assert_eq!(fit.segment.len(), 4);
+        assert_eq!(fit.stats.count, times.len());
+        assert!(fit.stats.max_abs < 1e-12);
+        for &t in &[0.2, 1.5, 2.9] {
+            assert!((fit.segment.eval(t) - f(t)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_weights_and_ridge() {
+        let times: Vec<f64> = (0..=20).map(|i| i as f64 * 0.05).collect();
+        let mut values: Vec<f64> = times.iter().map(|t| t.exp()).collect();
+        values[7] += 10.0; // outlier
+
+        let plain = fit_least_squares(&times, &values, None, 10, (0.0, 1.0), 0.0).unwrap();
+        let mut weights = vec![1.0; times.len()];
+        weights[7] = 0.0;
+        let weighted =
+            fit_least_squares(&times, &values, Some(&weights), 10, (0.0, 1.0), 0.0).unwrap();
+        assert!((weighted.segment.eval(0.5) - 0.5_f64.exp()).abs() < 1e-8);
+        assert!(plain.stats.rms > weighted.stats.weighted_rms);
+        assert!(weighted.stats.weighted_rms < 1e-8);
+
+        // The ridge term shrinks the coefficients.
+        let ridged = fit_least_squares(&times, &values, None, 10, (0.0, 1.0), 1.0).unwrap();
+        let norm = |c: &[f64]| c.iter().map(|x| x * x).sum::<f64>();
+        assert!(norm(&ridged.segment.coeffs) < norm(&plain.segment.coeffs));
+
+        // With a ridge term even a single sample gives a solution.
+        assert!(fit_least_squares(&[0.5], &[1.0], None, 4, (0.0, 1.0), 1e-3).is_ok());
+    }
+
+    #[test]
+    fn test_input_validation() {
+        let t = [0.0, 0.5, 1.0];
+        let v = [1.0, 2.0, 3.0];
+        assert_eq!(
+            fit_least_squares(&t, &v[..2], None, 1, (0.0, 1.0), 0.0),
+            Err(LeastSquaresError::LengthMismatch { times: 3, other: 2 })
+        );
+        assert_eq!(
+            fit_least_squares(&t, &v, Some(&[1.0, -1.0, 1.0]), 1, (0.0, 1.0), 0.0),
+            Err(LeastSquaresError::InvalidWeight { index: 1 })
+        );
+        assert_eq!(
+            fit_least_squares(&t, &v, None, 1, (1.0, 0.0), 0.0),
+            Err(LeastSquaresError::InvalidDomain)
+        );
+        assert_eq!(
+            fit_least_squares(&t, &v, None, 1, (0.0, 0.9), 0.0),
+            Err(LeastSquaresError::OutOfDomain { index: 2 })
+        );
+        assert_eq!(
+            fit_least_squares(&t, &v, None, 1, (0.0, 1.0), -1.0),
+            Err(LeastSquaresError::InvalidRidge)
+        );
+        assert_eq!(
+            fit_least_squares(&t, &v, None, 5, (0.0, 1.0), 0.0),
+            Err(LeastSquaresError::Underdetermined)
+        );
+    }
+
+    #[test]
+    fn test_quantity_values() {
+        use qtty::Quantity;
+        type Kilometers = Quantity<qtty::Kilometer>;
+
+        let times = [0.0, 1.0, 2.0, 3.0];
+        let values: Vec<Kilometers> = times.iter().map(|&t| Kilometers::new(7.0 * t)).collect();
+        let fit = fit_least_squares(&times, &values, None, 1, (0.0, 3.0), 0.0).unwrap();
+        assert!((fit.segment.eval(1.5).value() - 10.5).abs() < 1e-12);
+    }
+}
@@ -12,6 +12,17 @@
 //! ```text
 //! ξ_k = cos(π(2k+1) / (2N)),  k = 0, …, N-1
 //! ```
+//!
+//! Chebyshev–Lobatto nodes (extrema of `T_{N-1}`) include both endpoints:
+//!
+//! ```text
+//! η_k = cos(πk / (N-1)),  k = 0, …, N-1
+//! ```
+//!
+//! Fitting at these nodes (see
+//! [`fit_coeffs_lobatto`](crate::fit_coeffs_lobatto)) makes each segment
+//! reproduce `f` at its endpoints, so adjacent segments share their
+//! boundary values.
 
 /// Compute `N` Chebyshev nodes on `[-1, 1]`.
 ///
@@ -27,11 +38,7 @@
 #[inline]
 pub fn nodes<const N: usize>() -> [f64; N] {
     let mut out = [0.0_f64; N];
-    let n = N as f64;
-    for (k, x) in out.iter_mut().enumerate() {
-        let arg = std::f64::consts::PI * (2.0 * k as f64 + 1.0) / (2.0 * n);
-        *x = arg.cos();
-    }
+    fill_nodes(&mut out);
     out
 }
 
@@ -48,16 +55,107 @@ pub fn nodes<const N: usize>() -> [f64; N] {
 /// ```
 #[inline]
 pub fn nodes_mapped<const N: usize>(start: f64, end: f64) -> [f64; N] {
-    let mid = 0.5 * (start + end);
-    let half = 0.5 * (end - start);
-    let unit = nodes::<N>();
     let mut out = [0.0_f64; N];
-    for (x, &u) in out.iter_mut().zip(unit.iter()) {
-        *x = mid + half * u;
+    fill_nodes(&mut out);
+    map_nodes(&mut out, start, end);
+    out
+}
+
+/// Compute `n` Chebyshev nodes on `[-1, 1]`, with `n` chosen at runtime.
+///
+/// Same ordering and values as [`nodes`], for use with
+/// [`ChebySeries`](crate::ChebySeries) and the other dynamic-degree types.
+///
+/// # Example
+///
+/// ```
+/// let xi = cheby::nodes_dyn(5);
+/// assert_eq!(xi, cheby::nodes::<5>());
+/// ```
+pub fn nodes_dyn(n: usize) -> Vec<f64> {
+    let mut out = vec![0.0_f64; n];
+    fill_nodes(&mut out);
+    out
+}
+
+/// Compute `n` Chebyshev nodes mapped to `[start, end]`, with `n` chosen
+/// at runtime.
+///
+/// Runtime-sized counterpart of [`nodes_mapped`].
+pub fn nodes_mapped_dyn(n: usize, start: f64, end: f64) -> Vec<f64> {
+    let mut out = nodes_dyn(n);
+    map_nodes(&mut out, start, end);
+    out
+}
+
+/// Compute `N` Chebyshev–Lobatto nodes on `[-1, 1]`.
+///
+/// Returns the nodes in descending order, from exactly `+1` to exactly
+/// `−1`. For `N = 1` the single node is `0`.
+///
+/// # Example
+///
+/// ```
+/// let eta: [f64; 5] = cheby::lobatto_nodes();
+/// assert_eq!((eta[0], eta[2], eta[4]), (1.0, 0.0, -1.0));
+/// assert_eq!(eta[1], -eta[3]);
+/// ```
+#[inline]
+pub fn lobatto_nodes<const N: usize>() -> [f64; N] {
+    let mut out = [0.0_f64; N];
+    if N > 1 {
+        let m = (N - 1) as f64;
+        for (k, x) in out.iter_mut().enumerate() {
+            // sin form of cos(πk/m): exactly antisymmetric, exact 0 and ±1.
+            let arg = std::f64::consts::PI * (m - 2.0 * k as f64) / (2.0 * m);
+            *x = arg.sin();
+        }
     }
     out
 }
 
+/// Compute `N` Chebyshev–Lobatto nodes mapped to `[start, end]`.
+///
+/// The first and last nodes are exactly `end` and `start`, so that
+/// adjacent segments sample `f` at the very same boundary time.
+///
+/// # Example
+///
+/// ```
+/// let t: [f64; 7] = cheby::lobatto_nodes_mapped(0.1, 0.7);
+/// assert_eq!((t[0], t[6]), (0.7, 0.1));
+/// ```
+#[inline]
+pub fn lobatto_nodes_mapped<const N: usize>(start: f64, end: f64) -> [f64; N] {
+    let mut out = lobatto_nodes::<N>();
+    map_nodes(&mut out, start, end);
+    if N > 1 {
+        out[0] = end;
+        out[N - 1] = start;
+    }
+    out
+}
+
+/// Write the `out.len()` Chebyshev nodes on `[-1, 1]` into `out`.
+#[inline]
+fn fill_nodes(out: &mut [f64]) {
+    let n = out.len() as f64;
+    for (k, x) in out.iter_mut().enumerate() {
+        let arg = std::f64::consts::PI * (2.0 * k as f64 + 1.0) / (2.0 * n);
+        *x = arg.cos();
+    }
+}
+
+/// Map nodes on `[-1, 1]` in place to `[start, end]`.
+#[inline]
+fn map_nodes(out: &mut [f64], start: f64, end: f64) {
+    let mid = 0.5 * (start + end);
+    let half = 0.5 * (end - start);
+    for x in out.iter_mut() {
+        *x = mid + half * *x;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,4 +202,32 @@ mod tests {
         let mean: f64 = t.iter().sum::<f64>() / 9.0;
         assert!((mean - 2.0).abs() < 1e-10);
     }
+
+    #[test]
+    fn test_lobatto_nodes() {
+        let eta: [f64; 9] = lobatto_nodes();
+        assert_eq!(eta[0], 1.0);
+        assert_eq!(eta[8], -1.0);
+        assert_eq!(eta[4], 0.0);
+        for k in 0..9 {
+            let expected = (std::f64::consts::PI * k as f64 / 8.0).cos();
+            assert!((eta[k] - expected).abs() < 1e-15);
+            assert_eq!(eta[k], -eta[8 - k]);
+        }
+        assert_eq!(lobatto_nodes::<1>(), [0.0]);
+
+        let t: [f64; 9] = lobatto_nodes_mapped(0.3, 1.1);
+        assert_eq!(t[0], 1.1);
+        assert_eq!(t[8], 0.3);
+        assert!(t.windows(2).all(|w| w[0] > w[1]));
+    }
+
+    #[test]
+    fn test_nodes_dyn_matches_const() {
+        let fixed: [f64; 7] = nodes();
+        assert_eq!(nodes_dyn(7), fixed);
+        let mapped: [f64; 7] = nodes_mapped(-3.0, 5.0);
+        assert_eq!(nodes_mapped_dyn(7, -3.0, 5.0), mapped);
+        assert!(nodes_dyn(0).is_empty());
+    }
 }
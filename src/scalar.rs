@@ -4,10 +4,68 @@
 //! Scalar trait for Chebyshev operations.
 //!
 //! [`ChebyScalar`] abstracts over numeric types that can participate in
-//! Chebyshev evaluation and fitting. Implemented for `f64` and for all
-//! `qtty::Quantity<U>` types.
+//! Chebyshev evaluation and fitting. Implemented for `f64`, `f32`, all
+//! `qtty::Quantity<U>` types, and fixed-size [`Vector`]s of any of them.
+//! Its [`Field`](ChebyScalar::Field) — a [`ChebyField`], `f64` or `f32` —
+//! is the precision the arithmetic runs in.
+//!
+//! [`ChebyNorm`] adds a magnitude, needed wherever errors are measured
+//! (adaptive fitting, residual statistics).
+
+use std::fmt::Debug;
+use std::ops::{Add, Div, Index, IndexMut, Mul, Sub};
 
-use std::ops::{Add, Div, Mul, Sub};
+/// The real field in which Chebyshev arithmetic is carried out: `f64` or
+/// `f32`.
+///
+/// The normalised argument `τ`, the Clenshaw multipliers and the DCT
+/// weights all live in the field; constants are computed in `f64` and
+/// rounded once with [`from_f64`](Self::from_f64).
+pub trait ChebyField:
+    Copy
+    + Debug
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    /// Round an `f64` to this field.
+    fn from_f64(x: f64) -> Self;
+
+    /// Widen to `f64`.
+    fn to_f64(self) -> f64;
+}
+
+impl ChebyField for f64 {
+    #[inline]
+    fn from_f64(x: f64) -> Self {
+        x
+    }
+
+    #[inline]
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
+impl ChebyField for f32 {
+    #[inline]
+    fn from_f64(x: f64) -> Self {
+        x as f32
+    }
+
+    #[inline]
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+/// `x` rounded to the arithmetic field of `T`.
+#[inline]
+pub(crate) fn field<T: ChebyScalar>(x: f64) -> T::Field {
+    T::Field::from_f64(x)
+}
 
 /// A scalar type usable as a Chebyshev coefficient or value.
 ///
@@ -15,23 +73,59 @@ use std::ops::{Add, Div, Mul, Sub};
 /// and the DCT coefficient computation:
 ///
 /// - Addition and subtraction of two values of the same type.
-/// - Multiplication and division by a dimensionless `f64`.
+/// - Multiplication and division by a dimensionless
+///   [`Field`](Self::Field) value.
 /// - A zero element.
+///
+/// `Field` is `f64` for `f64` and `qtty::Quantity<U>`, and `f32` for
+/// `f32`, so `f32` coefficients are evaluated entirely in single
+/// precision. See [`evaluate_widened`](crate::evaluate_widened) for
+/// `f32` storage with `f64` accumulation.
 pub trait ChebyScalar:
     Copy
     + Add<Output = Self>
     + Sub<Output = Self>
-    + Mul<f64, Output = Self>
-    + Div<f64, Output = Self>
+    + Mul<<Self as ChebyScalar>::Field, Output = Self>
+    + Div<<Self as ChebyScalar>::Field, Output = Self>
     + Sized
 {
+    /// The real field the arithmetic is carried out in.
+    type Field: ChebyField;
+
     /// The additive identity (zero).
     fn zero() -> Self;
 }
 
-// ── f64 implementation ──────────────────────────────────────────────────
+/// A [`ChebyScalar`] with a magnitude, used to measure approximation errors.
+///
+/// Error-controlled routines (adaptive fitting, least squares) work in
+/// `f64`, so the field is fixed to `f64`.
+pub trait ChebyNorm: ChebyScalar<Field = f64> {
+    /// A non-negative magnitude of the value, in the value's own units.
+    fn norm(&self) -> f64;
+}
+
+// ── f64 / f32 implementations ───────────────────────────────────────────
 
 impl ChebyScalar for f64 {
+    type Field = f64;
+
+    #[inline]
+    fn zero() -> Self {
+        0.0
+    }
+}
+
+impl ChebyNorm for f64 {
+    #[inline]
+    fn norm(&self) -> f64 {
+        self.abs()
+    }
+}
+
+impl ChebyScalar for f32 {
+    type Field = f32;
+
     #[inline]
     fn zero() -> Self {
         0.0
@@ -44,8 +138,222 @@ impl<U> ChebyScalar for qtty::Quantity<U>
 where
     U: qtty::Unit,
 {
+    type Field = f64;
+
     #[inline]
     fn zero() -> Self {
         Self::new(0.0)
     }
 }
+
+impl<U> ChebyNorm for qtty::Quantity<U>
+where
+    U: qtty::Unit,
+{
+    #[inline]
+    fn norm(&self) -> f64 {
+        self.value().abs()
+    }
+}
+
+// ── Small fixed-size vectors ────────────────────────────────────────────
+
+/// A fixed-size vector of [`ChebyScalar`] components, itself a
+/// [`ChebyScalar`].
+///
+/// Arithmetic is component-wise, so fitting, evaluation and tables work
+/// on whole position or state vectors at once: every node sample and
+/// every coefficient is a `Vector`.
+///
+/// Plain arrays `[T; D]` cannot implement `ChebyScalar` because the
+/// arithmetic operator traits cannot be implemented for them outside the
+/// standard library; `Vector` is a transparent wrapper that converts to
+/// and from `[T; D]` for free. For large tables prefer
+/// [`ChebyVecSegmentTable`](crate::ChebyVecSegmentTable), whose
+/// component-major storage matches the JPL layout.
+///
+/// # Example
+///
+/// ```
+/// use cheby::{ChebySegmentTable, Vec3};
+///
+/// let table: ChebySegmentTable<Vec3, 12> =
+///     ChebySegmentTable::from_fn(|t| Vec3::new([t.cos(), t.sin(), 0.0]), 0.0, 6.0, 1.0);
+/// let p = table.eval(1.0).unwrap();
+/// assert!((p.x() - 1.0_f64.cos()).abs() < 1e-12);
+/// assert!((p.y() - 1.0_f64.sin()).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(transparent)]
+pub struct Vector<T, const D: usize>(pub [T; D]);
+
+/// A three-component [`Vector`], `f64` by default.
+pub type Vec3<T = f64> = Vector<T, 3>;
+
+impl<T: ChebyScalar, const D: usize> Vector<T, D> {
+    /// Wrap an array of components.
+    #[inline]
+    pub const fn new(components: [T; D]) -> Self {
+        Self(components)
+    }
+
+    /// The components as an array.
+    #[inline]
+    pub fn into_array(self) -> [T; D] {
+        self.0
+    }
+}
+
+impl<T: ChebyScalar> Vector<T, 3> {
+    /// First component.
+    #[inline]
+    pub fn x(&self) -> T {
+        self.0[0]
+    }
+
+    /// Second component.
+    #[inline]
+    pub fn y(&self) -> T {
+        self.0[1]
+    }
+
+    /// Third component.
+    #[inline]
+    pub fn z(&self) -> T {
+        self.0[2]
+    }
+}
+
+impl<T, const D: usize> From<[T; D]> for Vector<T, D> {
+    #[inline]
+    fn from(components: [T; D]) -> Self {
+        Self(components)
+    }
+}
+
+impl<T, const D: usize> From<Vector<T, D>> for [T; D] {
+    #[inline]
+    fn from(v: Vector<T, D>) -> Self {
+        v.0
+    }
+}
+
+impl<T, const D: usize> Index<usize> for Vector<T, D> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, i: usize) -> &T {
+        &self.0[i]
+    }
+}
+
+impl<T, const D: usize> IndexMut<usize> for Vector<T, D> {
+    #[inline]
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        &mut self.0[i]
+    }
+}
+
+impl<T: ChebyScalar, const D: usize> Add for Vector<T, D> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self(std::array::from_fn(|i| self.0[i] + rhs.0[i]))
+    }
+}
+
+impl<T: ChebyScalar, const D: usize> Sub for Vector<T, D> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self(std::array::from_fn(|i| self.0[i] - rhs.0[i]))
+    }
+}
+
+impl<T: ChebyScalar, const D: usize> Mul<T::Field> for Vector<T, D> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: T::Field) -> Self {
+        Self(self.0.map(|c| c * rhs))
+    }
+}
+
+impl<T: ChebyScalar, const D: usize> Div<T::Field> for Vector<T, D> {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: T::Field) -> Self {
+        Self(self.0.map(|c| c / rhs))
+    }
+}
+
+impl<T: ChebyScalar, const D: usize> ChebyScalar for Vector<T, D> {
+    type Field = T::Field;
+
+    #[inline]
+    fn zero() -> Self {
+        Self([T::zero(); D])
+    }
+}
+
+impl<T: ChebyNorm, const D: usize> ChebyNorm for Vector<T, D> {
+    /// Euclidean norm of the component magnitudes.
+    #[inline]
+    fn norm(&self) -> f64 {
+        self.0.iter().map(|c| c.norm().powi(2)).sum::<f64>().sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{evaluate, fit_coeffs, fit_from_fn};
+
+    #[test]
+    fn test_vector_arithmetic() {
+        let a = Vec3::new([1.0, 2.0, 3.0]);
+        let b = Vec3::from([0.5, -1.0, 4.0]);
+        assert_eq!(a + b, Vec3::new([1.5, 1.0, 7.0]));
+        assert_eq!(a - b, Vec3::new([0.5, 3.0, -1.0]));
+        assert_eq!(a * 2.0, Vec3::new([2.0, 4.0, 6.0]));
+        assert_eq!(a / 2.0, Vec3::new([0.5, 1.0, 1.5]));
+        assert_eq!(Vec3::<f64>::zero().into_array(), [0.0; 3]);
+        assert_eq!((a.x(), a.y(), a.z()), (1.0, 2.0, 3.0));
+        assert_eq!(Vector::new([3.0, 4.0]).norm(), 5.0);
+    }
+
+    #[test]
+    fn test_vector_fit_matches_components() {
+        let f = |t: f64| Vec3::new([t.exp(), t.sin(), 1.0 - t * t]);
+        let coeffs: [Vec3; 12] = fit_from_fn(f, -0.5, 2.0);
+        for d in 0..3 {
+            let scalar: [f64; 12] = fit_from_fn(|t| f(t)[d], -0.5, 2.0);
+            for (v, s) in coeffs.iter().zip(&scalar) {
+                assert_eq!(v[d], *s);
+            }
+        }
+        let values: [Vec3; 8] = std::array::from_fn(|i| Vec3::new([i as f64, 0.0, -(i as f64)]));
+        let c = fit_coeffs(&values);
+        let tau = crate::nodes::<8>()[3];
+        let back = evaluate(&c, tau);
+        assert!((back - values[3]).norm() < 1e-13);
+    }
+
+    #[test]
+    fn test_vector_of_quantities() {
+        type Kilometers = qtty::Quantity<qtty::Kilometer>;
+
+        let p = Vec3::new([
+            Kilometers::new(3.0),
+            Kilometers::new(0.0),
+            Kilometers::new(4.0),
+        ]);
+        assert_eq!(p.norm(), 5.0);
+        let c: [Vec3<Kilometers>; 6] =
+            fit_from_fn(|t| Vec3::new([Kilometers::new(t); 3]), 0.0, 1.0);
+        assert!((evaluate(&c, 0.0).y().value() - 0.5).abs() < 1e-14);
+    }
+}
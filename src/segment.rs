@@ -9,10 +9,20 @@
 //! A [`ChebySegmentTable`] manages a sequence of uniform-duration segments
 //! with automatic lookup, suitable for caching ephemeris-style data over
 //! a time range.
+//!
+//! A [`ChebyVarTable`] holds contiguous segments of arbitrary duration,
+//! located by binary search over the breakpoints.
 
+use crate::axis::{ChebyAxis, ChebyCalculus, IntoAxis};
+use crate::calculus;
+use crate::cursor::Cursor;
+use crate::epoch::SplitTime;
 use crate::eval;
-use crate::fit;
-use crate::scalar::ChebyScalar;
+use crate::fit::{self, HermiteError};
+use crate::grid::UniformGrid;
+use crate::scalar::{field, ChebyScalar};
+use crate::series::DynChebySegment;
+use std::fmt;
 
 // ─────────────────────────────────────────────────────────────────────────
 // ChebySegment — single segment
@@ -22,50 +32,198 @@ use crate::scalar::ChebyScalar;
 ///
 /// Stores `N` coefficients and the domain `[mid - half, mid + half]`.
 /// The `eval*` methods handle the `t → τ` mapping automatically.
-#[derive(Debug, Clone)]
-pub struct ChebySegment<T: ChebyScalar, const N: usize> {
+///
+/// `X` is the type of the independent variable (see [`axis`](crate::axis)):
+/// `f64` by default, or a `qtty` quantity such as `qtty::Days`, in which
+/// case derivatives and integrals carry the matching units.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChebySegment<T: ChebyScalar, const N: usize, X: ChebyAxis = f64> {
     /// Chebyshev coefficients `c[0..N]`.
     pub coeffs: [T; N],
     /// Midpoint of the segment domain.
-    pub mid: f64,
+    pub mid: X,
     /// Half-width of the segment domain.
-    pub half: f64,
+    pub half: X,
 }
 
-impl<T: ChebyScalar, const N: usize> ChebySegment<T, N> {
+impl<T: ChebyScalar, const N: usize, X: ChebyAxis> ChebySegment<T, N, X> {
     /// Create a segment from pre-computed coefficients and domain.
     #[inline]
-    pub fn new(coeffs: [T; N], mid: f64, half: f64) -> Self {
+    pub fn new(coeffs: [T; N], mid: X, half: X) -> Self {
         Self { coeffs, mid, half }
     }
 
     /// Normalise `t` to `τ ∈ [-1, 1]` within this segment.
     #[inline]
-    pub fn normalise(&self, t: f64) -> f64 {
-        (t - self.mid) / self.half
+    pub fn normalise(&self, t: impl IntoAxis<X>) -> f64 {
+        (t.into_axis().value() - self.mid.value()) / self.half.value()
+    }
+
+    /// `τ` for `t`, rounded to the arithmetic field of `T`.
+    #[inline]
+    fn tau(&self, t: impl IntoAxis<X>) -> T::Field {
+        field::<T>(self.normalise(t))
+    }
+
+    /// The half-width, rounded to the arithmetic field of `T`.
+    #[inline]
+    fn half_field(&self) -> T::Field {
+        field::<T>(self.half.value())
     }
 
     /// Evaluate the Chebyshev polynomial at physical time `t`.
     #[inline]
-    pub fn eval(&self, t: f64) -> T {
-        eval::evaluate(&self.coeffs, self.normalise(t))
+    pub fn eval(&self, t: impl IntoAxis<X>) -> T {
+        eval::evaluate(&self.coeffs, self.tau(t))
     }
 
     /// Evaluate the derivative `df/dt` at physical time `t`.
     ///
     /// Accounts for the chain rule: `df/dt = (df/dτ) · (dτ/dt) = (df/dτ) / half`.
     #[inline]
-    pub fn eval_derivative(&self, t: f64) -> T {
-        let tau = self.normalise(t);
-        eval::evaluate_derivative(&self.coeffs, tau) / self.half
+    pub fn eval_derivative(&self, t: impl IntoAxis<X>) -> T::Derivative
+    where
+        T: ChebyCalculus<X>,
+    {
+        (eval::evaluate_derivative(&self.coeffs, self.tau(t)) / self.half_field()).into_derivative()
     }
 
     /// Evaluate both value and derivative `(f(t), df/dt)` in one pass.
     #[inline]
-    pub fn eval_both(&self, t: f64) -> (T, T) {
-        let tau = self.normalise(t);
+    pub fn eval_both(&self, t: impl IntoAxis<X>) -> (T, T::Derivative)
+    where
+        T: ChebyCalculus<X>,
+    {
+        let (v, d) = eval::evaluate_both(&self.coeffs, self.tau(t));
+        (v, (d / self.half_field()).into_derivative())
+    }
+
+    /// Evaluate at `t` in the wider type `W`, converting each coefficient
+    /// as it is read (e.g. `f32` storage with `f64` accumulation).
+    #[inline]
+    pub fn eval_widened<W: ChebyScalar>(&self, t: impl IntoAxis<X>) -> W
+    where
+        T: Into<W>,
+    {
+        eval::evaluate_widened(&self.coeffs, field::<W>(self.normalise(t)))
+    }
+
+    /// Evaluate `(f(t), df/dt)` in the wider type `W`; see
+    /// [`eval_widened`](Self::eval_widened).
+    #[inline]
+    pub fn eval_both_widened<W>(&self, t: impl IntoAxis<X>) -> (W, W::Derivative)
+    where
+        T: Into<W>,
+        W: ChebyCalculus<X>,
+    {
+        let (v, d) = eval::evaluate_both_widened(&self.coeffs, field::<W>(self.normalise(t)));
+        (v, (d / field::<W>(self.half.value())).into_derivative())
+    }
+
+    /// Definite integral `∫_a^b f(t) dt`.
+    ///
+    /// The limits may lie anywhere, but outside the segment domain the
+    /// polynomial is extrapolated. On an `f64` axis the result keeps the
+    /// type of `T`; on a `qtty` axis it is the product unit (see
+    /// [`ChebyCalculus`]).
+    #[inline]
+    pub fn integrate(&self, a: impl IntoAxis<X>, b: impl IntoAxis<X>) -> T::Integral
+    where
+        T: ChebyCalculus<X>,
+    {
+        let fa = calculus::evaluate_antiderivative(&self.coeffs, self.tau(a));
+        let fb = calculus::evaluate_antiderivative(&self.coeffs, self.tau(b));
+        ((fb - fa) * self.half_field()).into_integral()
+    }
+
+    /// Normalise a two-part time to `τ`, subtracting `mid` before the two
+    /// parts are combined (see [`SplitTime`]).
+    #[inline]
+    pub fn normalise_split(&self, t: impl Into<SplitTime<X>>) -> f64 {
+        t.into().offset_from(self.mid) / self.half.value()
+    }
+
+    /// Evaluate at the two-part time `t`.
+    #[inline]
+    pub fn eval_split(&self, t: impl Into<SplitTime<X>>) -> T {
+        eval::evaluate(&self.coeffs, field::<T>(self.normalise_split(t)))
+    }
+
+    /// Evaluate the derivative `df/dt` at the two-part time `t`.
+    #[inline]
+    pub fn eval_derivative_split(&self, t: impl Into<SplitTime<X>>) -> T::Derivative
+    where
+        T: ChebyCalculus<X>,
+    {
+        self.eval_both_split(t).1
+    }
+
+    /// Evaluate `(f(t), df/dt)` at the two-part time `t` in one pass.
+    #[inline]
+    pub fn eval_both_split(&self, t: impl Into<SplitTime<X>>) -> (T, T::Derivative)
+    where
+        T: ChebyCalculus<X>,
+    {
+        let tau = field::<T>(self.normalise_split(t));
         let (v, d) = eval::evaluate_both(&self.coeffs, tau);
-        (v, d / self.half)
+        (v, (d / self.half_field()).into_derivative())
+    }
+
+    /// The derivative `df/dt` as a segment over the same domain.
+    ///
+    /// The `1/half` scaling is already applied, so the result evaluates
+    /// like [`eval_derivative`](Self::eval_derivative). Its last
+    /// coefficient is zero.
+    pub fn derivative(&self) -> ChebySegment<T::Derivative, N, X>
+    where
+        T: ChebyCalculus<X>,
+    {
+        let half = self.half_field();
+        let coeffs =
+            calculus::derivative_coeffs(&self.coeffs).map(|c| (c / half).into_derivative());
+        ChebySegment::new(coeffs, self.mid, self.half)
+    }
+}
+
+impl<T: ChebyScalar, const N: usize> ChebySegment<T, N> {
+    /// Evaluate the `n`-th derivative `dⁿf/dtⁿ` at physical time `t`.
+    ///
+    /// Applies the chain rule `dⁿf/dtⁿ = (dⁿf/dτⁿ) / halfⁿ`.
+    ///
+    /// Only on `f64` axes, where every derivative keeps the type of `T`.
+    /// On a `qtty` axis the `n`-th derivative has a different unit for
+    /// each `n`; apply [`derivative`](ChebySegment::derivative) `n` times
+    /// instead.
+    #[inline]
+    pub fn eval_nth_derivative(&self, t: f64, n: usize) -> T {
+        let scale = field::<T>(self.half.powi(n as i32));
+        eval::evaluate_nth_derivative(&self.coeffs, self.tau(t), n) / scale
+    }
+
+    /// Evaluate `f(t)` and its first `K` time derivatives in one pass.
+    ///
+    /// Only on `f64` axes, like
+    /// [`eval_nth_derivative`](Self::eval_nth_derivative).
+    #[inline]
+    pub fn eval_up_to<const K: usize>(&self, t: f64) -> (T, [T; K]) {
+        let (v, mut d) = eval::evaluate_up_to::<T, K>(&self.coeffs, self.tau(t));
+        let mut scale = 1.0;
+        for dk in &mut d {
+            scale /= self.half;
+            *dk = *dk * field::<T>(scale);
+        }
+        (v, d)
+    }
+
+    /// The antiderivative `F(t) = constant + ∫_{mid-half}^t f(s) ds` as a
+    /// segment over the same domain, with `N + 1` coefficients.
+    pub fn integral(&self, constant: T) -> DynChebySegment<T> {
+        let scaled = self.coeffs.map(|c| c * self.half_field());
+        DynChebySegment::new(
+            calculus::integral_coeffs(&scaled, constant),
+            self.mid,
+            self.half,
+        )
     }
 }
 
@@ -75,18 +233,17 @@ impl<T: ChebyScalar, const N: usize> ChebySegment<T, N> {
 
 /// A table of uniform-duration Chebyshev segments covering a time range.
 ///
-/// Each segment has the same duration; lookup is O(1) by index.
-#[derive(Debug, Clone)]
-pub struct ChebySegmentTable<T: ChebyScalar, const N: usize> {
-    /// Start of the first segment.
-    start: f64,
-    /// Duration of each segment.
-    segment_len: f64,
+/// Each segment has the same duration; lookup is O(1) by index. `X` is the
+/// type of the independent variable, as for [`ChebySegment`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChebySegmentTable<T: ChebyScalar, const N: usize, X: ChebyAxis = f64> {
+    /// Start and segment length, in the unit of `X`.
+    grid: UniformGrid,
     /// Segments, in chronological order.
-    segments: Vec<ChebySegment<T, N>>,
+    segments: Vec<ChebySegment<T, N, X>>,
 }
 
-impl<T: ChebyScalar, const N: usize> ChebySegmentTable<T, N> {
+impl<T: ChebyScalar, const N: usize, X: ChebyAxis> ChebySegmentTable<T, N, X> {
     /// Build a segment table by sampling `f` at Chebyshev nodes within
     /// each segment.
     ///
@@ -99,32 +256,524 @@ impl<T: ChebyScalar, const N: usize> ChebySegmentTable<T, N> {
     /// - `start` — start of the domain.
     /// - `end` — end of the domain.
     /// - `segment_len` — duration of each segment.
-    pub fn from_fn(f: impl Fn(f64) -> T, start: f64, end: f64, segment_len: f64) -> Self {
-        let span = end - start;
-        let num_segments = ((span / segment_len).ceil() as usize).max(1);
+    pub fn from_fn(f: impl Fn(X) -> T, start: X, end: X, segment_len: X) -> Self {
+        let grid = UniformGrid::new(start.value(), segment_len.value());
+        let f = |t: f64| f(X::from_value(t));
+        let half = grid.segment_len * 0.5;
+        let segments = grid
+            .cover(end.value())
+            .map(|(lo, hi)| {
+                let coeffs = fit::fit_from_fn(f, lo, hi);
+                ChebySegment::new(coeffs, X::from_value(lo + half), X::from_value(half))
+            })
+            .collect();
+        Self { grid, segments }
+    }
+
+    /// Build a segment table by sampling `f` at Chebyshev–Lobatto nodes
+    /// within each segment.
+    ///
+    /// Like [`from_fn`](Self::from_fn), but every segment is fitted with
+    /// [`fit_from_fn_lobatto`](crate::fit_from_fn_lobatto), which samples
+    /// both of its endpoints. Boundaries are computed as
+    /// `start + i · segment_len` for both neighbours, so adjacent segments
+    /// interpolate the same value of `f` there and the table is
+    /// continuous up to rounding error.
+    pub fn from_fn_lobatto(f: impl Fn(X) -> T, start: X, end: X, segment_len: X) -> Self {
+        let grid = UniformGrid::new(start.value(), segment_len.value());
+        let f = |t: f64| f(X::from_value(t));
+        let half = grid.segment_len * 0.5;
+        let segments = grid
+            .cover(end.value())
+            .map(|(lo, hi)| {
+                let coeffs = fit::fit_from_fn_lobatto(f, lo, hi);
+                ChebySegment::new(coeffs, X::from_value(lo + half), X::from_value(half))
+            })
+            .collect();
+        Self { grid, segments }
+    }
+
+    /// Build from pre-computed segments.
+    pub fn from_segments(segments: Vec<ChebySegment<T, N, X>>, start: X, segment_len: X) -> Self {
+        Self {
+            grid: UniformGrid::new(start.value(), segment_len.value()),
+            segments,
+        }
+    }
+
+    /// Number of segments in the table.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Whether the table is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Start of the covered domain.
+    #[inline]
+    pub fn start(&self) -> X {
+        X::from_value(self.grid.start)
+    }
+
+    /// End of the covered domain.
+    #[inline]
+    pub fn end(&self) -> X {
+        X::from_value(self.grid.end(self.segments.len()))
+    }
+
+    /// Duration of each segment.
+    #[inline]
+    pub fn segment_len(&self) -> X {
+        X::from_value(self.grid.segment_len)
+    }
+
+    /// Index of the segment containing `t`, or `None` if `t` is outside
+    /// the table range.
+    #[inline]
+    pub fn segment_index(&self, t: impl IntoAxis<X>) -> Option<usize> {
+        self.index_of(t.into_axis().value())
+    }
+
+    /// [`segment_index`](Self::segment_index) on the value of `t` in the
+    /// unit of `X`.
+    #[inline]
+    pub(crate) fn index_of(&self, t: f64) -> Option<usize> {
+        self.grid.index(t, self.segments.len())
+    }
+
+    /// Bounds `[lo, hi]` of segment `i`, in the unit of `X`.
+    #[inline]
+    pub(crate) fn bounds(&self, i: usize) -> (f64, f64) {
+        self.grid.bounds(i)
+    }
+
+    /// Look up the segment containing `t`, returning `None` if `t` is
+    /// outside the table range.
+    #[inline]
+    pub fn get_segment(&self, t: impl IntoAxis<X>) -> Option<&ChebySegment<T, N, X>> {
+        self.segment_index(t).map(|idx| &self.segments[idx])
+    }
+
+    /// Evaluate at `t`, returning `None` if outside the table range.
+    #[inline]
+    pub fn eval(&self, t: impl IntoAxis<X>) -> Option<T> {
+        self.get_segment(t).map(|s| s.eval(t))
+    }
+
+    /// Evaluate derivative at `t`, returning `None` if outside range.
+    #[inline]
+    pub fn eval_derivative(&self, t: impl IntoAxis<X>) -> Option<T::Derivative>
+    where
+        T: ChebyCalculus<X>,
+    {
+        self.get_segment(t).map(|s| s.eval_derivative(t))
+    }
+
+    /// Evaluate value and derivative at `t`, returning `None` if outside range.
+    #[inline]
+    pub fn eval_both(&self, t: impl IntoAxis<X>) -> Option<(T, T::Derivative)>
+    where
+        T: ChebyCalculus<X>,
+    {
+        self.get_segment(t).map(|s| s.eval_both(t))
+    }
+
+    /// Evaluate at `t` in the wider type `W` (e.g. an `f32` table with
+    /// `f64` accumulation), returning `None` if outside the table range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cheby::ChebySegmentTable;
+    ///
+    /// let table: ChebySegmentTable<f32, 12> =
+    ///     ChebySegmentTable::from_fn(|t| t.sin() as f32, 0.0, 4.0, 1.0);
+    /// let v: f64 = table.eval_widened(2.5).unwrap();
+    /// assert!((v - 2.5_f64.sin()).abs() < 1e-6);
+    /// ```
+    #[inline]
+    pub fn eval_widened<W: ChebyScalar>(&self, t: impl IntoAxis<X>) -> Option<W>
+    where
+        T: Into<W>,
+    {
+        self.get_segment(t).map(|s| s.eval_widened(t))
+    }
+
+    /// Evaluate value and derivative in the wider type `W`, returning
+    /// `None` if outside range.
+    #[inline]
+    pub fn eval_both_widened<W>(&self, t: impl IntoAxis<X>) -> Option<(W, W::Derivative)>
+    where
+        T: Into<W>,
+        W: ChebyCalculus<X>,
+    {
+        self.get_segment(t).map(|s| s.eval_both_widened(t))
+    }
+
+    /// Definite integral `∫_a^b f(t) dt`, summed across segment
+    /// boundaries.
+    ///
+    /// Returns `None` if either limit lies outside `[start, end]`.
+    /// Reversed limits give the negated integral.
+    pub fn integrate(&self, a: impl IntoAxis<X>, b: impl IntoAxis<X>) -> Option<T::Integral>
+    where
+        T: ChebyCalculus<X>,
+    {
+        let (a, b) = (a.into_axis().value(), b.into_axis().value());
+        calculus::integrate_table(self, a, b, |s, a, b| {
+            s.integrate(X::from_value(a), X::from_value(b))
+        })
+    }
+
+    /// Index of the segment containing the two-part time `t`, or `None`
+    /// if it is outside the table range.
+    ///
+    /// The offset from the table start is formed without rounding `t` to
+    /// one `f64` first (see [`SplitTime`]).
+    #[inline]
+    pub fn segment_index_split(&self, t: impl Into<SplitTime<X>>) -> Option<usize> {
+        self.grid
+            .index_at_offset(t.into().offset_from(self.start()), self.segments.len())
+    }
+
+    /// Evaluate at the two-part time `t`, returning `None` if outside the
+    /// table range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cheby::{ChebySegment, ChebySegmentTable, SplitTime};
+    ///
+    /// // Seconds since JD 2451544.5, on two 1-day segments.
+    /// let segments = (0..2)
+    ///     .map(|i| {
+    ///         let c0 = (i as f64 + 0.5) * 86_400.0;
+    ///         ChebySegment::new([c0, 43_200.0], 2_451_545.0 + i as f64, 0.5)
+    ///     })
+    ///     .collect();
+    /// let table: ChebySegmentTable<f64, 2> =
+    ///     ChebySegmentTable::from_segments(segments, 2_451_544.5, 1.0);
+    /// let secs = table.eval_split((2_451_545.5, 1.0e-9)).unwrap();
+    /// assert!((secs - (86_400.0 + 86.4e-6)).abs() < 1e-9);
+    /// ```
+    #[inline]
+    pub fn eval_split(&self, t: impl Into<SplitTime<X>>) -> Option<T> {
+        let t = t.into();
+        self.segment_index_split(t)
+            .map(|i| self.segments[i].eval_split(t))
+    }
+
+    /// Evaluate the derivative at the two-part time `t`, returning `None`
+    /// if outside range.
+    #[inline]
+    pub fn eval_derivative_split(&self, t: impl Into<SplitTime<X>>) -> Option<T::Derivative>
+    where
+        T: ChebyCalculus<X>,
+    {
+        let t = t.into();
+        self.segment_index_split(t)
+            .map(|i| self.segments[i].eval_derivative_split(t))
+    }
+
+    /// Evaluate value and derivative at the two-part time `t`, returning
+    /// `None` if outside range.
+    #[inline]
+    pub fn eval_both_split(&self, t: impl Into<SplitTime<X>>) -> Option<(T, T::Derivative)>
+    where
+        T: ChebyCalculus<X>,
+    {
+        let t = t.into();
+        self.segment_index_split(t)
+            .map(|i| self.segments[i].eval_both_split(t))
+    }
+
+    /// Direct access to the underlying segments slice.
+    #[inline]
+    pub fn segments(&self) -> &[ChebySegment<T, N, X>] {
+        &self.segments
+    }
+
+    /// A table of the derivative `df/dt`, segment by segment, over the
+    /// same domain.
+    pub fn derivative(&self) -> ChebySegmentTable<T::Derivative, N, X>
+    where
+        T: ChebyCalculus<X>,
+    {
+        ChebySegmentTable {
+            grid: self.grid,
+            segments: self.segments.iter().map(ChebySegment::derivative).collect(),
+        }
+    }
+
+    /// Consume the table, returning its segments.
+    #[inline]
+    pub fn into_segments(self) -> Vec<ChebySegment<T, N, X>> {
+        self.segments
+    }
+
+    /// A [`Cursor`] for fast repeated lookups at nearby times.
+    ///
+    /// Cursor times are plain values in the unit of `X`. Its `eval`
+    /// methods need an `f64` axis; on a `qtty` axis, use
+    /// [`seek`](Cursor::seek) and evaluate the returned segment.
+    #[inline]
+    pub fn cursor(&self) -> Cursor<'_, Self> {
+        Cursor::new(self)
+    }
+}
+
+impl<T: ChebyScalar, const N: usize> ChebySegmentTable<T, N> {
+    /// Build a segment table from values and derivatives of `f`, with
+    /// C¹ continuity between consecutive segments.
+    ///
+    /// `f(t)` returns `(value, d value / dt)`. Each segment is fitted with
+    /// [`fit_hermite`](crate::fit_hermite), which matches value and
+    /// derivative exactly at both ends of the segment; since neighbours
+    /// share their boundary (computed as `start + i · segment_len`),
+    /// values and first derivatives agree there up to rounding error.
+    ///
+    /// # Errors
+    ///
+    /// [`HermiteError::InvalidDomain`] if the domain is not finite,
+    /// `start >= end` or `segment_len` is not positive, and otherwise the
+    /// first error from fitting a segment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N < 4`.
+    pub fn from_fn_hermite(
+        f: impl Fn(f64) -> (T, T),
+        start: f64,
+        end: f64,
+        segment_len: f64,
+    ) -> Result<Self, HermiteError>
+    where
+        T: ChebyScalar<Field = f64>,
+    {
+        if !(start < end && end.is_finite() && segment_len > 0.0 && segment_len.is_finite()) {
+            return Err(HermiteError::InvalidDomain);
+        }
+        let grid = UniformGrid::new(start, segment_len);
         let half = segment_len * 0.5;
+        let segments = grid
+            .cover(end)
+            .map(|(lo, hi)| {
+                let coeffs = fit::fit_hermite(&f, lo, hi)?;
+                Ok(ChebySegment::new(coeffs, lo + half, half))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { grid, segments })
+    }
+
+    /// Evaluate the `n`-th derivative at `t`, returning `None` if outside
+    /// range. Only on `f64` axes; see
+    /// [`ChebySegment::eval_nth_derivative`].
+    #[inline]
+    pub fn eval_nth_derivative(&self, t: f64, n: usize) -> Option<T> {
+        self.get_segment(t).map(|s| s.eval_nth_derivative(t, n))
+    }
 
-        let mut segments = Vec::with_capacity(num_segments);
-        for i in 0..num_segments {
-            let seg_start = start + i as f64 * segment_len;
-            let seg_end = seg_start + segment_len;
-            let mid = seg_start + half;
-            let coeffs = fit::fit_from_fn(&f, seg_start, seg_end);
-            segments.push(ChebySegment { coeffs, mid, half });
+    /// Evaluate the value and first `K` derivatives at `t`, returning
+    /// `None` if outside range. Only on `f64` axes.
+    #[inline]
+    pub fn eval_up_to<const K: usize>(&self, t: f64) -> Option<(T, [T; K])> {
+        self.get_segment(t).map(|s| s.eval_up_to::<K>(t))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// ChebyVarTable — non-uniform piecewise segments
+// ─────────────────────────────────────────────────────────────────────────
+
+/// Relative tolerance used when checking that adjacent segment domains
+/// meet, scaled by the magnitude of the shared boundary.
+pub(crate) const CONTIGUITY_TOL: f64 = 1e-12;
+
+/// Reasons a set of segments cannot form a [`ChebyVarTable`], or
+/// [`fit_adaptive`](crate::adaptive::fit_adaptive) cannot build one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VarTableError {
+    /// No segments were given.
+    Empty,
+    /// The domain `[start, end]` to fit is not finite or not increasing.
+    InvalidDomain {
+        /// Start of the domain.
+        start: f64,
+        /// End of the domain.
+        end: f64,
+    },
+    /// The number of breakpoints is not `segments + 1`.
+    BreakpointCount {
+        /// Number of segments supplied.
+        segments: usize,
+        /// Number of breakpoints supplied.
+        breakpoints: usize,
+    },
+    /// Segment `index` has a non-positive (or non-finite) width.
+    EmptySegment {
+        /// Index of the offending segment.
+        index: usize,
+    },
+    /// Segment `index` has a non-finite midpoint or half-width.
+    NonFiniteDomain {
+        /// Index of the offending segment.
+        index: usize,
+    },
+    /// Segment `index` does not start where segment `index - 1` ends.
+    NotContiguous {
+        /// Index of the offending segment.
+        index: usize,
+        /// Where the previous segment ends.
+        expected: f64,
+        /// Where this segment starts.
+        found: f64,
+    },
+    /// Segment `index` has non-finite coefficients or error estimate.
+    NonFinite {
+        /// Index of the offending segment.
+        index: usize,
+    },
+}
+
+impl fmt::Display for VarTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "no segments given"),
+            Self::InvalidDomain { start, end } => {
+                write!(f, "domain [{start}, {end}] is not finite and increasing")
+            }
+            Self::BreakpointCount {
+                segments,
+                breakpoints,
+            } => write!(
+                f,
+                "{segments} segments need {} breakpoints, got {breakpoints}",
+                segments + 1
+            ),
+            Self::EmptySegment { index } => {
+                write!(f, "segment {index} has a non-positive width")
+            }
+            Self::NonFiniteDomain { index } => {
+                write!(f, "segment {index} has a non-finite midpoint or half-width")
+            }
+            Self::NotContiguous {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "segment {index} starts at {found}, expected {expected} (end of segment {})",
+                index - 1
+            ),
+            Self::NonFinite { index } => write!(f, "segment {index} has non-finite values"),
         }
+    }
+}
 
-        Self {
-            start,
-            segment_len,
+impl std::error::Error for VarTableError {}
+
+/// A table of contiguous Chebyshev segments of varying duration.
+///
+/// Segment `i` covers `[breakpoints[i], breakpoints[i + 1])`; lookup is
+/// O(log n) by binary search. Use it when segment lengths must vary, e.g.
+/// shorter segments around perihelion passages or manoeuvres, or to skip
+/// over data gaps.
+///
+/// `X` is the type of the independent variable, as for [`ChebySegment`];
+/// breakpoints are stored as plain values in its unit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChebyVarTable<T: ChebyScalar, const N: usize, X: ChebyAxis = f64> {
+    /// Segment boundaries, `segments.len() + 1` values in increasing order.
+    breakpoints: Vec<f64>,
+    /// Segments, in chronological order.
+    segments: Vec<ChebySegment<T, N, X>>,
+}
+
+impl<T: ChebyScalar, const N: usize, X: ChebyAxis> ChebyVarTable<T, N, X> {
+    /// Build from pre-computed segments, taking the breakpoints from each
+    /// segment's domain `[mid - half, mid + half]`.
+    ///
+    /// The segments must be non-empty, have finite midpoints and positive
+    /// finite half-widths, and be in chronological order and contiguous:
+    /// each must start where the previous one ends, to within
+    /// a relative tolerance of `1e-12`. Where they meet, the start of the
+    /// later segment is used as the shared breakpoint.
+    pub fn from_segments(segments: Vec<ChebySegment<T, N, X>>) -> Result<Self, VarTableError> {
+        let first = segments.first().ok_or(VarTableError::Empty)?;
+        let mut breakpoints = Vec::with_capacity(segments.len() + 1);
+        breakpoints.push(first.mid.value() - first.half.value());
+
+        for (index, seg) in segments.iter().enumerate() {
+            let (mid, half) = (seg.mid.value(), seg.half.value());
+            if !(mid.is_finite() && half.is_finite()) {
+                return Err(VarTableError::NonFiniteDomain { index });
+            }
+            if half <= 0.0 {
+                return Err(VarTableError::EmptySegment { index });
+            }
+            let seg_start = mid - half;
+            let prev_end = breakpoints[index];
+            if index > 0 {
+                let scale = prev_end.abs().max(seg_start.abs()).max(half);
+                if (seg_start - prev_end).abs() > CONTIGUITY_TOL * scale {
+                    return Err(VarTableError::NotContiguous {
+                        index,
+                        expected: prev_end,
+                        found: seg_start,
+                    });
+                }
+                breakpoints[index] = seg_start;
+            }
+            breakpoints.push(mid + half);
+        }
+
+        Ok(Self {
+            breakpoints,
             segments,
+        })
+    }
+
+    /// Build from pre-computed segments and explicit breakpoints.
+    ///
+    /// Segment `i` is used on `[breakpoints[i], breakpoints[i + 1])`,
+    /// regardless of its own `mid`/`half`. The breakpoints must be
+    /// strictly increasing and number `segments.len() + 1`.
+    pub fn from_segments_with_breakpoints(
+        segments: Vec<ChebySegment<T, N, X>>,
+        breakpoints: Vec<X>,
+    ) -> Result<Self, VarTableError> {
+        let breakpoints: Vec<f64> = breakpoints.into_iter().map(X::value).collect();
+        if segments.is_empty() {
+            return Err(VarTableError::Empty);
+        }
+        if breakpoints.len() != segments.len() + 1 {
+            return Err(VarTableError::BreakpointCount {
+                segments: segments.len(),
+                breakpoints: breakpoints.len(),
+            });
         }
+        if let Some(index) = breakpoints
+            .windows(2)
+            .position(|w| !(w[1] > w[0] && (w[1] - w[0]).is_finite()))
+        {
+            return Err(VarTableError::EmptySegment { index });
+        }
+        Ok(Self {
+            breakpoints,
+            segments,
+        })
     }
 
-    /// Build from pre-computed segments.
-    pub fn from_segments(segments: Vec<ChebySegment<T, N>>, start: f64, segment_len: f64) -> Self {
+    /// Assemble a table from segments and their `segments.len() + 1`
+    /// breakpoints, which the caller guarantees to be consistent.
+    pub(crate) fn from_parts(segments: Vec<ChebySegment<T, N, X>>, breakpoints: Vec<f64>) -> Self {
+        debug_assert_eq!(breakpoints.len(), segments.len() + 1);
         Self {
-            start,
-            segment_len,
+            breakpoints,
             segments,
         }
     }
@@ -143,57 +792,157 @@ impl<T: ChebyScalar, const N: usize> ChebySegmentTable<T, N> {
 
     /// Start of the covered domain.
     #[inline]
-    pub fn start(&self) -> f64 {
-        self.start
+    pub fn start(&self) -> X {
+        X::from_value(self.breakpoints[0])
     }
 
     /// End of the covered domain.
     #[inline]
-    pub fn end(&self) -> f64 {
-        self.start + self.segments.len() as f64 * self.segment_len
+    pub fn end(&self) -> X {
+        X::from_value(self.breakpoints[self.breakpoints.len() - 1])
     }
 
-    /// Duration of each segment.
+    /// Segment boundaries: `len() + 1` strictly increasing values, in the
+    /// unit of `X`.
     #[inline]
-    pub fn segment_len(&self) -> f64 {
-        self.segment_len
+    pub fn breakpoints(&self) -> &[f64] {
+        &self.breakpoints
     }
 
-    /// Look up the segment containing `t`, returning `None` if `t` is
-    /// outside the table range.
+    /// Index of the segment containing `t`, or `None` if `t` is outside
+    /// the table range.
     #[inline]
-    pub fn get_segment(&self, t: f64) -> Option<&ChebySegment<T, N>> {
-        let offset = t - self.start;
-        if offset < 0.0 {
+    pub fn segment_index(&self, t: impl IntoAxis<X>) -> Option<usize> {
+        self.index_of(t.into_axis().value())
+    }
+
+    /// [`segment_index`](Self::segment_index) on the value of `t` in the
+    /// unit of `X`.
+    #[inline]
+    pub(crate) fn index_of(&self, t: f64) -> Option<usize> {
+        let (start, end) = (
+            self.breakpoints[0],
+            self.breakpoints[self.breakpoints.len() - 1],
+        );
+        if !(t >= start && t < end) {
             return None;
         }
-        let idx = (offset / self.segment_len) as usize;
-        self.segments.get(idx)
+        // Number of breakpoints <= t, minus one, is the segment index.
+        Some(self.breakpoints.partition_point(|&b| b <= t) - 1)
+    }
+
+    /// Look up the segment containing `t`, returning `None` if `t` is
+    /// outside the table range.
+    #[inline]
+    pub fn get_segment(&self, t: impl IntoAxis<X>) -> Option<&ChebySegment<T, N, X>> {
+        self.segment_index(t).map(|idx| &self.segments[idx])
     }
 
     /// Evaluate at `t`, returning `None` if outside the table range.
     #[inline]
-    pub fn eval(&self, t: f64) -> Option<T> {
+    pub fn eval(&self, t: impl IntoAxis<X>) -> Option<T> {
         self.get_segment(t).map(|s| s.eval(t))
     }
 
     /// Evaluate derivative at `t`, returning `None` if outside range.
     #[inline]
-    pub fn eval_derivative(&self, t: f64) -> Option<T> {
+    pub fn eval_derivative(&self, t: impl IntoAxis<X>) -> Option<T::Derivative>
+    where
+        T: ChebyCalculus<X>,
+    {
         self.get_segment(t).map(|s| s.eval_derivative(t))
     }
 
     /// Evaluate value and derivative at `t`, returning `None` if outside range.
     #[inline]
-    pub fn eval_both(&self, t: f64) -> Option<(T, T)> {
+    pub fn eval_both(&self, t: impl IntoAxis<X>) -> Option<(T, T::Derivative)>
+    where
+        T: ChebyCalculus<X>,
+    {
         self.get_segment(t).map(|s| s.eval_both(t))
     }
 
+    /// Definite integral `∫_a^b f(t) dt`, summed across segment
+    /// boundaries.
+    ///
+    /// Returns `None` if either limit lies outside `[start, end]`.
+    /// Reversed limits give the negated integral.
+    pub fn integrate(&self, a: impl IntoAxis<X>, b: impl IntoAxis<X>) -> Option<T::Integral>
+    where
+        T: ChebyCalculus<X>,
+    {
+        let (a, b) = (a.into_axis().value(), b.into_axis().value());
+        calculus::integrate_table(self, a, b, |s, a, b| {
+            s.integrate(X::from_value(a), X::from_value(b))
+        })
+    }
+
     /// Direct access to the underlying segments slice.
     #[inline]
-    pub fn segments(&self) -> &[ChebySegment<T, N>] {
+    pub fn segments(&self) -> &[ChebySegment<T, N, X>] {
         &self.segments
     }
+
+    /// A table of the derivative `df/dt`, segment by segment, over the
+    /// same breakpoints.
+    pub fn derivative(&self) -> ChebyVarTable<T::Derivative, N, X>
+    where
+        T: ChebyCalculus<X>,
+    {
+        ChebyVarTable::from_parts(
+            self.segments.iter().map(ChebySegment::derivative).collect(),
+            self.breakpoints.clone(),
+        )
+    }
+
+    /// Consume the table, returning its segments.
+    #[inline]
+    pub fn into_segments(self) -> Vec<ChebySegment<T, N, X>> {
+        self.segments
+    }
+
+    /// A [`Cursor`] for fast repeated lookups at nearby times.
+    ///
+    /// Cursor times are plain values in the unit of `X`. Its `eval`
+    /// methods need an `f64` axis; on a `qtty` axis, use
+    /// [`seek`](Cursor::seek) and evaluate the returned segment.
+    #[inline]
+    pub fn cursor(&self) -> Cursor<'_, Self> {
+        Cursor::new(self)
+    }
+}
+
+impl<T: ChebyScalar, const N: usize> ChebyVarTable<T, N> {
+    /// Evaluate the `n`-th derivative at `t`, returning `None` if outside
+    /// range. Only on `f64` axes; see
+    /// [`ChebySegment::eval_nth_derivative`].
+    #[inline]
+    pub fn eval_nth_derivative(&self, t: f64, n: usize) -> Option<T> {
+        self.get_segment(t).map(|s| s.eval_nth_derivative(t, n))
+    }
+
+    /// Evaluate the value and first `K` derivatives at `t`, returning
+    /// `None` if outside range. Only on `f64` axes.
+    #[inline]
+    pub fn eval_up_to<const K: usize>(&self, t: f64) -> Option<(T, [T; K])> {
+        self.get_segment(t).map(|s| s.eval_up_to::<K>(t))
+    }
+}
+
+impl<T: ChebyScalar, const N: usize, X: ChebyAxis> TryFrom<ChebySegmentTable<T, N, X>>
+    for ChebyVarTable<T, N, X>
+{
+    type Error = VarTableError;
+
+    /// Convert a uniform table, keeping its exact `start + i · segment_len`
+    /// breakpoints. Fails only if the table is empty.
+    fn try_from(table: ChebySegmentTable<T, N, X>) -> Result<Self, Self::Error> {
+        let (start, len) = (table.start().value(), table.segment_len().value());
+        let breakpoints = (0..=table.len())
+            .map(|i| X::from_value(start + i as f64 * len))
+            .collect();
+        Self::from_segments_with_breakpoints(table.into_segments(), breakpoints)
+    }
 }
 
 #[cfg(test)]
@@ -303,6 +1052,102 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_derivative_table() {
+        let position: ChebySegmentTable<f64, 14> =
+            ChebySegmentTable::from_fn(|t| (0.8 * t).sin(), 0.0, 10.0, 2.5);
+        let velocity = position.derivative();
+        assert_eq!(velocity.len(), position.len());
+        assert_eq!(velocity.end(), position.end());
+        for &t in &[0.0, 1.3, 2.5, 7.77, 9.99] {
+            let v = velocity.eval(t).unwrap();
+            assert!((v - position.eval_derivative(t).unwrap()).abs() < 1e-12);
+            assert!((v - 0.8 * (0.8 * t).cos()).abs() < 1e-9);
+        }
+        let accel = velocity.derivative();
+        let t = 4.2;
+        assert!(
+            (accel.eval(t).unwrap() - position.eval_nth_derivative(t, 2).unwrap()).abs() < 1e-10
+        );
+
+        let var = ChebyVarTable::try_from(position.clone())
+            .unwrap()
+            .derivative();
+        assert_eq!(var.breakpoints(), &[0.0, 2.5, 5.0, 7.5, 10.0]);
+        assert_eq!(var.eval(3.3), velocity.eval(3.3));
+    }
+
+    #[test]
+    fn test_higher_derivatives_chain_rule() {
+        // sin(ωt) on a non-unit domain exercises the 1/halfᵏ scaling.
+        let w = 3.0;
+        let table: ChebySegmentTable<f64, 20> =
+            ChebySegmentTable::from_fn(|t| (w * t).sin(), 0.0, 4.0, 2.0);
+        let t = 2.7;
+        let exact = [
+            (w * t).sin(),
+            w * (w * t).cos(),
+            -w * w * (w * t).sin(),
+            -w * w * w * (w * t).cos(),
+        ];
+
+        let (v, d) = table.eval_up_to::<3>(t).unwrap();
+        assert!((v - exact[0]).abs() < 1e-11);
+        for (k, (got, want)) in d.iter().zip(&exact[1..]).enumerate() {
+            let tol = 1e-9 * 10f64.powi(k as i32);
+            assert!((got - want).abs() < tol, "order {}: {got} vs {want}", k + 1);
+            let nth = table.eval_nth_derivative(t, k + 1).unwrap();
+            assert!((nth - got).abs() < 1e-12 * want.abs().max(1.0));
+        }
+        assert_eq!(table.eval_nth_derivative(t, 1), table.eval_derivative(t));
+        assert!(table.eval_up_to::<2>(4.5).is_none());
+    }
+
+    #[test]
+    fn test_table_from_fn_lobatto_continuity() {
+        let table: ChebySegmentTable<f64, 12> =
+            ChebySegmentTable::from_fn_lobatto(f64::exp, 0.0, 3.0, 0.75);
+        assert_eq!(table.len(), 4);
+
+        let segs = table.segments();
+        for i in 1..segs.len() {
+            let b = i as f64 * 0.75;
+            let left = segs[i - 1].eval(b);
+            let right = segs[i].eval(b);
+            assert!(
+                (left - right).abs() < 1e-14 * b.exp(),
+                "boundary {b}: {left} vs {right}, exact {}",
+                b.exp()
+            );
+        }
+        for &t in &[0.0, 0.4, 1.6, 2.9] {
+            assert!((table.eval(t).unwrap() - t.exp()).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_table_from_fn_hermite_c1() {
+        let f = |t: f64| (t.sin() * t, t.cos() * t + t.sin());
+        let table: ChebySegmentTable<f64, 10> =
+            ChebySegmentTable::from_fn_hermite(f, 0.0, 4.0, 0.5).unwrap();
+        assert_eq!(table.len(), 8);
+
+        let segs = table.segments();
+        for i in 1..segs.len() {
+            let b = i as f64 * 0.5;
+            let (lv, ld) = segs[i - 1].eval_both(b);
+            let (rv, rd) = segs[i].eval_both(b);
+            assert!((lv - rv).abs() < 1e-14, "value jump at {b}");
+            assert!((ld - rd).abs() < 1e-12, "rate jump at {b}");
+            assert!((lv - f(b).0).abs() < 1e-14);
+        }
+        for &t in &[0.3, 1.1, 3.9] {
+            let (v, d) = table.eval_both(t).unwrap();
+            assert!((v - f(t).0).abs() < 1e-10);
+            assert!((d - f(t).1).abs() < 1e-8);
+        }
+    }
+
     #[test]
     fn test_table_metadata() {
         let table: ChebySegmentTable<f64, 9> = ChebySegmentTable::from_fn(f64::sin, 1.0, 3.0, 0.5);
@@ -319,4 +1164,291 @@ mod tests {
         // Just past the end
         assert!(table.eval(1.1).is_none());
     }
+
+    #[test]
+    fn test_segment_integrate() {
+        let coeffs: [f64; 15] = fit::fit_from_fn(f64::cos, 0.5, 2.5);
+        let seg = ChebySegment::new(coeffs, 1.5, 1.0);
+        let exact = |a: f64, b: f64| b.sin() - a.sin();
+        assert!((seg.integrate(0.5, 2.5) - exact(0.5, 2.5)).abs() < 1e-13);
+        assert!((seg.integrate(0.9, 1.7) - exact(0.9, 1.7)).abs() < 1e-13);
+        assert!((seg.integrate(1.7, 0.9) + exact(0.9, 1.7)).abs() < 1e-13);
+
+        let big_f = seg.integral(0.5_f64.sin());
+        assert_eq!(big_f.len(), 16);
+        assert_eq!(big_f.mid, seg.mid);
+        for &t in &[0.5, 1.1, 2.5] {
+            assert!((big_f.eval(t) - t.sin()).abs() < 1e-13);
+            assert!((big_f.eval_derivative(t) - seg.eval(t)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_table_integrate_across_boundaries() {
+        let table: ChebySegmentTable<f64, 13> = ChebySegmentTable::from_fn(f64::exp, 0.0, 3.0, 0.5);
+        let exact = |a: f64, b: f64| b.exp() - a.exp();
+        for &(a, b) in &[(0.1, 0.4), (0.2, 2.9), (0.0, 3.0), (0.5, 1.0), (1.3, 1.3)] {
+            let got = table.integrate(a, b).unwrap();
+            assert!((got - exact(a, b)).abs() < 1e-12, "[{a}, {b}]");
+        }
+        assert!((table.integrate(2.9, 0.2).unwrap() + exact(0.2, 2.9)).abs() < 1e-12);
+        assert!(table.integrate(-0.1, 1.0).is_none());
+        assert!(table.integrate(1.0, 3.1).is_none());
+        assert!(table.integrate(f64::NAN, 1.0).is_none());
+
+        let var = ChebyVarTable::try_from(table.clone()).unwrap();
+        assert_eq!(var.integrate(0.2, 2.9), table.integrate(0.2, 2.9));
+    }
+
+    #[test]
+    fn test_integrate_quantity() {
+        use qtty::Quantity;
+        type Kilometers = Quantity<qtty::Kilometer>;
+
+        let table: ChebySegmentTable<Kilometers, 4> =
+            ChebySegmentTable::from_fn(|t| Kilometers::new(3.0 * t * t), 0.0, 2.0, 1.0);
+        let area: Kilometers = table.integrate(0.0, 2.0).unwrap();
+        assert!((area.value() - 8.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_typed_axis_units() {
+        use qtty::{Day, Days, Hour, Hours, Kilometer, Kilometers, Per, Prod, Quantity};
+
+        // x(t) = 2t² km over t in days.
+        let f = |t: Days| Kilometers::new(2.0 * t.value() * t.value());
+        let table: ChebySegmentTable<Kilometers, 8, Days> =
+            ChebySegmentTable::from_fn(f, Days::new(0.0), Days::new(3.0), Days::new(1.0));
+        assert_eq!(table.end(), Days::new(3.0));
+
+        let t = Days::new(1.5);
+        let (x, v): (Kilometers, Quantity<Per<Kilometer, Day>>) = table.eval_both(t).unwrap();
+        assert!((x.value() - 4.5).abs() < 1e-12);
+        assert!((v.value() - 6.0).abs() < 1e-11);
+
+        let area: Quantity<Prod<Kilometer, Day>> =
+            table.integrate(Days::new(0.0), Days::new(3.0)).unwrap();
+        assert!((area.value() - 18.0).abs() < 1e-11);
+
+        let velocity = table.derivative();
+        assert!((velocity.eval(t).unwrap().value() - 6.0).abs() < 1e-11);
+
+        // The same function over hours gives rates per hour.
+        let seg: ChebySegment<Kilometers, 8, Hours> = ChebySegment::new(
+            table.segments()[1].coeffs,
+            Hours::new(36.0),
+            Hours::new(12.0),
+        );
+        let per_hour: Quantity<Per<Kilometer, Hour>> = seg.eval_derivative(Hours::new(36.0));
+        assert!((per_hour.value() - 6.0 / 24.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_f32_table() {
+        let table: ChebySegmentTable<f32, 10> =
+            ChebySegmentTable::from_fn(|t| t.cos() as f32, 0.0, 4.0, 0.5);
+        let wide: ChebySegmentTable<f64, 10> = ChebySegmentTable::from_fn(f64::cos, 0.0, 4.0, 0.5);
+        for &t in &[0.0, 0.3, 1.75, 3.99] {
+            let (v, d) = table.eval_both(t).unwrap();
+            assert!((v as f64 - t.cos()).abs() < 1e-6, "t={t}");
+            assert!((d as f64 + t.sin()).abs() < 1e-5, "t={t}");
+
+            // f64 accumulation leaves only the coefficient storage error.
+            let (v, d): (f64, f64) = table.eval_both_widened(t).unwrap();
+            let (v64, d64) = wide.eval_both(t).unwrap();
+            assert!((v - v64).abs() < 5e-7, "t={t}");
+            assert!((d - d64).abs() < 1e-5, "t={t}");
+        }
+        assert_eq!(table.eval_widened::<f64>(4.5), None);
+    }
+
+    fn var_segment(start: f64, end: f64) -> ChebySegment<f64, 15> {
+        let coeffs = fit::fit_from_fn(f64::exp, start, end);
+        ChebySegment::new(coeffs, 0.5 * (start + end), 0.5 * (end - start))
+    }
+
+    #[test]
+    fn test_var_table_lookup() {
+        let edges = [0.0, 0.25, 1.0, 1.125, 3.0];
+        let segments = edges.windows(2).map(|w| var_segment(w[0], w[1])).collect();
+        let table = ChebyVarTable::from_segments(segments).unwrap();
+
+        assert_eq!(table.len(), 4);
+        assert_eq!(table.breakpoints(), &edges);
+        assert_eq!(table.segment_index(0.0), Some(0));
+        assert_eq!(table.segment_index(0.25), Some(1));
+        assert_eq!(table.segment_index(1.1), Some(2));
+        assert_eq!(table.segment_index(2.999), Some(3));
+        assert_eq!(table.segment_index(3.0), None);
+        assert_eq!(table.segment_index(-1e-9), None);
+        assert!(table.eval(f64::NAN).is_none());
+
+        for &t in &[0.1, 0.5, 1.05, 2.0] {
+            let (v, d) = table.eval_both(t).unwrap();
+            assert!((v - t.exp()).abs() < 1e-10);
+            assert!((d - t.exp()).abs() < 1e-8);
+            assert_eq!(Some(v), table.eval(t));
+            assert_eq!(Some(d), table.eval_derivative(t));
+        }
+    }
+
+    #[test]
+    fn test_var_table_validation() {
+        assert_eq!(
+            ChebyVarTable::<f64, 15>::from_segments(vec![]),
+            Err(VarTableError::Empty)
+        );
+
+        let gap = vec![var_segment(0.0, 1.0), var_segment(1.5, 2.0)];
+        assert!(matches!(
+            ChebyVarTable::from_segments(gap),
+            Err(VarTableError::NotContiguous { index: 1, .. })
+        ));
+
+        let unsorted = vec![var_segment(1.0, 2.0), var_segment(0.0, 1.0)];
+        assert!(ChebyVarTable::from_segments(unsorted).is_err());
+
+        let degenerate = vec![ChebySegment::new([0.0; 15], 1.0, 0.0)];
+        assert_eq!(
+            ChebyVarTable::from_segments(degenerate),
+            Err(VarTableError::EmptySegment { index: 0 })
+        );
+
+        for mid in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let first = vec![ChebySegment::new([1.0; 15], mid, 0.5)];
+            assert_eq!(
+                ChebyVarTable::from_segments(first),
+                Err(VarTableError::NonFiniteDomain { index: 0 })
+            );
+            let later = vec![
+                var_segment(0.0, 1.0),
+                ChebySegment::new([1.0; 15], mid, 0.5),
+            ];
+            assert_eq!(
+                ChebyVarTable::from_segments(later),
+                Err(VarTableError::NonFiniteDomain { index: 1 })
+            );
+        }
+        let infinite = vec![ChebySegment::new([1.0; 15], 0.0, f64::INFINITY)];
+        assert_eq!(
+            ChebyVarTable::from_segments(infinite),
+            Err(VarTableError::NonFiniteDomain { index: 0 })
+        );
+
+        let segs = vec![var_segment(0.0, 1.0), var_segment(1.0, 2.0)];
+        assert_eq!(
+            ChebyVarTable::from_segments_with_breakpoints(segs.clone(), vec![0.0, 1.0]),
+            Err(VarTableError::BreakpointCount {
+                segments: 2,
+                breakpoints: 2
+            })
+        );
+        assert_eq!(
+            ChebyVarTable::from_segments_with_breakpoints(segs, vec![0.0, 1.0, 1.0]),
+            Err(VarTableError::EmptySegment { index: 1 })
+        );
+    }
+
+    #[test]
+    fn test_var_table_from_uniform() {
+        let uniform: ChebySegmentTable<f64, 9> =
+            ChebySegmentTable::from_fn(f64::sin, 0.0, 3.0, 0.5);
+        let var = ChebyVarTable::try_from(uniform.clone()).unwrap();
+        assert_eq!(var.len(), uniform.len());
+        assert_eq!(var.end(), uniform.end());
+        for &t in &[0.0, 0.49, 0.5, 1.7, 2.99] {
+            assert_eq!(var.eval(t), uniform.eval(t));
+        }
+
+        let empty: ChebySegmentTable<f64, 9> = ChebySegmentTable::from_segments(vec![], 0.0, 1.0);
+        assert_eq!(ChebyVarTable::try_from(empty), Err(VarTableError::Empty));
+    }
+
+    #[test]
+    fn test_split_time_lookup_and_eval() {
+        // Seconds since JD 2451544.5 on 1-day segments.
+        let segments = (0..3)
+            .map(|i| {
+                let c0 = (i as f64 + 0.5) * 86_400.0;
+                ChebySegment::new([c0, 43_200.0], 2_451_545.0 + i as f64, 0.5)
+            })
+            .collect();
+        let table: ChebySegmentTable<f64, 2> =
+            ChebySegmentTable::from_segments(segments, 2_451_544.5, 1.0);
+
+        // Just before a boundary: rounding to one f64 would land on it.
+        let t = SplitTime::new(2_451_545.5, -1.0e-12);
+        assert_eq!(table.segment_index(t.to_f64()), Some(1));
+        assert_eq!(table.segment_index_split(t), Some(0));
+
+        let t = SplitTime::new(2_451_546.0, 0.25 + 2.0e-10);
+        let want = 1.75 * 86_400.0 + 2.0e-10 * 86_400.0;
+        let (v, d) = table.eval_both_split(t).unwrap();
+        assert!((v - want).abs() < 1e-9);
+        assert!((d - 86_400.0).abs() < 1e-9);
+        assert!((table.eval(t.to_f64()).unwrap() - want).abs() > 1e-6);
+        assert_eq!(table.eval_derivative_split(t), Some(d));
+        assert_eq!(table.eval_split(2_451_547.5), None);
+        assert_eq!(table.eval_split((2_451_544.5, -1.0e-12)), None);
+
+        // On a typed axis the parts are in its unit (or converted to it)
+        // and derivatives typed.
+        let segments = table
+            .segments()
+            .iter()
+            .map(|s| ChebySegment::new(s.coeffs, qtty::Days::new(s.mid), qtty::Days::new(s.half)))
+            .collect();
+        let typed: ChebySegmentTable<f64, 2, qtty::Days> = ChebySegmentTable::from_segments(
+            segments,
+            qtty::Days::new(2_451_544.5),
+            qtty::Days::new(1.0),
+        );
+        let typed_t = SplitTime::new(qtty::Days::new(t.hi), qtty::Days::new(t.lo));
+        let (tv, td) = typed.eval_both_split(typed_t).unwrap();
+        assert_eq!((tv, td.value()), (v, d));
+        let before = (qtty::Days::new(2_451_545.5), qtty::Days::new(-1.0e-12));
+        assert_eq!(typed.segment_index_split(before), Some(0));
+        let hours = (qtty::Hours::new(24.0 * 2_451_546.0), qtty::Hours::new(6.0));
+        assert_eq!(typed.segment_index_split(hours), Some(1));
+        assert!((typed.eval_split(hours).unwrap() - 1.75 * 86_400.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_typed_axis_converts_lookup_units() {
+        use qtty::{Days, Hours, Minutes};
+
+        let table: ChebySegmentTable<f64, 12, Days> = ChebySegmentTable::from_fn(
+            |t: Days| t.value().sin(),
+            Days::new(0.0),
+            Days::new(4.0),
+            Days::new(1.0),
+        );
+        let in_days = table.eval(Days::new(1.5)).unwrap();
+        assert_eq!(table.eval(Hours::new(36.0)), Some(in_days));
+        assert_eq!(
+            table.segment_index(Minutes::new(60.0 * 24.0 * 3.5)),
+            Some(3)
+        );
+        assert_eq!(table.eval(Hours::new(97.0)), None);
+        let area = table.integrate(Hours::new(0.0), Days::new(2.0)).unwrap();
+        assert!((area.value() - (1.0 - 2.0_f64.cos())).abs() < 1e-12);
+
+        let var = ChebyVarTable::try_from(table.clone()).unwrap();
+        assert_eq!(var.start(), Days::new(0.0));
+        assert_eq!(var.end(), Days::new(4.0));
+        assert_eq!(var.eval(Hours::new(36.0)), Some(in_days));
+        assert_eq!(
+            var.eval_both(Hours::new(60.0)),
+            table.eval_both(Days::new(2.5))
+        );
+
+        // Cursors take plain values in days.
+        let mut cursor = table.cursor();
+        assert_eq!(cursor.seek_index(2.5), Some(2));
+        assert_eq!(
+            cursor.seek(3.5).map(|s| s.eval(Days::new(3.5))),
+            table.eval(Days::new(3.5))
+        );
+        assert_eq!(var.cursor().seek_index(3.5), Some(3));
+    }
 }
@@ -0,0 +1,591 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! `serde` support for segments, tables and series (feature `serde`).
+//!
+//! [`ChebySegment`], [`ChebySegmentTable`], [`ChebyVarTable`],
+//! [`ChebyVecSegment`], [`ChebyVecSegmentTable`], [`ChebySeries`],
+//! [`DynChebySegment`] and [`DynChebySegmentTable`] implement `Serialize`
+//! and `Deserialize` for any `N`, with the [`Storable`] values and
+//! [`StorableAxis`] axes of the [`storage`](crate::storage) format.
+//!
+//! # Representation
+//!
+//! A value is written as its numbers: a single number for `f64`, `f32`
+//! and quantities, a list for a [`Vector`](crate::Vector). Quantities are
+//! written in their own unit, and each object records the units of its
+//! values and axis once, as [`UnitTag`]s. A `Vec3<Kilometers>` table on a
+//! `Days` axis reads, in JSON:
+//!
+//! ```text
+//! {
+//!   "value_unit": { "symbol": "km", "ratio": 1000.0 },
+//!   "axis_unit": { "symbol": "d", "ratio": 86400.0 },
+//!   "start": 0.0,
+//!   "segment_len": 1.0,
+//!   "segments": [
+//!     { "mid": 0.5, "half": 0.5, "coeffs": [[7000.0, 0.0, 5.0], ...] },
+//!     ...
+//!   ]
+//! }
+//! ```
+//!
+//! A [`ChebySegment`] has `value_unit`, `axis_unit`, `mid`, `half` and
+//! `coeffs`; a [`ChebyVarTable`] has `breakpoints` in place of `start` and
+//! `segment_len`; a [`ChebySeries`] has only `value_unit` and `coeffs`.
+//! Vector segments hold `D` lists of `N` coefficients, one per component.
+//! Numbers round-trip exactly wherever the format does; `serde_json` needs
+//! its `float_roundtrip` feature for that.
+//! Types without an axis parameter record plain numbers as their axis
+//! unit, so, for example, a [`DynChebySegmentTable`] reads a
+//! [`ChebySegmentTable`] of the same values.
+//!
+//! # Validation
+//!
+//! Deserializing checks that the units match the requested types, that
+//! there are `N` coefficients (and `D` components) per segment, that the
+//! domain is finite with positive segment lengths and half-widths, and
+//! that segment `i` of a uniform table covers
+//! `[start + i·segment_len, start + (i + 1)·segment_len]`. Failures are
+//! reported through the format's error type, with the message of the
+//! matching [`StorageError`].
+//!
+//! # Example
+//!
+//! ```
+//! use cheby::ChebySegmentTable;
+//! use qtty::{Days, Kilometers};
+//!
+//! let table: ChebySegmentTable<Kilometers, 8, Days> = ChebySegmentTable::from_fn(
+//!     |t: Days| Kilometers::new(t.value().sin()),
+//!     Days::new(0.0),
+//!     Days::new(4.0),
+//!     Days::new(1.0),
+//! );
+//! let json = serde_json::to_string(&table).unwrap();
+//! let loaded: ChebySegmentTable<Kilometers, 8, Days> = serde_json::from_str(&json).unwrap();
+//! assert_eq!(loaded, table);
+//!
+//! // The units are checked on the way in.
+//! assert!(serde_json::from_str::<ChebySegmentTable<qtty::Meters, 8, Days>>(&json).is_err());
+//! ```
+
+use crate::grid::{check_segment, UniformGrid};
+use crate::segment::{ChebySegment, ChebySegmentTable, ChebyVarTable};
+use crate::series::{ChebySeries, DynChebySegment, DynChebySegmentTable};
+use crate::storage::{Storable, StorableAxis, StorageError, UnitTag};
+use crate::vector::{ChebyVecSegment, ChebyVecSegmentTable};
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::ser::{SerializeTuple, Serializer};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::marker::PhantomData;
+
+// ─────────────────────────────────────────────────────────────────────────
+// Values
+// ─────────────────────────────────────────────────────────────────────────
+
+/// A value to write as its numbers.
+struct ValueRef<'a, T>(&'a T);
+
+/// A value read from its numbers.
+struct Value<T>(T);
+
+/// Coefficients to write as a list of values.
+struct Values<'a, T>(&'a [T]);
+
+/// Component-major coefficients to write as `D` lists of values.
+struct Rows<'a, T, const N: usize>(&'a [[T; N]]);
+
+impl<T: Storable> Serialize for ValueRef<'_, T> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        if T::COMPONENTS == 1 {
+            let mut x = 0.0;
+            self.0.to_numbers(&mut |v| x = v);
+            return s.serialize_f64(x);
+        }
+        let mut tuple = s.serialize_tuple(T::COMPONENTS)?;
+        let mut result = Ok(());
+        self.0.to_numbers(&mut |v| {
+            if result.is_ok() {
+                result = tuple.serialize_element(&v);
+            }
+        });
+        result?;
+        tuple.end()
+    }
+}
+
+impl<T: Storable> Serialize for Values<'_, T> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_seq(self.0.iter().map(ValueRef))
+    }
+}
+
+impl<T: Storable, const N: usize> Serialize for Rows<'_, T, N> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_seq(self.0.iter().map(|row| Values(row)))
+    }
+}
+
+impl<'de, T: Storable> Deserialize<'de> for Value<T> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        if T::COMPONENTS == 1 {
+            let x = f64::deserialize(d)?;
+            return Ok(Value(T::from_numbers(&mut || x)));
+        }
+        d.deserialize_tuple(T::COMPONENTS, NumbersVisitor(PhantomData))
+    }
+}
+
+/// Reads the `COMPONENTS` numbers of a multi-number value.
+struct NumbersVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Storable> Visitor<'de> for NumbersVisitor<T> {
+    type Value = Value<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a list of {} numbers", T::COMPONENTS)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value<T>, A::Error> {
+        let mut numbers = Vec::with_capacity(T::COMPONENTS);
+        while numbers.len() < T::COMPONENTS {
+            match seq.next_element::<f64>()? {
+                Some(x) => numbers.push(x),
+                None => return Err(de::Error::invalid_length(numbers.len(), &self)),
+            }
+        }
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(T::COMPONENTS + 1, &self));
+        }
+        let mut numbers = numbers.into_iter();
+        Ok(Value(T::from_numbers(&mut || {
+            numbers.next().unwrap_or(0.0)
+        })))
+    }
+}
+
+/// The values read into a list.
+fn values<T>(values: Vec<Value<T>>) -> Vec<T> {
+    values.into_iter().map(|v| v.0).collect()
+}
+
+/// Exactly `N` coefficients.
+fn array<T, const N: usize>(values: Vec<Value<T>>) -> Result<[T; N], StorageError> {
+    self::values(values)
+        .try_into()
+        .map_err(|values: Vec<T>| StorageError::Shape {
+            what: "coefficients",
+            stored: values.len() as u64,
+            requested: N as u64,
+        })
+}
+
+/// Exactly `D` components of `N` coefficients each.
+fn matrix<T, const N: usize, const D: usize>(
+    rows: Vec<Vec<Value<T>>>,
+) -> Result<[[T; N]; D], StorageError> {
+    let rows = rows
+        .into_iter()
+        .map(array)
+        .collect::<Result<Vec<[T; N]>, _>>()?;
+    rows.try_into()
+        .map_err(|rows: Vec<[T; N]>| StorageError::Shape {
+            what: "components",
+            stored: rows.len() as u64,
+            requested: D as u64,
+        })
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// Representations
+// ─────────────────────────────────────────────────────────────────────────
+
+/// A series: coefficients `C` in the value unit.
+#[derive(Serialize, Deserialize)]
+struct SeriesRepr<C> {
+    value_unit: UnitTag,
+    coeffs: C,
+}
+
+/// A standalone segment.
+#[derive(Serialize, Deserialize)]
+struct SegmentRepr<C> {
+    value_unit: UnitTag,
+    axis_unit: UnitTag,
+    mid: f64,
+    half: f64,
+    coeffs: C,
+}
+
+/// A segment inside a table, whose units are recorded by the table.
+#[derive(Serialize, Deserialize)]
+struct SlotRepr<C> {
+    mid: f64,
+    half: f64,
+    coeffs: C,
+}
+
+/// A table of uniform segments.
+#[derive(Serialize, Deserialize)]
+struct TableRepr<C> {
+    value_unit: UnitTag,
+    axis_unit: UnitTag,
+    start: f64,
+    segment_len: f64,
+    segments: Vec<SlotRepr<C>>,
+}
+
+/// A table of segments between breakpoints.
+#[derive(Serialize, Deserialize)]
+struct VarTableRepr<B, C> {
+    value_unit: UnitTag,
+    axis_unit: UnitTag,
+    breakpoints: B,
+    segments: Vec<SlotRepr<C>>,
+}
+
+/// Check the recorded units against those of `T` and `X`.
+fn check_units<T: Storable, X: StorableAxis>(
+    value: UnitTag,
+    axis: UnitTag,
+) -> Result<(), StorageError> {
+    for (what, stored, requested) in [("value", value, T::unit()), ("axis", axis, X::unit())] {
+        if stored != requested {
+            return Err(StorageError::Unit {
+                what,
+                stored,
+                requested,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// A segment of `N` coefficients from a table slot.
+fn segment<T: Storable, const N: usize, X: StorableAxis>(
+    slot: SlotRepr<Vec<Value<T>>>,
+) -> Result<ChebySegment<T, N, X>, StorageError> {
+    Ok(ChebySegment::new(
+        array(slot.coeffs)?,
+        X::from_value(slot.mid),
+        X::from_value(slot.half),
+    ))
+}
+
+/// Map a validation error into the format's error type.
+fn invalid<E: de::Error>(err: impl Into<StorageError>) -> E {
+    E::custom(err.into())
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// Fixed-degree segments and tables
+// ─────────────────────────────────────────────────────────────────────────
+
+impl<T: Storable, const N: usize, X: StorableAxis> Serialize for ChebySegment<T, N, X> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        SegmentRepr {
+            value_unit: T::unit(),
+            axis_unit: X::unit(),
+            mid: self.mid.value(),
+            half: self.half.value(),
+            coeffs: Values(&self.coeffs),
+        }
+        .serialize(s)
+    }
+}
+
+impl<'de, T: Storable, const N: usize, X: StorableAxis> Deserialize<'de> for ChebySegment<T, N, X> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let r = SegmentRepr::<Vec<Value<T>>>::deserialize(d)?;
+        check_units::<T, X>(r.value_unit, r.axis_unit).map_err(invalid)?;
+        check_segment(0, r.mid, r.half).map_err(invalid)?;
+        segment(SlotRepr {
+            mid: r.mid,
+            half: r.half,
+            coeffs: r.coeffs,
+        })
+        .map_err(invalid)
+    }
+}
+
+impl<T: Storable, const N: usize, X: StorableAxis> Serialize for ChebySegmentTable<T, N, X> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        TableRepr {
+            value_unit: T::unit(),
+            axis_unit: X::unit(),
+            start: self.start().value(),
+            segment_len: self.segment_len().value(),
+            segments: self
+                .segments()
+                .iter()
+                .map(|seg| SlotRepr {
+                    mid: seg.mid.value(),
+                    half: seg.half.value(),
+                    coeffs: Values(&seg.coeffs),
+                })
+                .collect(),
+        }
+        .serialize(s)
+    }
+}
+
+impl<'de, T: Storable, const N: usize, X: StorableAxis> Deserialize<'de>
+    for ChebySegmentTable<T, N, X>
+{
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let r = TableRepr::<Vec<Value<T>>>::deserialize(d)?;
+        check_units::<T, X>(r.value_unit, r.axis_unit).map_err(invalid)?;
+        UniformGrid::new(r.start, r.segment_len)
+            .check_slots(r.segments.iter().map(|s| (s.mid, s.half)))
+            .map_err(invalid)?;
+        let segments = r
+            .segments
+            .into_iter()
+            .map(segment)
+            .collect::<Result<_, _>>()
+            .map_err(invalid)?;
+        Ok(Self::from_segments(
+            segments,
+            X::from_value(r.start),
+            X::from_value(r.segment_len),
+        ))
+    }
+}
+
+impl<T: Storable, const N: usize, X: StorableAxis> Serialize for ChebyVarTable<T, N, X> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        VarTableRepr {
+            value_unit: T::unit(),
+            axis_unit: X::unit(),
+            breakpoints: self.breakpoints(),
+            segments: self
+                .segments()
+                .iter()
+                .map(|seg| SlotRepr {
+                    mid: seg.mid.value(),
+                    half: seg.half.value(),
+                    coeffs: Values(&seg.coeffs),
+                })
+                .collect(),
+        }
+        .serialize(s)
+    }
+}
+
+impl<'de, T: Storable, const N: usize, X: StorableAxis> Deserialize<'de>
+    for ChebyVarTable<T, N, X>
+{
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let r = VarTableRepr::<Vec<f64>, Vec<Value<T>>>::deserialize(d)?;
+        check_units::<T, X>(r.value_unit, r.axis_unit).map_err(invalid)?;
+        for (i, slot) in r.segments.iter().enumerate() {
+            check_segment(i, slot.mid, slot.half).map_err(invalid)?;
+        }
+        let segments = r
+            .segments
+            .into_iter()
+            .map(segment)
+            .collect::<Result<_, _>>()
+            .map_err(invalid)?;
+        let breakpoints = r.breakpoints.into_iter().map(X::from_value).collect();
+        Self::from_segments_with_breakpoints(segments, breakpoints)
+            .map_err(|e| invalid(StorageError::Invalid(e.to_string())))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// Vector segments and tables
+// ─────────────────────────────────────────────────────────────────────────
+
+impl<T: Storable, const N: usize, const D: usize> Serialize for ChebyVecSegment<T, N, D> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        SegmentRepr {
+            value_unit: T::unit(),
+            axis_unit: UnitTag::none(),
+            mid: self.mid,
+            half: self.half,
+            coeffs: Rows(&self.coeffs),
+        }
+        .serialize(s)
+    }
+}
+
+impl<'de, T: Storable, const N: usize, const D: usize> Deserialize<'de>
+    for ChebyVecSegment<T, N, D>
+{
+    fn deserialize<De: Deserializer<'de>>(d: De) -> Result<Self, De::Error> {
+        let r = SegmentRepr::<Vec<Vec<Value<T>>>>::deserialize(d)?;
+        check_units::<T, f64>(r.value_unit, r.axis_unit).map_err(invalid)?;
+        check_segment(0, r.mid, r.half).map_err(invalid)?;
+        Ok(Self::new(matrix(r.coeffs).map_err(invalid)?, r.mid, r.half))
+    }
+}
+
+impl<T: Storable, const N: usize, const D: usize> Serialize for ChebyVecSegmentTable<T, N, D> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        TableRepr {
+            value_unit: T::unit(),
+            axis_unit: UnitTag::none(),
+            start: self.start(),
+            segment_len: self.segment_len(),
+            segments: self
+                .segments()
+                .iter()
+                .map(|seg| SlotRepr {
+                    mid: seg.mid,
+                    half: seg.half,
+                    coeffs: Rows(&seg.coeffs),
+                })
+                .collect(),
+        }
+        .serialize(s)
+    }
+}
+
+impl<'de, T: Storable, const N: usize, const D: usize> Deserialize<'de>
+    for ChebyVecSegmentTable<T, N, D>
+{
+    fn deserialize<De: Deserializer<'de>>(d: De) -> Result<Self, De::Error> {
+        let r = TableRepr::<Vec<Vec<Value<T>>>>::deserialize(d)?;
+        check_units::<T, f64>(r.value_unit, r.axis_unit).map_err(invalid)?;
+        UniformGrid::new(r.start, r.segment_len)
+            .check_slots(r.segments.iter().map(|s| (s.mid, s.half)))
+            .map_err(invalid)?;
+        let segments = r
+            .segments
+            .into_iter()
+            .map(|slot| {
+                Ok(ChebyVecSegment::new(
+                    matrix(slot.coeffs)?,
+                    slot.mid,
+                    slot.half,
+                ))
+            })
+            .collect::<Result<_, StorageError>>()
+            .map_err(invalid)?;
+        Ok(Self::from_segments(segments, r.start, r.segment_len))
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// Runtime-sized series, segments and tables
+// ─────────────────────────────────────────────────────────────────────────
+
+impl<T: Storable> Serialize for ChebySeries<T> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        SeriesRepr {
+            value_unit: T::unit(),
+            coeffs: Values(self.coeffs()),
+        }
+        .serialize(s)
+    }
+}
+
+impl<'de, T: Storable> Deserialize<'de> for ChebySeries<T> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let r = SeriesRepr::<Vec<Value<T>>>::deserialize(d)?;
+        check_units::<T, f64>(r.value_unit, UnitTag::none()).map_err(invalid)?;
+        Ok(Self::new(values(r.coeffs)))
+    }
+}
+
+impl<T: Storable> Serialize for DynChebySegment<T> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        SegmentRepr {
+            value_unit: T::unit(),
+            axis_unit: UnitTag::none(),
+            mid: self.mid,
+            half: self.half,
+            coeffs: Values(&self.coeffs),
+        }
+        .serialize(s)
+    }
+}
+
+impl<'de, T: Storable> Deserialize<'de> for DynChebySegment<T> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let r = SegmentRepr::<Vec<Value<T>>>::deserialize(d)?;
+        check_units::<T, f64>(r.value_unit, r.axis_unit).map_err(invalid)?;
+        check_segment(0, r.mid, r.half).map_err(invalid)?;
+        Ok(Self::new(values(r.coeffs), r.mid, r.half))
+    }
+}
+
+impl<T: Storable> Serialize for DynChebySegmentTable<T> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        TableRepr {
+            value_unit: T::unit(),
+            axis_unit: UnitTag::none(),
+            start: self.start(),
+            segment_len: self.segment_len(),
+            segments: self
+                .segments()
+                .iter()
+                .map(|seg| SlotRepr {
+                    mid: seg.mid,
+                    half: seg.half,
+                    coeffs: Values(&seg.coeffs),
+                })
+                .collect(),
+        }
+        .serialize(s)
+    }
+}
+
+impl<'de, T: Storable> Deserialize<'de> for DynChebySegmentTable<T> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let r = TableRepr::<Vec<Value<T>>>::deserialize(d)?;
+        check_units::<T, f64>(r.value_unit, r.axis_unit).map_err(invalid)?;
+        UniformGrid::new(r.start, r.segment_len)
+            .check_slots(r.segments.iter().map(|s| (s.mid, s.half)))
+            .map_err(invalid)?;
+        let segments = r
+            .segments
+            .into_iter()
+            .map(|slot| DynChebySegment::new(values(slot.coeffs), slot.mid, slot.half))
+            .collect();
+        Ok(Self::from_segments(segments, r.start, r.segment_len))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vec3;
+
+    #[test]
+    fn test_value_shapes() {
+        let segment = ChebySegment::<Vec3<f32>, 2>::new(
+            [Vec3::new([1.0, 2.0, 3.0]), Vec3::new([0.5, 0.0, -1.0])],
+            0.0,
+            1.0,
+        );
+        assert_eq!(
+            serde_json::to_string(&segment).unwrap(),
+            r#"{"value_unit":{"symbol":"","ratio":1.0},"axis_unit":{"symbol":"","ratio":1.0},"mid":0.0,"half":1.0,"coeffs":[[1.0,2.0,3.0],[0.5,0.0,-1.0]]}"#
+        );
+        let series = ChebySeries::new(vec![1.5, -2.0]);
+        assert_eq!(
+            serde_json::to_string(&series).unwrap(),
+            r#"{"value_unit":{"symbol":"","ratio":1.0},"coeffs":[1.5,-2.0]}"#
+        );
+    }
+
+    #[test]
+    fn test_value_length_errors() {
+        let segment = |coeffs: &str| {
+            serde_json::from_str::<ChebySegment<Vec3<f64>, 1>>(&format!(
+                r#"{{"value_unit":{{"symbol":"","ratio":1.0}},"axis_unit":{{"symbol":"","ratio":1.0}},"mid":0.0,"half":1.0,"coeffs":{coeffs}}}"#
+            ))
+        };
+        assert!(segment("[[1.0,2.0,3.0]]").is_ok());
+        for coeffs in ["[[1.0,2.0]]", "[[1.0,2.0,3.0,4.0]]", "[1.0]"] {
+            let err = segment(coeffs).unwrap_err();
+            assert!(err.to_string().contains("3 numbers"), "{coeffs}: {err}");
+        }
+        let err = segment("[]").unwrap_err();
+        assert!(err.to_string().contains("has 0 coefficients"), "{err}");
+    }
+}
@@ -0,0 +1,598 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! Runtime-sized (dynamic-degree) Chebyshev series and segments.
+//!
+//! The types in [`segment`](crate::segment) fix the number of coefficients
+//! `N` at compile time. When the degree comes from configuration or an
+//! accuracy budget, use the counterparts in this module instead:
+//!
+//! - [`ChebySeries`] — a coefficient vector on `[-1, 1]` (the runtime
+//!   analogue of `[T; N]`).
+//! - [`DynChebySegment`] — a series plus its `[mid - half, mid + half]`
+//!   domain (the analogue of [`ChebySegment`]).
+//! - [`DynChebySegmentTable`] — uniform piecewise segments (the analogue
+//!   of [`ChebySegmentTable`]).
+//!
+//! Conversions between the const-generic and dynamic types are lossless:
+//! `From` goes from fixed to dynamic, and `TryFrom` goes back, returning
+//! the original value as the error when the coefficient count does not
+//! match `N`.
+
+use crate::calculus;
+use crate::cursor::Cursor;
+use crate::eval;
+use crate::fit;
+use crate::grid::UniformGrid;
+use crate::scalar::{field, ChebyScalar};
+use crate::segment::{ChebySegment, ChebySegmentTable};
+
+// ─────────────────────────────────────────────────────────────────────────
+// ChebySeries — coefficients on [-1, 1]
+// ─────────────────────────────────────────────────────────────────────────
+
+/// A Chebyshev series on `[-1, 1]` with a runtime number of coefficients.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChebySeries<T: ChebyScalar> {
+    coeffs: Vec<T>,
+}
+
+impl<T: ChebyScalar> ChebySeries<T> {
+    /// Create a series from pre-computed coefficients `c[0..n]`.
+    #[inline]
+    pub fn new(coeffs: Vec<T>) -> Self {
+        Self { coeffs }
+    }
+
+    /// Fit a series from values at the canonical Chebyshev nodes
+    /// [`nodes_dyn(values.len())`](crate::nodes_dyn).
+    #[inline]
+    pub fn fit(values: &[T]) -> Self {
+        Self::new(fit::fit_coeffs_dyn(values))
+    }
+
+    /// Sample `f` at `n` Chebyshev nodes on `[start, end]` and fit `n`
+    /// coefficients.
+    ///
+    /// The resulting series is expressed in the normalised variable `τ`;
+    /// use [`DynChebySegment::from_fn`] to keep the domain attached.
+    #[inline]
+    pub fn fit_from_fn(f: impl Fn(f64) -> T, start: f64, end: f64, n: usize) -> Self {
+        Self::new(fit::fit_from_fn_dyn(f, start, end, n))
+    }
+
+    /// Number of coefficients.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.coeffs.len()
+    }
+
+    /// Whether the series has no coefficients.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// The coefficients `c[0..n]`.
+    #[inline]
+    pub fn coeffs(&self) -> &[T] {
+        &self.coeffs
+    }
+
+    /// Consume the series, returning its coefficient vector.
+    #[inline]
+    pub fn into_coeffs(self) -> Vec<T> {
+        self.coeffs
+    }
+
+    /// Evaluate the series at `tau ∈ [-1, 1]`.
+    #[inline]
+    pub fn evaluate(&self, tau: T::Field) -> T {
+        eval::evaluate(&self.coeffs, tau)
+    }
+
+    /// Evaluate `df/dτ` at `tau`.
+    #[inline]
+    pub fn evaluate_derivative(&self, tau: T::Field) -> T {
+        eval::evaluate_derivative(&self.coeffs, tau)
+    }
+
+    /// Evaluate `(f(τ), df/dτ)` in one pass.
+    #[inline]
+    pub fn evaluate_both(&self, tau: T::Field) -> (T, T) {
+        eval::evaluate_both(&self.coeffs, tau)
+    }
+
+    /// Evaluate `dⁿf/dτⁿ` at `tau`.
+    #[inline]
+    pub fn evaluate_nth_derivative(&self, tau: T::Field, n: usize) -> T {
+        eval::evaluate_nth_derivative(&self.coeffs, tau, n)
+    }
+
+    /// The antiderivative `F(τ) = ∫ f dτ` with `F(-1) = constant`, one
+    /// coefficient longer than `self`.
+    #[inline]
+    pub fn integral(&self, constant: T) -> Self {
+        Self::new(calculus::integral_coeffs(&self.coeffs, constant))
+    }
+
+    /// Definite integral `∫_{-1}^{1} f(τ) dτ`.
+    #[inline]
+    pub fn definite_integral(&self) -> T {
+        calculus::definite_integral(&self.coeffs)
+    }
+
+    /// The derivative `df/dτ`, one coefficient shorter than `self`.
+    #[inline]
+    pub fn derivative(&self) -> Self {
+        Self::new(calculus::derivative_coeffs_dyn(&self.coeffs))
+    }
+}
+
+impl<T: ChebyScalar> From<Vec<T>> for ChebySeries<T> {
+    #[inline]
+    fn from(coeffs: Vec<T>) -> Self {
+        Self::new(coeffs)
+    }
+}
+
+impl<T: ChebyScalar, const N: usize> From<[T; N]> for ChebySeries<T> {
+    #[inline]
+    fn from(coeffs: [T; N]) -> Self {
+        Self::new(coeffs.to_vec())
+    }
+}
+
+impl<T: ChebyScalar, const N: usize> TryFrom<ChebySeries<T>> for [T; N] {
+    type Error = ChebySeries<T>;
+
+    /// Convert back to a fixed-size array, returning the series unchanged
+    /// if it does not have exactly `N` coefficients.
+    #[inline]
+    fn try_from(series: ChebySeries<T>) -> Result<Self, Self::Error> {
+        <[T; N]>::try_from(series.coeffs).map_err(ChebySeries::new)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// DynChebySegment — single segment
+// ─────────────────────────────────────────────────────────────────────────
+
+/// A single Chebyshev interpolation segment with a runtime number of
+/// coefficients.
+///
+/// Dynamic counterpart of [`ChebySegment`]: stores the coefficients and
+/// the domain `[mid - half, mid + half]`, and maps `t → τ` internally.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynChebySegment<T: ChebyScalar> {
+    /// Chebyshev coefficients `c[0..n]`.
+    pub coeffs: Vec<T>,
+    /// Midpoint of the segment domain.
+    pub mid: f64,
+    /// Half-width of the segment domain.
+    pub half: f64,
+}
+
+impl<T: ChebyScalar> DynChebySegment<T> {
+    /// Create a segment from pre-computed coefficients and domain.
+    #[inline]
+    pub fn new(coeffs: Vec<T>, mid: f64, half: f64) -> Self {
+        Self { coeffs, mid, half }
+    }
+
+    /// Fit `n` coefficients to `f` on `[start, end]`.
+    pub fn from_fn(f: impl Fn(f64) -> T, start: f64, end: f64, n: usize) -> Self {
+        let coeffs = fit::fit_from_fn_dyn(f, start, end, n);
+        Self::new(coeffs, 0.5 * (start + end), 0.5 * (end - start))
+    }
+
+    /// Number of coefficients.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.coeffs.len()
+    }
+
+    /// Whether the segment has no coefficients.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// Normalise `t` to `τ ∈ [-1, 1]` within this segment.
+    #[inline]
+    pub fn normalise(&self, t: f64) -> f64 {
+        (t - self.mid) / self.half
+    }
+
+    /// `τ` for `t`, rounded to the arithmetic field of `T`.
+    #[inline]
+    fn tau(&self, t: f64) -> T::Field {
+        field::<T>(self.normalise(t))
+    }
+
+    /// The half-width, rounded to the arithmetic field of `T`.
+    #[inline]
+    fn half_field(&self) -> T::Field {
+        field::<T>(self.half)
+    }
+
+    /// Evaluate the Chebyshev polynomial at physical time `t`.
+    #[inline]
+    pub fn eval(&self, t: f64) -> T {
+        eval::evaluate(&self.coeffs, self.tau(t))
+    }
+
+    /// Evaluate the derivative `df/dt` at physical time `t`.
+    #[inline]
+    pub fn eval_derivative(&self, t: f64) -> T {
+        eval::evaluate_derivative(&self.coeffs, self.tau(t)) / self.half_field()
+    }
+
+    /// Evaluate both value and derivative `(f(t), df/dt)` in one pass.
+    #[inline]
+    pub fn eval_both(&self, t: f64) -> (T, T) {
+        let (v, d) = eval::evaluate_both(&self.coeffs, self.tau(t));
+        (v, d / self.half_field())
+    }
+
+    /// Evaluate the `n`-th derivative `dⁿf/dtⁿ` at physical time `t`.
+    ///
+    /// Applies the chain rule `dⁿf/dtⁿ = (dⁿf/dτⁿ) / halfⁿ`.
+    #[inline]
+    pub fn eval_nth_derivative(&self, t: f64, n: usize) -> T {
+        let scale = field::<T>(self.half.powi(n as i32));
+        eval::evaluate_nth_derivative(&self.coeffs, self.tau(t), n) / scale
+    }
+
+    /// Evaluate `f(t)` and its first `K` time derivatives in one pass.
+    #[inline]
+    pub fn eval_up_to<const K: usize>(&self, t: f64) -> (T, [T; K]) {
+        let (v, mut d) = eval::evaluate_up_to::<T, K>(&self.coeffs, self.tau(t));
+        let mut scale = 1.0;
+        for dk in &mut d {
+            scale /= self.half;
+            *dk = *dk * field::<T>(scale);
+        }
+        (v, d)
+    }
+
+    /// Definite integral `∫_a^b f(t) dt`; see [`ChebySegment::integrate`].
+    #[inline]
+    pub fn integrate(&self, a: f64, b: f64) -> T {
+        let fa = calculus::evaluate_antiderivative(&self.coeffs, self.tau(a));
+        let fb = calculus::evaluate_antiderivative(&self.coeffs, self.tau(b));
+        (fb - fa) * self.half_field()
+    }
+
+    /// The antiderivative `F(t) = constant + ∫_{mid-half}^t f(s) ds` over
+    /// the same domain, one coefficient longer than `self`.
+    pub fn integral(&self, constant: T) -> Self {
+        let scaled: Vec<T> = self.coeffs.iter().map(|&c| c * self.half_field()).collect();
+        Self::new(
+            calculus::integral_coeffs(&scaled, constant),
+            self.mid,
+            self.half,
+        )
+    }
+
+    /// The derivative `df/dt` over the same domain, with the `1/half`
+    /// scaling applied; one coefficient shorter than `self`.
+    pub fn derivative(&self) -> Self {
+        let coeffs = calculus::derivative_coeffs_dyn(&self.coeffs)
+            .into_iter()
+            .map(|c| c / self.half_field())
+            .collect();
+        Self::new(coeffs, self.mid, self.half)
+    }
+}
+
+impl<T: ChebyScalar, const N: usize> From<ChebySegment<T, N>> for DynChebySegment<T> {
+    #[inline]
+    fn from(seg: ChebySegment<T, N>) -> Self {
+        Self::new(seg.coeffs.to_vec(), seg.mid, seg.half)
+    }
+}
+
+impl<T: ChebyScalar, const N: usize> TryFrom<DynChebySegment<T>> for ChebySegment<T, N> {
+    type Error = DynChebySegment<T>;
+
+    /// Convert to a const-generic segment, returning the segment unchanged
+    /// if it does not have exactly `N` coefficients.
+    #[inline]
+    fn try_from(seg: DynChebySegment<T>) -> Result<Self, Self::Error> {
+        let DynChebySegment { coeffs, mid, half } = seg;
+        match <[T; N]>::try_from(coeffs) {
+            Ok(coeffs) => Ok(ChebySegment::new(coeffs, mid, half)),
+            Err(coeffs) => Err(DynChebySegment::new(coeffs, mid, half)),
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// DynChebySegmentTable — uniform piecewise segments
+// ─────────────────────────────────────────────────────────────────────────
+
+/// A table of uniform-duration Chebyshev segments with a runtime number
+/// of coefficients per segment.
+///
+/// Dynamic counterpart of [`ChebySegmentTable`]; lookup is O(1) by index.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynChebySegmentTable<T: ChebyScalar> {
+    /// Start and segment length.
+    grid: UniformGrid,
+    /// Segments, in chronological order.
+    segments: Vec<DynChebySegment<T>>,
+}
+
+impl<T: ChebyScalar> DynChebySegmentTable<T> {
+    /// Build a segment table by fitting `n` coefficients to `f` in each
+    /// segment of `segment_len` covering `[start, end]`.
+    pub fn from_fn(f: impl Fn(f64) -> T, start: f64, end: f64, segment_len: f64, n: usize) -> Self {
+        let grid = UniformGrid::new(start, segment_len);
+        let segments = grid
+            .cover(end)
+            .map(|(lo, hi)| DynChebySegment::from_fn(&f, lo, hi, n))
+            .collect();
+
+        Self { grid, segments }
+    }
+
+    /// Build from pre-computed segments.
+    pub fn from_segments(segments: Vec<DynChebySegment<T>>, start: f64, segment_len: f64) -> Self {
+        Self {
+            grid: UniformGrid::new(start, segment_len),
+            segments,
+        }
+    }
+
+    /// Number of segments in the table.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Whether the table is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Start of the covered domain.
+    #[inline]
+    pub fn start(&self) -> f64 {
+        self.grid.start
+    }
+
+    /// End of the covered domain.
+    #[inline]
+    pub fn end(&self) -> f64 {
+        self.grid.end(self.segments.len())
+    }
+
+    /// Duration of each segment.
+    #[inline]
+    pub fn segment_len(&self) -> f64 {
+        self.grid.segment_len
+    }
+
+    /// Index of the segment containing `t`, or `None` if `t` is outside
+    /// the table range.
+    #[inline]
+    pub fn segment_index(&self, t: f64) -> Option<usize> {
+        self.grid.index(t, self.segments.len())
+    }
+
+    /// Bounds `[lo, hi]` of segment `i`.
+    #[inline]
+    pub(crate) fn bounds(&self, i: usize) -> (f64, f64) {
+        self.grid.bounds(i)
+    }
+
+    /// Look up the segment containing `t`, returning `None` if `t` is
+    /// outside the table range.
+    #[inline]
+    pub fn get_segment(&self, t: f64) -> Option<&DynChebySegment<T>> {
+        self.segment_index(t).map(|idx| &self.segments[idx])
+    }
+
+    /// Evaluate at `t`, returning `None` if outside the table range.
+    #[inline]
+    pub fn eval(&self, t: f64) -> Option<T> {
+        self.get_segment(t).map(|s| s.eval(t))
+    }
+
+    /// Evaluate derivative at `t`, returning `None` if outside range.
+    #[inline]
+    pub fn eval_derivative(&self, t: f64) -> Option<T> {
+        self.get_segment(t).map(|s| s.eval_derivative(t))
+    }
+
+    /// Evaluate value and derivative at `t`, returning `None` if outside range.
+    #[inline]
+    pub fn eval_both(&self, t: f64) -> Option<(T, T)> {
+        self.get_segment(t).map(|s| s.eval_both(t))
+    }
+
+    /// Evaluate the `n`-th derivative at `t`, returning `None` if outside
+    /// range.
+    #[inline]
+    pub fn eval_nth_derivative(&self, t: f64, n: usize) -> Option<T> {
+        self.get_segment(t).map(|s| s.eval_nth_derivative(t, n))
+    }
+
+    /// Evaluate the value and first `K` derivatives at `t`, returning
+    /// `None` if outside range.
+    #[inline]
+    pub fn eval_up_to<const K: usize>(&self, t: f64) -> Option<(T, [T; K])> {
+        self.get_segment(t).map(|s| s.eval_up_to::<K>(t))
+    }
+
+    /// Definite integral `∫_a^b f(t) dt`, summed across segment
+    /// boundaries.
+    ///
+    /// Returns `None` if either limit lies outside `[start, end]`.
+    /// Reversed limits give the negated integral.
+    pub fn integrate(&self, a: f64, b: f64) -> Option<T> {
+        calculus::integrate_table(self, a, b, |s, a, b| s.integrate(a, b))
+    }
+
+    /// A [`Cursor`] for fast repeated lookups at nearby times.
+    #[inline]
+    pub fn cursor(&self) -> Cursor<'_, Self> {
+        Cursor::new(self)
+    }
+
+    /// Direct access to the underlying segments slice.
+    #[inline]
+    pub fn segments(&self) -> &[DynChebySegment<T>] {
+        &self.segments
+    }
+
+    /// A table of the derivative `df/dt`, segment by segment, over the
+    /// same domain.
+    pub fn derivative(&self) -> Self {
+        Self {
+            grid: self.grid,
+            segments: self
+                .segments
+                .iter()
+                .map(DynChebySegment::derivative)
+                .collect(),
+        }
+    }
+}
+
+impl<T: ChebyScalar, const N: usize> From<ChebySegmentTable<T, N>> for DynChebySegmentTable<T> {
+    fn from(table: ChebySegmentTable<T, N>) -> Self {
+        let start = table.start();
+        let segment_len = table.segment_len();
+        let segments = table
+            .into_segments()
+            .into_iter()
+            .map(DynChebySegment::from)
+            .collect();
+        Self::from_segments(segments, start, segment_len)
+    }
+}
+
+impl<T: ChebyScalar, const N: usize> TryFrom<DynChebySegmentTable<T>> for ChebySegmentTable<T, N> {
+    type Error = DynChebySegmentTable<T>;
+
+    /// Convert to a const-generic table, returning the table unchanged if
+    /// any segment does not have exactly `N` coefficients.
+    fn try_from(table: DynChebySegmentTable<T>) -> Result<Self, Self::Error> {
+        if table.segments.iter().any(|s| s.len() != N) {
+            return Err(table);
+        }
+        let segments = table
+            .segments
+            .into_iter()
+            .map(|s| {
+                let coeffs: [T; N] = std::array::from_fn(|k| s.coeffs[k]);
+                ChebySegment::new(coeffs, s.mid, s.half)
+            })
+            .collect();
+        Ok(ChebySegmentTable::from_segments(
+            segments,
+            table.grid.start,
+            table.grid.segment_len,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_series_fit_and_eval() {
+        let series = ChebySeries::fit_from_fn(f64::sin, -1.0, 1.0, 15);
+        assert_eq!(series.len(), 15);
+        for &x in &[-0.9, -0.2, 0.0, 0.5] {
+            assert!((series.evaluate(x) - x.sin()).abs() < 1e-12);
+            assert!((series.evaluate_derivative(x) - x.cos()).abs() < 1e-10);
+            let (v, d) = series.evaluate_both(x);
+            assert_eq!(v, series.evaluate(x));
+            assert_eq!(d, series.evaluate_derivative(x));
+        }
+    }
+
+    #[test]
+    fn test_series_array_roundtrip() {
+        let coeffs = [1.0, 2.0, 3.0];
+        let series = ChebySeries::from(coeffs);
+        let back: [f64; 3] = series.clone().try_into().unwrap();
+        assert_eq!(back, coeffs);
+
+        let wrong: Result<[f64; 4], _> = series.clone().try_into();
+        assert_eq!(wrong.unwrap_err(), series);
+    }
+
+    #[test]
+    fn test_segment_matches_const() {
+        let fixed = ChebySegment::<f64, 11>::new(fit::fit_from_fn(f64::exp, 1.0, 3.0), 2.0, 1.0);
+        let dynamic = DynChebySegment::from_fn(f64::exp, 1.0, 3.0, 11);
+        assert_eq!(dynamic, DynChebySegment::from(fixed.clone()));
+        for &t in &[1.1, 2.0, 2.9] {
+            assert_eq!(dynamic.eval(t), fixed.eval(t));
+            assert_eq!(dynamic.eval_derivative(t), fixed.eval_derivative(t));
+            assert_eq!(dynamic.eval_both(t), fixed.eval_both(t));
+        }
+
+        let back: ChebySegment<f64, 11> = dynamic.clone().try_into().unwrap();
+        assert_eq!(back.coeffs, fixed.coeffs);
+        let wrong: Result<ChebySegment<f64, 9>, _> = dynamic.clone().try_into();
+        assert_eq!(wrong.unwrap_err(), dynamic);
+    }
+
+    #[test]
+    fn test_integration_matches_const() {
+        let fixed: ChebySegment<f64, 12> =
+            ChebySegment::new(crate::fit_from_fn(f64::sin, -1.0, 2.0), 0.5, 1.5);
+        let dynamic = DynChebySegment::from(fixed.clone());
+        assert_eq!(dynamic.integrate(-0.5, 1.5), fixed.integrate(-0.5, 1.5));
+        assert_eq!(dynamic.integral(1.0), fixed.integral(1.0));
+
+        let series = ChebySeries::from(fixed.coeffs);
+        let big_f = series.integral(0.0);
+        assert_eq!(big_f.len(), 13);
+        assert!((big_f.evaluate(1.0) - series.definite_integral()).abs() < 1e-14);
+        let table = DynChebySegmentTable::from_fn(f64::sin, 0.0, 4.0, 1.0, 12);
+        let exact = 0.3_f64.cos() - 3.8_f64.cos();
+        assert!((table.integrate(0.3, 3.8).unwrap() - exact).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_derivative_matches_const() {
+        let fixed: ChebySegmentTable<f64, 10> = ChebySegmentTable::from_fn(f64::exp, 0.0, 2.0, 0.5);
+        let dynamic = DynChebySegmentTable::from(fixed.clone()).derivative();
+        let fixed = fixed.derivative();
+        assert_eq!(dynamic.segments()[0].len(), 9);
+        for &t in &[0.1, 0.9, 1.6] {
+            assert!((dynamic.eval(t).unwrap() - fixed.eval(t).unwrap()).abs() < 1e-13);
+        }
+
+        let series = ChebySeries::new(vec![1.0, 2.0, 3.0]);
+        assert_eq!(series.derivative().coeffs(), &[2.0, 12.0]);
+    }
+
+    #[test]
+    fn test_table_matches_const() {
+        let fixed: ChebySegmentTable<f64, 9> = ChebySegmentTable::from_fn(f64::sin, 0.0, 4.0, 1.0);
+        let dynamic = DynChebySegmentTable::from_fn(f64::sin, 0.0, 4.0, 1.0, 9);
+        assert_eq!(dynamic.len(), 4);
+        assert_eq!(dynamic.end(), fixed.end());
+        for &t in &[0.0, 0.7, 1.5, 3.99] {
+            assert_eq!(dynamic.eval(t), fixed.eval(t));
+            assert_eq!(dynamic.eval_both(t), fixed.eval_both(t));
+        }
+        assert!(dynamic.eval(4.0).is_none());
+        assert!(dynamic.eval_derivative(-0.5).is_none());
+
+        assert_eq!(DynChebySegmentTable::from(fixed.clone()), dynamic);
+        let back: ChebySegmentTable<f64, 9> = dynamic.clone().try_into().unwrap();
+        assert_eq!(back.eval(2.5), fixed.eval(2.5));
+        let wrong: Result<ChebySegmentTable<f64, 5>, _> = dynamic.clone().try_into();
+        assert_eq!(wrong.unwrap_err(), dynamic);
+    }
+}
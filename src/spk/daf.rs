@@ -0,0 +1,304 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! The DAF container: file record, comment area and summary records.
+//!
+//! A DAF is a sequence of 1024-byte records. Record 1 is the file record;
+//! records 2 up to `FWARD − 1` hold comments; record `FWARD` starts a
+//! doubly linked list of summary records, each immediately followed by a
+//! record of segment names. Addresses count `f64` words from 1 at the
+//! start of the file.
+
+use super::{SpkError, SpkSegment, SpkSummary};
+use crate::bytes::Endian;
+use memmap2::Mmap;
+use std::fs::File;
+use std::path::Path;
+
+/// Bytes per DAF record.
+pub(super) const RECORD: usize = 1024;
+/// Comment characters per comment record.
+pub(super) const COMMENT_LEN: usize = 1000;
+/// `f64` words per SPK summary: two epochs and six packed `i32`s.
+pub(super) const SUMMARY_WORDS: usize = 5;
+/// Summaries per summary record, after `NEXT`, `PREV` and `NSUM`.
+pub(super) const SUMMARIES_PER_RECORD: usize = (RECORD / 8 - 3) / SUMMARY_WORDS;
+/// Characters per segment name.
+pub(super) const NAME_LEN: usize = 8 * SUMMARY_WORDS;
+/// Characters of the internal file name.
+pub(super) const IFNAME_LEN: usize = 60;
+
+/// Byte offsets of the file record fields.
+pub(super) const ND: usize = 8;
+pub(super) const NI: usize = 12;
+pub(super) const IFNAME: usize = 16;
+pub(super) const FWARD: usize = 76;
+pub(super) const BWARD: usize = 80;
+pub(super) const FREE: usize = 84;
+pub(super) const LOCFMT: usize = 88;
+pub(super) const FTPSTR: usize = 699;
+
+fn format_error(message: impl Into<String>) -> SpkError {
+    SpkError::Format(message.into())
+}
+
+/// Fixed-width text with trailing blanks and NULs removed.
+fn text(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes)
+        .trim_end_matches([' ', '\0'])
+        .to_string()
+}
+
+/// The byte order named by `LOCFMT`, or for files written before it
+/// existed, the one in which `ND` reads as 2.
+fn detect(bytes: &[u8]) -> Option<Endian> {
+    match &bytes[LOCFMT..LOCFMT + 8] {
+        b"LTL-IEEE" => Some(Endian::Little),
+        b"BIG-IEEE" => Some(Endian::Big),
+        _ => [Endian::Little, Endian::Big]
+            .into_iter()
+            .find(|e| e.i32(bytes, ND) == 2),
+    }
+}
+
+/// Walk the summary records from `first`, collecting every summary.
+fn read_summaries(bytes: &[u8], endian: Endian, first: usize) -> Result<Vec<SpkSummary>, SpkError> {
+    let records = bytes.len() / RECORD;
+    let mut summaries = Vec::new();
+    let mut next = first;
+    let mut visited = 0;
+    while next != 0 {
+        visited += 1;
+        if next > records || visited > records {
+            return Err(format_error(format!(
+                "summary record {next} is out of range"
+            )));
+        }
+        if next == records {
+            return Err(format_error(format!(
+                "summary record {next} has no name record"
+            )));
+        }
+        let at = (next - 1) * RECORD;
+        let word = |i: usize| endian.f64(bytes, at + 8 * i);
+        let count = word(2);
+        if !(0.0..=SUMMARIES_PER_RECORD as f64).contains(&count) || count.fract() != 0.0 {
+            return Err(format_error(format!(
+                "summary record {next} lists {count} summaries"
+            )));
+        }
+        for s in 0..count as usize {
+            let base = at + 8 * (3 + SUMMARY_WORDS * s);
+            let int = |i: usize| endian.i32(bytes, base + 16 + 4 * i);
+            let address = |i: usize| {
+                usize::try_from(int(i))
+                    .map_err(|_| format_error(format!("negative address in record {next}")))
+            };
+            let name_at = at + RECORD + NAME_LEN * s;
+            summaries.push(SpkSummary {
+                name: text(&bytes[name_at..name_at + NAME_LEN]),
+                start: endian.f64(bytes, base),
+                end: endian.f64(bytes, base + 8),
+                target: int(0),
+                center: int(1),
+                frame: int(2),
+                data_type: int(3),
+                start_address: address(4)?,
+                end_address: address(5)?,
+            });
+        }
+        let forward = word(0);
+        if forward < 0.0 || forward.fract() != 0.0 {
+            return Err(format_error(format!("bad link in summary record {next}")));
+        }
+        next = forward as usize;
+    }
+    Ok(summaries)
+}
+
+/// An SPK file, read in place.
+///
+/// Opening the file decodes its summaries; segments are decoded on
+/// request by [`segment`](Self::segment). `B` is the storage holding the
+/// file: a memory map for [`open`](Self::open), or any byte buffer for
+/// [`from_bytes`](Self::from_bytes).
+#[derive(Debug)]
+pub struct Spk<B = Mmap> {
+    /// The file contents.
+    bytes: B,
+    /// Byte order of the file.
+    endian: Endian,
+    /// Internal file name (`LOCIFN`).
+    internal_name: String,
+    /// First summary record (`FWARD`).
+    first_summary: usize,
+    /// Every summary, in file order.
+    summaries: Vec<SpkSummary>,
+}
+
+impl Spk<Mmap> {
+    /// Memory-map the file at `path`.
+    ///
+    /// The file must not be truncated or modified while it is open: like
+    /// every memory map, the contents are read from the file on access.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SpkError> {
+        let file = File::open(path)?;
+        // SAFETY: the map is read-only and only exposed as `&[u8]`; the
+        // caller is told above not to change the file underneath it.
+        let map = unsafe { Mmap::map(&file)? };
+        Self::from_bytes(map)
+    }
+}
+
+impl<B: AsRef<[u8]>> Spk<B> {
+    /// Read the file record and every summary from the complete contents
+    /// of a file.
+    ///
+    /// The byte order is taken from the file record. Only SPK summaries
+    /// (`ND = 2`, `NI = 6`) are accepted; segments of any data type are
+    /// listed, but only types 2 and 3 can be evaluated.
+    pub fn from_bytes(bytes: B) -> Result<Self, SpkError> {
+        let data = bytes.as_ref();
+        if data.len() < RECORD {
+            return Err(format_error("file is shorter than its file record"));
+        }
+        if !matches!(&data[..8], b"DAF/SPK " | b"NAIF/DAF") {
+            return Err(format_error("not a DAF/SPK file"));
+        }
+        let endian = detect(data).ok_or_else(|| format_error("cannot detect byte order"))?;
+        let (nd, ni) = (endian.i32(data, ND), endian.i32(data, NI));
+        if (nd, ni) != (2, 6) {
+            return Err(format_error(format!(
+                "summaries have ND = {nd}, NI = {ni}; SPK needs 2 and 6"
+            )));
+        }
+        let first_summary = usize::try_from(endian.i32(data, FWARD))
+            .ok()
+            .filter(|&r| r >= 2)
+            .ok_or_else(|| format_error("bad first summary record"))?;
+        let summaries = read_summaries(data, endian, first_summary)?;
+        let internal_name = text(&data[IFNAME..IFNAME + IFNAME_LEN]);
+        Ok(Self {
+            bytes,
+            endian,
+            internal_name,
+            first_summary,
+            summaries,
+        })
+    }
+
+    /// Byte order of the file.
+    #[inline]
+    pub fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    /// Internal file name.
+    #[inline]
+    pub fn internal_name(&self) -> &str {
+        &self.internal_name
+    }
+
+    /// Every segment summary, in file order.
+    #[inline]
+    pub fn summaries(&self) -> &[SpkSummary] {
+        &self.summaries
+    }
+
+    /// The comment area, with line ends restored.
+    ///
+    /// Comments are stored 1000 characters per record with NUL between
+    /// lines and EOT after the last one.
+    pub fn comments(&self) -> String {
+        let mut out = String::new();
+        for r in 1..self.first_summary - 1 {
+            let at = r * RECORD;
+            let chunk = &self.bytes.as_ref()[at..at + COMMENT_LEN];
+            let end = chunk.iter().position(|&b| b == 4);
+            for &b in &chunk[..end.unwrap_or(COMMENT_LEN)] {
+                out.push(if b == 0 { '\n' } else { b as char });
+            }
+            if end.is_some() {
+                break;
+            }
+        }
+        out
+    }
+
+    /// The segment for `target` relative to `center` in `frame`.
+    ///
+    /// As in SPICE, later segments take precedence over earlier ones.
+    pub fn find(&self, target: i32, center: i32, frame: i32) -> Option<&SpkSummary> {
+        self.summaries
+            .iter()
+            .rev()
+            .find(|s| s.matches(target, center, frame))
+    }
+
+    /// The segment for `target` relative to `center` in `frame` that
+    /// covers `t`, later segments taking precedence.
+    pub fn find_at(&self, target: i32, center: i32, frame: i32, t: f64) -> Option<&SpkSummary> {
+        self.summaries
+            .iter()
+            .rev()
+            .find(|s| s.matches(target, center, frame) && s.covers(t))
+    }
+
+    /// Decode the type 2 or type 3 segment described by `summary`.
+    pub fn segment<'a>(&'a self, summary: &'a SpkSummary) -> Result<SpkSegment<'a>, SpkError> {
+        let bytes = self.bytes.as_ref();
+        let (first, last) = (summary.start_address, summary.end_address);
+        if first == 0 || last < first || 8 * last > bytes.len() {
+            return Err(format_error(format!(
+                "segment `{}` addresses {first}..={last} lie outside the file",
+                summary.name
+            )));
+        }
+        SpkSegment::new(summary, &bytes[8 * (first - 1)..8 * last], self.endian)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A file record and one empty summary record.
+    fn empty_file() -> Vec<u8> {
+        let mut bytes = vec![0; 3 * RECORD];
+        bytes[..8].copy_from_slice(b"DAF/SPK ");
+        bytes[ND..ND + 4].copy_from_slice(&2_i32.to_le_bytes());
+        bytes[NI..NI + 4].copy_from_slice(&6_i32.to_le_bytes());
+        bytes[FWARD..FWARD + 4].copy_from_slice(&2_i32.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_detect_without_locfmt() {
+        let mut bytes = empty_file();
+        assert_eq!(detect(&bytes), Some(Endian::Little));
+        bytes[ND..ND + 4].copy_from_slice(&2_i32.to_be_bytes());
+        assert_eq!(detect(&bytes), Some(Endian::Big));
+        bytes[LOCFMT..LOCFMT + 8].copy_from_slice(b"LTL-IEEE");
+        assert_eq!(detect(&bytes), Some(Endian::Little));
+    }
+
+    #[test]
+    fn test_file_record_errors() {
+        let spk = Spk::from_bytes(empty_file()).unwrap();
+        assert!(spk.summaries().is_empty());
+        assert_eq!(spk.comments(), "");
+
+        let mut bytes = empty_file();
+        bytes[..8].copy_from_slice(b"DAF/CK  ");
+        assert!(matches!(Spk::from_bytes(bytes), Err(SpkError::Format(_))));
+        let mut bytes = empty_file();
+        bytes[NI..NI + 4].copy_from_slice(&5_i32.to_le_bytes());
+        assert!(matches!(Spk::from_bytes(bytes), Err(SpkError::Format(_))));
+        // A forward link past the end of the file.
+        let mut bytes = empty_file();
+        bytes[RECORD..RECORD + 8].copy_from_slice(&9.0_f64.to_le_bytes());
+        let err = Spk::from_bytes(bytes).unwrap_err();
+        assert!(err.to_string().contains("out of range"), "{err}");
+        assert!(Spk::from_bytes(vec![0; 100]).is_err());
+    }
+}
@@ -0,0 +1,429 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! SPICE SPK kernels with Chebyshev segments (feature `spk`).
+//!
+//! An SPK file is a DAF (double-precision array file): a file record, an
+//! optional comment area, and a linked list of summary records, each
+//! describing the segments (arrays) stored after them. Every segment
+//! gives the ephemeris of one `target` relative to one `center` in one
+//! `frame` over a time span, in TDB seconds past J2000.
+//!
+//! Types 2 and 3 are uniform Chebyshev tables: `N` records of `RSIZE`
+//! values, each the midpoint and radius of its interval followed by the
+//! coefficients of x, y, z (type 2) or x, y, z, vx, vy, vz (type 3),
+//! closed by the directory `INIT, INTLEN, RSIZE, N`. [`SpkSegment`] reads
+//! them in place and evaluates them like a
+//! [`ChebyVecSegmentTable`], to which
+//! [`SpkSegment::to_table`] converts them. Positions are in km and
+//! velocities in km/s. [`SpkWriter`] goes the other way, writing vector
+//! tables as type 2 or type 3 segments.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use cheby::spk::Spk;
+//!
+//! let spk = Spk::open("de440s.bsp")?;
+//! // Mars barycentre relative to the solar-system barycentre, J2000 frame.
+//! let summary = spk.find(4, 0, 1).expect("no such segment");
+//! let segment = spk.segment(summary)?;
+//! let (position, velocity) = segment.eval_both(0.0).unwrap();
+//! # let _ = (position, velocity);
+//! # Ok::<(), cheby::spk::SpkError>(())
+//! ```
+
+mod daf;
+mod writer;
+
+use crate::eval::{clenshaw, clenshaw_both};
+use crate::vector::{ChebyVecSegment, ChebyVecSegmentTable};
+use std::fmt;
+
+pub use crate::bytes::Endian;
+pub use daf::Spk;
+pub use writer::SpkWriter;
+
+// ─────────────────────────────────────────────────────────────────────────
+// Errors
+// ─────────────────────────────────────────────────────────────────────────
+
+/// Why an SPK file or segment could not be read.
+#[derive(Debug)]
+pub enum SpkError {
+    /// The file could not be read.
+    Io(std::io::Error),
+    /// The file is not a well-formed DAF/SPK file.
+    Format(String),
+    /// The segment has an SPK data type other than 2 or 3.
+    UnsupportedType(i32),
+    /// A segment, name or comment cannot be written to an SPK file.
+    Invalid(String),
+    /// A table of `requested` coefficients or components was asked for a
+    /// segment stored with `stored`.
+    Shape {
+        /// `"coefficients"` or `"components"`.
+        what: &'static str,
+        /// What the segment holds.
+        stored: usize,
+        /// What was requested.
+        requested: usize,
+    },
+}
+
+impl fmt::Display for SpkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "cannot read SPK file: {e}"),
+            Self::Format(message) => write!(f, "malformed SPK file: {message}"),
+            Self::Invalid(message) => write!(f, "cannot write SPK file: {message}"),
+            Self::UnsupportedType(t) => {
+                write!(f, "SPK type {t} is not a Chebyshev type (2 or 3)")
+            }
+            Self::Shape {
+                what,
+                stored,
+                requested,
+            } => write!(f, "segment has {stored} {what}, requested {requested}"),
+        }
+    }
+}
+
+impl std::error::Error for SpkError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for SpkError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// Summaries
+// ─────────────────────────────────────────────────────────────────────────
+
+/// The descriptor of one SPK segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpkSummary {
+    /// Segment name (up to 40 characters, trailing blanks removed).
+    pub name: String,
+    /// First epoch covered, TDB seconds past J2000.
+    pub start: f64,
+    /// Last epoch covered, TDB seconds past J2000.
+    pub end: f64,
+    /// NAIF ID of the target body.
+    pub target: i32,
+    /// NAIF ID of the centre of motion.
+    pub center: i32,
+    /// NAIF ID of the reference frame (1 is J2000).
+    pub frame: i32,
+    /// SPK data type.
+    pub data_type: i32,
+    /// Address of the first value of the segment (1-based, in `f64`s).
+    pub start_address: usize,
+    /// Address of the last value of the segment (1-based, in `f64`s).
+    pub end_address: usize,
+}
+
+impl SpkSummary {
+    /// `true` if the segment is for `target` relative to `center` in `frame`.
+    #[inline]
+    pub fn matches(&self, target: i32, center: i32, frame: i32) -> bool {
+        (self.target, self.center, self.frame) == (target, center, frame)
+    }
+
+    /// `true` if `t` lies within `[start, end]`.
+    #[inline]
+    pub fn covers(&self, t: f64) -> bool {
+        t >= self.start && t <= self.end
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// SpkSegment — a type 2 or 3 segment, read in place
+// ─────────────────────────────────────────────────────────────────────────
+
+/// A type 2 or type 3 SPK segment: `N` uniform Chebyshev records.
+///
+/// Lookup follows SPICE: the record is `⌊(t − INIT) / INTLEN⌋`, with the
+/// end of the last interval belonging to the last record, and only times
+/// within the summary's `[start, end]` are accepted.
+#[derive(Debug, Clone, Copy)]
+pub struct SpkSegment<'a> {
+    summary: &'a SpkSummary,
+    /// The `N` records, without the directory.
+    records: &'a [u8],
+    endian: Endian,
+    init: f64,
+    intlen: f64,
+    rsize: usize,
+    n: usize,
+}
+
+impl<'a> SpkSegment<'a> {
+    /// Decode the directory at the end of `data`, the segment's values.
+    fn new(summary: &'a SpkSummary, data: &'a [u8], endian: Endian) -> Result<Self, SpkError> {
+        let components = match summary.data_type {
+            2 => 3,
+            3 => 6,
+            t => return Err(SpkError::UnsupportedType(t)),
+        };
+        let bad =
+            |message: &str| SpkError::Format(format!("segment `{}`: {message}", summary.name));
+        let words = data.len() / 8;
+        if words < 4 {
+            return Err(bad("too short for a directory"));
+        }
+        let word = |i: usize| endian.f64(data, 8 * i);
+        let (init, intlen) = (word(words - 4), word(words - 3));
+        let (rsize, n) = (word(words - 2), word(words - 1));
+        let valid_count = |x: f64| x >= 0.0 && x.fract() == 0.0 && x < 1e15;
+        if !(valid_count(rsize) && valid_count(n)) {
+            return Err(bad("RSIZE and N must be counts"));
+        }
+        let (rsize, n) = (rsize as usize, n as usize);
+        if rsize < 2 + components || (rsize - 2) % components != 0 {
+            return Err(bad("RSIZE does not fit the segment type"));
+        }
+        if n == 0 || n * rsize + 4 != words {
+            return Err(bad("N × RSIZE does not match the segment length"));
+        }
+        if !(intlen > 0.0 && intlen.is_finite()) {
+            return Err(bad("INTLEN must be positive"));
+        }
+        Ok(Self {
+            summary,
+            records: &data[..8 * n * rsize],
+            endian,
+            init,
+            intlen,
+            rsize,
+            n,
+        })
+    }
+
+    /// The segment's summary.
+    #[inline]
+    pub fn summary(&self) -> &'a SpkSummary {
+        self.summary
+    }
+
+    /// Start of the first record (`INIT`).
+    #[inline]
+    pub fn init(&self) -> f64 {
+        self.init
+    }
+
+    /// Length of each record's interval (`INTLEN`).
+    #[inline]
+    pub fn segment_len(&self) -> f64 {
+        self.intlen
+    }
+
+    /// Values per record (`RSIZE`).
+    #[inline]
+    pub fn rsize(&self) -> usize {
+        self.rsize
+    }
+
+    /// Number of records (`N`).
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Always `false`: a segment without records is rejected.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Coefficients per component (degree + 1).
+    #[inline]
+    pub fn ncoeff(&self) -> usize {
+        (self.rsize - 2) / self.components()
+    }
+
+    /// Components per record: 3 (type 2) or 6 (type 3).
+    #[inline]
+    pub fn components(&self) -> usize {
+        if self.summary.data_type == 2 {
+            3
+        } else {
+            6
+        }
+    }
+
+    /// First epoch covered.
+    #[inline]
+    pub fn start(&self) -> f64 {
+        self.summary.start
+    }
+
+    /// Last epoch covered.
+    #[inline]
+    pub fn end(&self) -> f64 {
+        self.summary.end
+    }
+
+    /// Value `k` of record `i`.
+    #[inline]
+    fn value(&self, i: usize, k: usize) -> f64 {
+        self.endian.f64(self.records, 8 * (i * self.rsize + k))
+    }
+
+    /// `(mid, radius)` of record `i`.
+    #[inline]
+    pub fn record_domain(&self, i: usize) -> (f64, f64) {
+        (self.value(i, 0), self.value(i, 1))
+    }
+
+    /// Index of the record covering `t`, or `None` if `t` is outside
+    /// `[start, end]`.
+    #[inline]
+    pub fn segment_index(&self, t: f64) -> Option<usize> {
+        if !self.summary.covers(t) || t < self.init {
+            return None;
+        }
+        let i = ((t - self.init) / self.intlen) as usize;
+        Some(i.min(self.n - 1))
+    }
+
+    /// Record covering `t`, `t` mapped to `τ` in it, and its radius.
+    #[inline]
+    fn locate(&self, t: f64) -> Option<(usize, f64, f64)> {
+        let i = self.segment_index(t)?;
+        let (mid, radius) = self.record_domain(i);
+        Some((i, (t - mid) / radius, radius))
+    }
+
+    /// Clenshaw sum over component `d` of record `i`.
+    #[inline]
+    fn component(&self, i: usize, d: usize, tau: f64) -> f64 {
+        let n = self.ncoeff();
+        clenshaw(n, |k| self.value(i, 2 + d * n + k), tau)
+    }
+
+    /// Position at `t`, or `None` if out of range.
+    pub fn eval(&self, t: f64) -> Option<[f64; 3]> {
+        let (i, tau, _) = self.locate(t)?;
+        Some(std::array::from_fn(|d| self.component(i, d, tau)))
+    }
+
+    /// Position and velocity at `t`, or `None` if out of range.
+    ///
+    /// Type 2 differentiates the position series; type 3 evaluates its
+    /// velocity series.
+    pub fn eval_both(&self, t: f64) -> Option<([f64; 3], [f64; 3])> {
+        let (i, tau, radius) = self.locate(t)?;
+        if self.summary.data_type == 3 {
+            let p = std::array::from_fn(|d| self.component(i, d, tau));
+            let v = std::array::from_fn(|d| self.component(i, d + 3, tau));
+            return Some((p, v));
+        }
+        let n = self.ncoeff();
+        let both: [(f64, f64); 3] =
+            std::array::from_fn(|d| clenshaw_both(n, |k| self.value(i, 2 + d * n + k), tau));
+        Some((both.map(|b| b.0), both.map(|b| b.1 / radius)))
+    }
+
+    /// Record `i` as a segment of `N` coefficients and `D` components.
+    fn record_segment<const N: usize, const D: usize>(
+        &self,
+        i: usize,
+    ) -> ChebyVecSegment<f64, N, D> {
+        let coeffs = std::array::from_fn(|d| std::array::from_fn(|k| self.value(i, 2 + d * N + k)));
+        let (mid, radius) = self.record_domain(i);
+        ChebyVecSegment::new(coeffs, mid, radius)
+    }
+
+    /// Copy the segment into a vector table starting at `INIT` with
+    /// segments of `INTLEN`.
+    ///
+    /// `N` must equal [`ncoeff`](Self::ncoeff) and `D`
+    /// [`components`](Self::components): 3 for type 2, 6 (position then
+    /// velocity) for type 3.
+    pub fn to_table<const N: usize, const D: usize>(
+        &self,
+    ) -> Result<ChebyVecSegmentTable<f64, N, D>, SpkError> {
+        for (what, stored, requested) in [
+            ("components", self.components(), D),
+            ("coefficients", self.ncoeff(), N),
+        ] {
+            if stored != requested {
+                return Err(SpkError::Shape {
+                    what,
+                    stored,
+                    requested,
+                });
+            }
+        }
+        let segments = (0..self.n).map(|i| self.record_segment(i)).collect();
+        Ok(ChebyVecSegmentTable::from_segments(
+            segments,
+            self.init,
+            self.intlen,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(data_type: i32) -> SpkSummary {
+        SpkSummary {
+            name: "TEST".into(),
+            start: 0.0,
+            end: 20.0,
+            target: 10,
+            center: 0,
+            frame: 1,
+            data_type,
+            start_address: 1,
+            end_address: 0,
+        }
+    }
+
+    fn words(values: &[f64]) -> Vec<u8> {
+        values.iter().flat_map(|x| x.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn test_directory() {
+        // Two type 2 records of two coefficients: x, y, z = 1, 2, 3 + τ.
+        let record = |mid: f64| [mid, 5.0, 1.0, 0.0, 2.0, 0.0, 3.0, 1.0];
+        let mut values = [record(5.0), record(15.0)].concat();
+        values.extend([0.0, 10.0, 8.0, 2.0]);
+        let (s, data) = (summary(2), words(&values));
+        let segment = SpkSegment::new(&s, &data, Endian::Little).unwrap();
+        assert_eq!((segment.len(), segment.ncoeff()), (2, 2));
+        assert_eq!(segment.eval(17.5), Some([1.0, 2.0, 3.5]));
+        assert_eq!(
+            segment.eval_both(20.0),
+            Some(([1.0, 2.0, 4.0], [0.0, 0.0, 0.2]))
+        );
+
+        let bad = |values: &[f64], data_type| {
+            let data = words(values);
+            SpkSegment::new(&summary(data_type), &data, Endian::Little).map(|_| ())
+        };
+        assert!(matches!(bad(&values, 1), Err(SpkError::UnsupportedType(1))));
+        let n = values.len() - 1;
+        // Five coefficients cannot be split over three components.
+        values[n - 1] = 7.0;
+        assert!(matches!(bad(&values, 2), Err(SpkError::Format(_))));
+        values[n - 1] = 8.0;
+        values[n] = 3.0;
+        assert!(matches!(bad(&values, 2), Err(SpkError::Format(_))));
+        values[n] = 2.5;
+        assert!(matches!(bad(&values, 2), Err(SpkError::Format(_))));
+        assert!(matches!(bad(&[1.0; 3], 2), Err(SpkError::Format(_))));
+    }
+}
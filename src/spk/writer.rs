@@ -0,0 +1,390 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! Writing type 2 and type 3 SPK kernels.
+//!
+//! The file is laid out as SPICE's own writers would leave it after
+//! adding every segment in one go: the file record, the comment records,
+//! the linked summary/name record pairs, then the segment data in the
+//! order the segments were added, padded to a whole record.
+
+use super::daf::{
+    BWARD, COMMENT_LEN, FREE, FTPSTR, FWARD, IFNAME_LEN, LOCFMT, NAME_LEN, RECORD,
+    SUMMARIES_PER_RECORD,
+};
+use super::{Endian, SpkError, SpkSummary};
+use crate::grid::UniformGrid;
+use crate::vector::ChebyVecSegmentTable;
+use std::io::Write;
+use std::path::Path;
+
+/// The FTP validation string, which shows whether a file went through a
+/// text-mode transfer.
+const FTP: &[u8; 28] = b"FTPSTR:\r:\n:\r\n:\r\x00:\x81:\x10\xce:ENDFTP";
+
+fn invalid(message: impl Into<String>) -> SpkError {
+    SpkError::Invalid(message.into())
+}
+
+/// Check that `text` is printable ASCII of at most `max` characters.
+fn check_text(what: &str, text: &str, max: usize) -> Result<(), SpkError> {
+    if !text.bytes().all(|b| b.is_ascii_graphic() || b == b' ') {
+        return Err(invalid(format!("{what} `{text}` is not printable ASCII")));
+    }
+    if text.len() > max {
+        return Err(invalid(format!(
+            "{what} `{text}` is longer than {max} characters"
+        )));
+    }
+    Ok(())
+}
+
+/// An SPK kernel being assembled from vector tables.
+///
+/// Segments are added with [`push_type2`](Self::push_type2) (position
+/// only; velocity is the derivative) or [`push_type3`](Self::push_type3)
+/// (position and velocity fitted separately) and written in one go. As
+/// in SPICE, times are TDB seconds past J2000, positions km and
+/// velocities km/s; the tables are written as they are. A table is
+/// rejected unless its domain is finite and each segment covers its slot
+/// `[start + i·segment_len, start + (i+1)·segment_len]`, which is where
+/// readers look for it.
+///
+/// ```
+/// use cheby::spk::{Spk, SpkWriter};
+/// use cheby::ChebyVecSegmentTable;
+///
+/// let orbit = |t: f64| [t.cos(), t.sin(), 0.0];
+/// let table = ChebyVecSegmentTable::<f64, 12, 3>::from_fn(orbit, 0.0, 8.0, 2.0);
+/// let mut writer = SpkWriter::new("CIRCLE").with_comments("A unit circle.\n");
+/// writer.push_type2("CIRCLE", -1000, 0, 1, &table)?;
+///
+/// let spk = Spk::from_bytes(writer.to_bytes()?)?;
+/// let segment = spk.segment(spk.find(-1000, 0, 1).unwrap())?;
+/// assert_eq!(segment.eval(3.0), table.eval(3.0));
+/// # Ok::<(), cheby::spk::SpkError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpkWriter {
+    endian: Endian,
+    internal_name: String,
+    comments: String,
+    /// Each segment's summary, with addresses assigned on writing, and
+    /// its values.
+    segments: Vec<(SpkSummary, Vec<f64>)>,
+}
+
+impl SpkWriter {
+    /// An empty little-endian kernel with internal file name
+    /// `internal_name` (at most 60 characters).
+    pub fn new(internal_name: impl Into<String>) -> Self {
+        Self {
+            endian: Endian::Little,
+            internal_name: internal_name.into(),
+            comments: String::new(),
+            segments: Vec::new(),
+        }
+    }
+
+    /// Set the byte order of the file.
+    #[inline]
+    pub fn with_endian(mut self, endian: Endian) -> Self {
+        self.endian = endian;
+        self
+    }
+
+    /// Set the comment area: ASCII text, lines separated by `\n`.
+    #[inline]
+    pub fn with_comments(mut self, comments: impl Into<String>) -> Self {
+        self.comments = comments.into();
+        self
+    }
+
+    /// Number of segments added.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// `true` if no segment was added.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Add a type 2 segment of `target` relative to `center` in `frame`
+    /// from a table of positions.
+    pub fn push_type2<const N: usize>(
+        &mut self,
+        name: &str,
+        target: i32,
+        center: i32,
+        frame: i32,
+        table: &ChebyVecSegmentTable<f64, N, 3>,
+    ) -> Result<(), SpkError> {
+        self.push(name, [target, center, frame, 2], table)
+    }
+
+    /// Add a type 3 segment of `target` relative to `center` in `frame`
+    /// from a table of positions followed by velocities.
+    pub fn push_type3<const N: usize>(
+        &mut self,
+        name: &str,
+        target: i32,
+        center: i32,
+        frame: i32,
+        table: &ChebyVecSegmentTable<f64, N, 6>,
+    ) -> Result<(), SpkError> {
+        self.push(name, [target, center, frame, 3], table)
+    }
+
+    /// Add a segment with ids `[target, center, frame, data_type]`.
+    fn push<const N: usize, const D: usize>(
+        &mut self,
+        name: &str,
+        [target, center, frame, data_type]: [i32; 4],
+        table: &ChebyVecSegmentTable<f64, N, D>,
+    ) -> Result<(), SpkError> {
+        check_text("segment name", name, NAME_LEN)?;
+        if N == 0 || table.is_empty() {
+            return Err(invalid(format!("segment `{name}` has no coefficients")));
+        }
+        // Readers locate records through `INIT` and `INTLEN` alone, so each
+        // record must cover its slot.
+        UniformGrid::new(table.start(), table.segment_len())
+            .check_slots(table.segments().iter().map(|s| (s.mid, s.half)))
+            .map_err(|e| invalid(format!("segment `{name}`: {e}")))?;
+        let mut words = Vec::with_capacity(table.len() * (2 + N * D) + 4);
+        for segment in table.segments() {
+            words.extend([segment.mid, segment.half]);
+            words.extend(segment.coeffs.iter().flatten());
+        }
+        words.extend([
+            table.start(),
+            table.segment_len(),
+            (2 + N * D) as f64,
+            table.len() as f64,
+        ]);
+        let summary = SpkSummary {
+            name: name.to_string(),
+            start: table.start(),
+            end: table.end(),
+            target,
+            center,
+            frame,
+            data_type,
+            start_address: 0,
+            end_address: 0,
+        };
+        self.segments.push((summary, words));
+        Ok(())
+    }
+
+    /// The comment area: `\n` stored as NUL, closed by EOT.
+    fn encoded_comments(&self) -> Result<Vec<u8>, SpkError> {
+        if self.comments.is_empty() {
+            return Ok(Vec::new());
+        }
+        if let Some(line) = self
+            .comments
+            .lines()
+            .find(|l| !l.bytes().all(|b| b.is_ascii_graphic() || b == b' '))
+        {
+            return Err(invalid(format!(
+                "comment line `{line}` is not printable ASCII"
+            )));
+        }
+        let mut out: Vec<u8> = self
+            .comments
+            .bytes()
+            .map(|b| if b == b'\n' { 0 } else { b })
+            .collect();
+        out.push(4);
+        Ok(out)
+    }
+
+    /// Summaries of the segments as they will be written, with their
+    /// addresses.
+    pub fn summaries(&self) -> Vec<SpkSummary> {
+        let comment_records = self
+            .encoded_comments()
+            .map_or(0, |c| c.len().div_ceil(COMMENT_LEN));
+        self.layout(comment_records).0
+    }
+
+    /// Summaries with addresses, and the first free address, for a file
+    /// with `comment_records` comment records.
+    fn layout(&self, comment_records: usize) -> (Vec<SpkSummary>, usize) {
+        let summary_records = self.summary_records();
+        let mut address = (1 + comment_records + 2 * summary_records) * RECORD / 8 + 1;
+        let summaries = self
+            .segments
+            .iter()
+            .map(|(summary, words)| {
+                let start_address = address;
+                address += words.len();
+                SpkSummary {
+                    start_address,
+                    end_address: address - 1,
+                    ..summary.clone()
+                }
+            })
+            .collect();
+        (summaries, address)
+    }
+
+    /// Summary records needed; an empty kernel still has one.
+    fn summary_records(&self) -> usize {
+        self.segments.len().div_ceil(SUMMARIES_PER_RECORD).max(1)
+    }
+
+    /// The complete file.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SpkError> {
+        check_text("internal file name", &self.internal_name, IFNAME_LEN)?;
+        let comments = self.encoded_comments()?;
+        let comment_records = comments.len().div_ceil(COMMENT_LEN);
+        let (summaries, free) = self.layout(comment_records);
+        if free > i32::MAX as usize {
+            return Err(invalid("the segments exceed the DAF address range"));
+        }
+        let first_summary = 2 + comment_records;
+        let summary_records = self.summary_records();
+        let e = self.endian;
+        let mut out = Vec::with_capacity((free / (RECORD / 8) + 1) * RECORD);
+
+        // File record.
+        out.extend(b"DAF/SPK ");
+        e.put_i32(&mut out, 2);
+        e.put_i32(&mut out, 6);
+        out.extend(format!("{:<IFNAME_LEN$}", self.internal_name).bytes());
+        let last_summary = first_summary + 2 * (summary_records - 1);
+        for (at, x) in [(FWARD, first_summary), (BWARD, last_summary), (FREE, free)] {
+            debug_assert_eq!(out.len(), at);
+            e.put_i32(&mut out, x as i32);
+        }
+        debug_assert_eq!(out.len(), LOCFMT);
+        out.extend(match e {
+            Endian::Little => b"LTL-IEEE",
+            Endian::Big => b"BIG-IEEE",
+        });
+        out.resize(FTPSTR, 0);
+        out.extend(FTP);
+        out.resize(RECORD, 0);
+
+        // Comment records.
+        for chunk in comments.chunks(COMMENT_LEN) {
+            let end = out.len() + RECORD;
+            out.extend(chunk);
+            out.resize(end, 0);
+        }
+
+        // Summary and name records.
+        for k in 0..summary_records {
+            let record = first_summary + 2 * k;
+            let next = if k + 1 < summary_records {
+                record + 2
+            } else {
+                0
+            };
+            let prev = if k > 0 { record - 2 } else { 0 };
+            let chunk = summaries
+                .chunks(SUMMARIES_PER_RECORD)
+                .nth(k)
+                .unwrap_or_default();
+            let end = out.len() + RECORD;
+            for x in [next, prev, chunk.len()] {
+                e.put_f64(&mut out, x as f64);
+            }
+            for s in chunk {
+                e.put_f64(&mut out, s.start);
+                e.put_f64(&mut out, s.end);
+                for x in [s.target, s.center, s.frame, s.data_type] {
+                    e.put_i32(&mut out, x);
+                }
+                e.put_i32(&mut out, s.start_address as i32);
+                e.put_i32(&mut out, s.end_address as i32);
+            }
+            out.resize(end, 0);
+            for s in chunk {
+                out.extend(format!("{:<NAME_LEN$}", s.name).bytes());
+            }
+            out.resize(end + RECORD, b' ');
+        }
+
+        // Segment data.
+        for (_, words) in &self.segments {
+            for &x in words {
+                e.put_f64(&mut out, x);
+            }
+        }
+        out.resize(out.len().div_ceil(RECORD) * RECORD, 0);
+        Ok(out)
+    }
+
+    /// Write the complete file to `w`.
+    pub fn write_to(&self, mut w: impl Write) -> Result<(), SpkError> {
+        w.write_all(&self.to_bytes()?)?;
+        Ok(())
+    }
+
+    /// Write the complete file to `path`.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), SpkError> {
+        std::fs::write(path, self.to_bytes()?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spk::Spk;
+
+    #[test]
+    fn test_empty_kernel() {
+        let bytes = SpkWriter::new("EMPTY").to_bytes().unwrap();
+        assert_eq!(bytes.len(), 2 * RECORD + RECORD);
+        assert_eq!(&bytes[FTPSTR..FTPSTR + FTP.len()], FTP);
+        let spk = Spk::from_bytes(bytes).unwrap();
+        assert!(spk.summaries().is_empty());
+        assert_eq!(spk.internal_name(), "EMPTY");
+    }
+
+    #[test]
+    fn test_rejects_bad_text() {
+        let table = ChebyVecSegmentTable::<f64, 2, 3>::from_fn(|t| [t; 3], 0.0, 1.0, 1.0);
+        let mut writer = SpkWriter::new("X");
+        let err = writer
+            .push_type2(&"N".repeat(41), 1, 0, 1, &table)
+            .unwrap_err();
+        assert!(matches!(err, SpkError::Invalid(_)), "{err}");
+        assert!(writer.push_type2("TAB\t", 1, 0, 1, &table).is_err());
+        let empty = ChebyVecSegmentTable::<f64, 2, 3>::from_segments(Vec::new(), 0.0, 1.0);
+        assert!(writer.push_type2("EMPTY", 1, 0, 1, &empty).is_err());
+        assert!(writer.is_empty());
+
+        let err = writer
+            .push_type2(
+                "NAN",
+                1,
+                0,
+                1,
+                &ChebyVecSegmentTable::from_segments(table.segments().to_vec(), f64::NAN, 1.0),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot write SPK file: segment `NAN`: domain start NaN, segment length 1"
+        );
+        let shifted = ChebyVecSegmentTable::from_segments(table.segments().to_vec(), 0.5, 1.0);
+        let err = writer.push_type2("SHIFTED", 1, 0, 1, &shifted).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "cannot write SPK file: segment `SHIFTED`: segment 0 covers [0, 1], expected [0.5, 1.5]"
+        );
+        assert!(writer.is_empty());
+
+        let writer = SpkWriter::new("X").with_comments("bell \x07");
+        assert!(matches!(writer.to_bytes(), Err(SpkError::Invalid(_))));
+        assert!(SpkWriter::new("é").to_bytes().is_err());
+    }
+}
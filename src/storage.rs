@@ -0,0 +1,666 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! A versioned binary file format for segment tables.
+//!
+//! [`ChebySegmentTable::write_to`] and [`ChebySegmentTable::read_from`]
+//! save a fitted table and load it back exactly, so it need not be refit
+//! on every start. Values are any [`Storable`] scalar (`f64`, `f32`,
+//! `qtty` quantities and [`Vector`]s of them) and the axis any
+//! [`StorableAxis`]; the file records both units, and reading checks
+//! them, the degree and the value shape against the requested type.
+//!
+//! # Layout (version 1)
+//!
+//! All numbers are little-endian.
+//!
+//! | Bytes        | Field                                                    |
+//! |--------------|----------------------------------------------------------|
+//! | 8            | magic `CHEBYTAB`                                         |
+//! | 2            | format version (`u16`, currently 1)                      |
+//! | 1            | bytes per stored number: 8 (`f64`) or 4 (`f32`)          |
+//! | 1            | reserved, 0                                              |
+//! | 4            | `N`, coefficients per segment (`u32`)                    |
+//! | 4            | numbers per value, e.g. 3 for a `Vec3` (`u32`)           |
+//! | 8            | segment count (`u64`)                                    |
+//! | 2 + s + 8    | value unit: symbol length (`u16`), UTF-8 symbol, ratio   |
+//! | 2 + s + 8    | axis unit, likewise                                      |
+//! | 16           | start and segment length (`f64`, in the axis unit)       |
+//! | per segment  | `mid`, `half` (`f64`), then the `N` coefficients         |
+//! | 4            | CRC-32 (IEEE) of every preceding byte                    |
+//!
+//! A unit is its `qtty` symbol and its ratio to the canonical unit of its
+//! dimension; plain numbers have an empty symbol and ratio 1. Composite
+//! units (`Per`, `Prod`) have no symbol and are told apart by ratio only.
+//!
+//! # Example
+//!
+//! ```
+//! use cheby::ChebySegmentTable;
+//!
+//! let table = ChebySegmentTable::<f64, 10>::from_fn(f64::sin, 0.0, 4.0, 1.0);
+//! let mut file = Vec::new();
+//! table.write_to(&mut file)?;
+//! let loaded = ChebySegmentTable::<f64, 10>::read_from(file.as_slice())?;
+//! assert_eq!(loaded, table);
+//! # Ok::<(), cheby::storage::StorageError>(())
+//! ```
+
+use crate::axis::ChebyAxis;
+use crate::grid::{GridError, UniformGrid};
+use crate::scalar::{ChebyScalar, Vector};
+use crate::segment::{ChebySegment, ChebySegmentTable};
+use qtty::{Quantity, Unit};
+use std::fmt;
+use std::io::{self, Read, Write};
+
+/// First bytes of every table file.
+pub const MAGIC: [u8; 8] = *b"CHEBYTAB";
+/// The format version written by this crate.
+pub const VERSION: u16 = 1;
+
+// ─────────────────────────────────────────────────────────────────────────
+// Units and stored scalars
+// ─────────────────────────────────────────────────────────────────────────
+
+/// The unit of stored values or of the axis: a `qtty` symbol and the
+/// ratio to the canonical unit of its dimension.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnitTag {
+    /// Unit symbol, empty for plain numbers and composite units.
+    pub symbol: String,
+    /// Conversion ratio to the canonical unit, 1 for plain numbers.
+    pub ratio: f64,
+}
+
+impl UnitTag {
+    /// The tag of plain numbers.
+    #[inline]
+    pub fn none() -> Self {
+        Self {
+            symbol: String::new(),
+            ratio: 1.0,
+        }
+    }
+
+    /// The tag of `qtty` unit `U`.
+    #[inline]
+    pub fn of<U: Unit>() -> Self {
+        Self {
+            symbol: U::SYMBOL.to_string(),
+            ratio: U::RATIO,
+        }
+    }
+}
+
+impl fmt::Display for UnitTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.symbol.is_empty(), self.ratio == 1.0) {
+            (true, true) => f.write_str("no unit"),
+            (true, false) => write!(f, "unit of ratio {}", self.ratio),
+            _ => write!(f, "{} (ratio {})", self.symbol, self.ratio),
+        }
+    }
+}
+
+/// A [`ChebyScalar`] that can be written to a table file as a fixed
+/// number of `f64`s or `f32`s.
+pub trait Storable: ChebyScalar {
+    /// Numbers per value.
+    const COMPONENTS: usize;
+    /// Bytes per number: 8 or 4.
+    const WIDTH: usize;
+
+    /// The unit of the value.
+    fn unit() -> UnitTag;
+
+    /// Append the value's `COMPONENTS` numbers, little-endian.
+    fn write_le(&self, out: &mut Vec<u8>);
+
+    /// Pass the value's `COMPONENTS` numbers to `out`, in order.
+    fn to_numbers(&self, out: &mut impl FnMut(f64));
+
+    /// A value from its `COMPONENTS` numbers, taken in order from `next`.
+    fn from_numbers(next: &mut impl FnMut() -> f64) -> Self;
+}
+
+/// An axis type that records its unit in a table file.
+pub trait StorableAxis: ChebyAxis {
+    /// The unit of the axis.
+    fn unit() -> UnitTag;
+}
+
+impl Storable for f64 {
+    const COMPONENTS: usize = 1;
+    const WIDTH: usize = 8;
+
+    #[inline]
+    fn unit() -> UnitTag {
+        UnitTag::none()
+    }
+
+    #[inline]
+    fn write_le(&self, out: &mut Vec<u8>) {
+        out.extend(self.to_le_bytes());
+    }
+
+    #[inline]
+    fn to_numbers(&self, out: &mut impl FnMut(f64)) {
+        out(*self);
+    }
+
+    #[inline]
+    fn from_numbers(next: &mut impl FnMut() -> f64) -> Self {
+        next()
+    }
+}
+
+impl Storable for f32 {
+    const COMPONENTS: usize = 1;
+    const WIDTH: usize = 4;
+
+    #[inline]
+    fn unit() -> UnitTag {
+        UnitTag::none()
+    }
+
+    #[inline]
+    fn write_le(&self, out: &mut Vec<u8>) {
+        out.extend(self.to_le_bytes());
+    }
+
+    #[inline]
+    fn to_numbers(&self, out: &mut impl FnMut(f64)) {
+        out(f64::from(*self));
+    }
+
+    #[inline]
+    fn from_numbers(next: &mut impl FnMut() -> f64) -> Self {
+        next() as f32
+    }
+}
+
+impl<U: Unit> Storable for Quantity<U> {
+    const COMPONENTS: usize = 1;
+    const WIDTH: usize = 8;
+
+    #[inline]
+    fn unit() -> UnitTag {
+        UnitTag::of::<U>()
+    }
+
+    #[inline]
+    fn write_le(&self, out: &mut Vec<u8>) {
+        self.value().write_le(out);
+    }
+
+    #[inline]
+    fn to_numbers(&self, out: &mut impl FnMut(f64)) {
+        out(self.value());
+    }
+
+    #[inline]
+    fn from_numbers(next: &mut impl FnMut() -> f64) -> Self {
+        Self::new(next())
+    }
+}
+
+impl<T: Storable, const D: usize> Storable for Vector<T, D> {
+    const COMPONENTS: usize = D * T::COMPONENTS;
+    const WIDTH: usize = T::WIDTH;
+
+    #[inline]
+    fn unit() -> UnitTag {
+        T::unit()
+    }
+
+    #[inline]
+    fn write_le(&self, out: &mut Vec<u8>) {
+        for x in &self.0 {
+            x.write_le(out);
+        }
+    }
+
+    #[inline]
+    fn to_numbers(&self, out: &mut impl FnMut(f64)) {
+        for x in &self.0 {
+            x.to_numbers(out);
+        }
+    }
+
+    #[inline]
+    fn from_numbers(next: &mut impl FnMut() -> f64) -> Self {
+        Vector(std::array::from_fn(|_| T::from_numbers(next)))
+    }
+}
+
+impl StorableAxis for f64 {
+    #[inline]
+    fn unit() -> UnitTag {
+        UnitTag::none()
+    }
+}
+
+impl<U: Unit> StorableAxis for Quantity<U> {
+    #[inline]
+    fn unit() -> UnitTag {
+        UnitTag::of::<U>()
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// Errors
+// ─────────────────────────────────────────────────────────────────────────
+
+/// Why a table file could not be written or read.
+#[derive(Debug)]
+pub enum StorageError {
+    /// The underlying reader or writer failed.
+    Io(io::Error),
+    /// The file ends before the table does.
+    Truncated,
+    /// The file does not start with [`MAGIC`].
+    NotATable,
+    /// The file was written in a format version this crate cannot read.
+    UnsupportedVersion(u16),
+    /// The checksum does not match the contents.
+    Corrupt {
+        /// CRC-32 stored in the file.
+        stored: u32,
+        /// CRC-32 of the bytes read.
+        computed: u32,
+    },
+    /// The file holds a table of another shape than the one requested.
+    Shape {
+        /// `"coefficients"`, `"components"` or `"bytes per number"`.
+        what: &'static str,
+        /// What the file holds.
+        stored: u64,
+        /// What the requested type needs.
+        requested: u64,
+    },
+    /// The file holds values or an axis in another unit.
+    Unit {
+        /// `"value"` or `"axis"`.
+        what: &'static str,
+        /// The unit in the file.
+        stored: UnitTag,
+        /// The unit of the requested type.
+        requested: UnitTag,
+    },
+    /// The contents are well formed but not a valid table, e.g. a segment
+    /// with a non-positive half-width.
+    Invalid(String),
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "table I/O failed: {e}"),
+            Self::Truncated => f.write_str("table file is truncated"),
+            Self::NotATable => f.write_str("not a cheby table file"),
+            Self::UnsupportedVersion(v) => {
+                write!(f, "table format version {v} is not supported")
+            }
+            Self::Corrupt { stored, computed } => write!(
+                f,
+                "table checksum mismatch: stored {stored:#010x}, computed {computed:#010x}"
+            ),
+            Self::Shape {
+                what,
+                stored,
+                requested,
+            } => write!(f, "table has {stored} {what}, requested {requested}"),
+            Self::Unit {
+                what,
+                stored,
+                requested,
+            } => write!(f, "table {what} is in {stored}, requested {requested}"),
+            Self::Invalid(message) => write!(f, "invalid table: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<GridError> for StorageError {
+    fn from(e: GridError) -> Self {
+        Self::Invalid(e.to_string())
+    }
+}
+
+impl From<io::Error> for StorageError {
+    fn from(e: io::Error) -> Self {
+        if e.kind() == io::ErrorKind::UnexpectedEof {
+            Self::Truncated
+        } else {
+            Self::Io(e)
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// CRC-32
+// ─────────────────────────────────────────────────────────────────────────
+
+/// Table for the reflected IEEE polynomial.
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut c = i as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 {
+                0xEDB8_8320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[i] = c;
+        i += 1;
+    }
+    table
+};
+
+/// A running CRC-32 (IEEE, as in zlib and PNG).
+#[derive(Debug, Clone, Copy)]
+struct Crc32(u32);
+
+impl Crc32 {
+    fn new() -> Self {
+        Self(!0)
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = CRC_TABLE[((self.0 ^ b as u32) & 0xFF) as usize] ^ (self.0 >> 8);
+        }
+    }
+
+    fn finish(self) -> u32 {
+        !self.0
+    }
+}
+
+/// A reader that checksums everything read through it.
+pub(crate) struct Checked<R> {
+    inner: R,
+    crc: Crc32,
+}
+
+impl<R: Read> Checked<R> {
+    pub(crate) fn new(inner: R) -> Self {
+        Self {
+            inner,
+            crc: Crc32::new(),
+        }
+    }
+
+    /// Read the trailing CRC-32 and compare it with the bytes read.
+    pub(crate) fn verify(&mut self) -> Result<(), StorageError> {
+        let computed = self.crc.finish();
+        let stored = u32::from_le_bytes(self.array()?);
+        if stored != computed {
+            return Err(StorageError::Corrupt { stored, computed });
+        }
+        Ok(())
+    }
+
+    fn fill(&mut self, buf: &mut [u8]) -> Result<(), StorageError> {
+        self.inner.read_exact(buf)?;
+        self.crc.update(buf);
+        Ok(())
+    }
+
+    fn array<const K: usize>(&mut self) -> Result<[u8; K], StorageError> {
+        let mut buf = [0; K];
+        self.fill(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn f64(&mut self) -> Result<f64, StorageError> {
+        Ok(f64::from_le_bytes(self.array()?))
+    }
+
+    fn unit(&mut self) -> Result<UnitTag, StorageError> {
+        let len = u16::from_le_bytes(self.array()?) as usize;
+        let mut symbol = vec![0; len];
+        self.fill(&mut symbol)?;
+        let symbol = String::from_utf8(symbol)
+            .map_err(|_| StorageError::Invalid("unit symbol is not UTF-8".into()))?;
+        Ok(UnitTag {
+            symbol,
+            ratio: self.f64()?,
+        })
+    }
+}
+
+/// Number `k` of `bytes`, stored little-endian in `width` bytes.
+#[inline]
+pub(crate) fn number(bytes: &[u8], width: usize, k: usize) -> f64 {
+    let at = k * width;
+    if width == 4 {
+        f32::from_le_bytes(bytes[at..at + 4].try_into().unwrap()) as f64
+    } else {
+        f64::from_le_bytes(bytes[at..at + 8].try_into().unwrap())
+    }
+}
+
+/// Bytes per stored segment: `mid`, `half` and `N` values.
+#[inline]
+pub(crate) fn segment_bytes<T: Storable, const N: usize>() -> usize {
+    16 + N * T::COMPONENTS * T::WIDTH
+}
+
+/// The domain of a table file after its header.
+pub(crate) struct Header {
+    pub(crate) start: f64,
+    pub(crate) segment_len: f64,
+    pub(crate) count: u64,
+}
+
+/// Read the header and check it against `T`, `N` and `X`.
+pub(crate) fn read_header<T: Storable, const N: usize, X: StorableAxis>(
+    r: &mut Checked<impl Read>,
+) -> Result<Header, StorageError> {
+    if r.array::<8>()? != MAGIC {
+        return Err(StorageError::NotATable);
+    }
+    let version = u16::from_le_bytes(r.array()?);
+    if version != VERSION {
+        return Err(StorageError::UnsupportedVersion(version));
+    }
+    let [width, _] = r.array()?;
+    let ncoeff = u32::from_le_bytes(r.array()?);
+    let components = u32::from_le_bytes(r.array()?);
+    let count = u64::from_le_bytes(r.array()?);
+    for (what, stored, requested) in [
+        ("coefficients", ncoeff as usize, N),
+        ("components", components as usize, T::COMPONENTS),
+        ("bytes per number", width as usize, T::WIDTH),
+    ] {
+        if stored != requested {
+            return Err(StorageError::Shape {
+                what,
+                stored: stored as u64,
+                requested: requested as u64,
+            });
+        }
+    }
+    for (what, requested) in [("value", T::unit()), ("axis", X::unit())] {
+        let stored = r.unit()?;
+        if stored != requested {
+            return Err(StorageError::Unit {
+                what,
+                stored,
+                requested,
+            });
+        }
+    }
+    Ok(Header {
+        start: r.f64()?,
+        segment_len: r.f64()?,
+        count,
+    })
+}
+
+impl<'a> Checked<&'a [u8]> {
+    /// The next `len` bytes, checksummed, borrowed from the input.
+    pub(crate) fn take(&mut self, len: usize) -> Result<&'a [u8], StorageError> {
+        if len > self.inner.len() {
+            return Err(StorageError::Truncated);
+        }
+        let (head, rest) = self.inner.split_at(len);
+        self.crc.update(head);
+        self.inner = rest;
+        Ok(head)
+    }
+
+    /// Bytes not read yet.
+    pub(crate) fn remaining(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+fn put_unit(out: &mut Vec<u8>, unit: &UnitTag) {
+    out.extend((unit.symbol.len() as u16).to_le_bytes());
+    out.extend(unit.symbol.as_bytes());
+    out.extend(unit.ratio.to_le_bytes());
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// Table I/O
+// ─────────────────────────────────────────────────────────────────────────
+
+impl<T: Storable, const N: usize, X: StorableAxis> ChebySegmentTable<T, N, X> {
+    /// Write the table in the [format](crate::storage) of this module.
+    pub fn write_to(&self, mut w: impl Write) -> Result<(), StorageError> {
+        let value_bytes = T::COMPONENTS * T::WIDTH;
+        let mut out = Vec::with_capacity(96 + self.len() * (16 + N * value_bytes));
+        out.extend(MAGIC);
+        out.extend(VERSION.to_le_bytes());
+        out.extend([T::WIDTH as u8, 0]);
+        out.extend((N as u32).to_le_bytes());
+        out.extend((T::COMPONENTS as u32).to_le_bytes());
+        out.extend((self.len() as u64).to_le_bytes());
+        put_unit(&mut out, &T::unit());
+        put_unit(&mut out, &X::unit());
+        out.extend(self.start().value().to_le_bytes());
+        out.extend(self.segment_len().value().to_le_bytes());
+        for segment in self.segments() {
+            out.extend(segment.mid.value().to_le_bytes());
+            out.extend(segment.half.value().to_le_bytes());
+            for c in &segment.coeffs {
+                c.write_le(&mut out);
+            }
+        }
+        let mut crc = Crc32::new();
+        crc.update(&out);
+        out.extend(crc.finish().to_le_bytes());
+        w.write_all(&out)?;
+        Ok(())
+    }
+
+    /// Read a table written by [`write_to`](Self::write_to).
+    ///
+    /// The degree, value shape, precision and units in the file must match
+    /// `T`, `N` and `X`; the checksum must match the contents; and the
+    /// domain must be finite with positive segment length and
+    /// half-widths.
+    pub fn read_from(r: impl Read) -> Result<Self, StorageError> {
+        let mut r = Checked::new(r);
+        let header = read_header::<T, N, X>(&mut r)?;
+
+        // Grow as segments arrive, so a corrupt count cannot allocate
+        // more than the file holds.
+        let mut segments = Vec::new();
+        let mut bytes = vec![0; segment_bytes::<T, N>()];
+        for _ in 0..header.count {
+            r.fill(&mut bytes)?;
+            let mut k = 0;
+            let mut next = || {
+                k += 1;
+                number(&bytes[16..], T::WIDTH, k - 1)
+            };
+            let coeffs: [T; N] = std::array::from_fn(|_| T::from_numbers(&mut next));
+            segments.push((number(&bytes, 8, 0), number(&bytes, 8, 1), coeffs));
+        }
+        r.verify()?;
+
+        UniformGrid::new(header.start, header.segment_len)
+            .check_slots(segments.iter().map(|&(mid, half, _)| (mid, half)))?;
+        let segments = segments
+            .into_iter()
+            .map(|(mid, half, coeffs)| {
+                ChebySegment::new(coeffs, X::from_value(mid), X::from_value(half))
+            })
+            .collect();
+        Ok(Self::from_segments(
+            segments,
+            X::from_value(header.start),
+            X::from_value(header.segment_len),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32_check_value() {
+        let mut crc = Crc32::new();
+        crc.update(b"123456789");
+        assert_eq!(crc.finish(), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_header_layout() {
+        let table = ChebySegmentTable::<f32, 3>::from_fn(|t| t as f32, 0.0, 2.0, 1.0);
+        let mut out = Vec::new();
+        table.write_to(&mut out).unwrap();
+        assert_eq!(&out[..8], b"CHEBYTAB");
+        assert_eq!(out[8..12], [1, 0, 4, 0]);
+        assert_eq!(out[12..16], 3_u32.to_le_bytes());
+        assert_eq!(out[16..20], 1_u32.to_le_bytes());
+        assert_eq!(out[20..28], 2_u64.to_le_bytes());
+        // Two empty units, domain, two segments of 16 + 3 × 4 bytes, CRC.
+        assert_eq!(out.len(), 28 + 2 * 10 + 16 + 2 * 28 + 4);
+    }
+
+    #[test]
+    fn test_vector_round_trip() {
+        let table = ChebySegmentTable::<Vector<f32, 2>, 4>::from_fn(
+            |t| Vector([t as f32, -(t as f32)]),
+            -1.0,
+            1.0,
+            0.5,
+        );
+        let mut out = Vec::new();
+        table.write_to(&mut out).unwrap();
+        let read = ChebySegmentTable::<Vector<f32, 2>, 4>::read_from(&out[..]).unwrap();
+        assert_eq!(read, table);
+        let err = ChebySegmentTable::<Vector<f32, 3>, 4>::read_from(&out[..]).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                StorageError::Shape {
+                    what: "components",
+                    stored: 2,
+                    requested: 3
+                }
+            ),
+            "{err}"
+        );
+    }
+}
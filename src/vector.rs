@@ -0,0 +1,402 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! Vector-valued Chebyshev segments sharing one domain.
+//!
+//! Ephemerides store several components (x, y, z, or a 6-component state)
+//! per time interval, all with the same domain and degree. Keeping one
+//! [`ChebySegmentTable`](crate::ChebySegmentTable) per component repeats
+//! the lookup and the `t → τ` normalisation for every component;
+//! [`ChebyVecSegment`] and [`ChebyVecSegmentTable`] do both once and run a
+//! single interleaved Clenshaw loop over all `D` components.
+//!
+//! Coefficients are stored component-major (`coeffs[d][k]`), the layout
+//! used by JPL DE records.
+
+use crate::cursor::Cursor;
+use crate::fit;
+use crate::grid::UniformGrid;
+use crate::nodes;
+use crate::scalar::{field, ChebyScalar};
+use crate::segment::ChebySegment;
+use std::fmt;
+
+// ─────────────────────────────────────────────────────────────────────────
+// Interleaved Clenshaw evaluation
+// ─────────────────────────────────────────────────────────────────────────
+
+/// Evaluate `D` series of `N` coefficients at `tau` in one pass.
+#[inline]
+fn evaluate_vec<T: ChebyScalar, const N: usize, const D: usize>(
+    coeffs: &[[T; N]; D],
+    tau: T::Field,
+) -> [T; D] {
+    if N == 0 {
+        return [T::zero(); D];
+    }
+    let two_tau = tau + tau;
+    let mut b_kp1 = [T::zero(); D];
+    let mut b_kp2 = [T::zero(); D];
+    for k in (1..N).rev() {
+        for d in 0..D {
+            let b_k = b_kp1[d] * two_tau - b_kp2[d] + coeffs[d][k];
+            b_kp2[d] = b_kp1[d];
+            b_kp1[d] = b_k;
+        }
+    }
+    std::array::from_fn(|d| coeffs[d][0] + b_kp1[d] * tau - b_kp2[d])
+}
+
+/// Evaluate `D` series and their `τ`-derivatives at `tau` in one pass.
+#[inline]
+fn evaluate_vec_both<T: ChebyScalar, const N: usize, const D: usize>(
+    coeffs: &[[T; N]; D],
+    tau: T::Field,
+) -> ([T; D], [T; D]) {
+    if N == 0 {
+        return ([T::zero(); D], [T::zero(); D]);
+    }
+    let two_tau = tau + tau;
+    let mut b_kp1 = [T::zero(); D];
+    let mut b_kp2 = [T::zero(); D];
+    let mut db_kp1 = [T::zero(); D];
+    let mut db_kp2 = [T::zero(); D];
+    for k in (1..N).rev() {
+        for d in 0..D {
+            let b_k = b_kp1[d] * two_tau - b_kp2[d] + coeffs[d][k];
+            let db_k = db_kp1[d] * two_tau - db_kp2[d] + b_kp1[d] + b_kp1[d];
+            b_kp2[d] = b_kp1[d];
+            b_kp1[d] = b_k;
+            db_kp2[d] = db_kp1[d];
+            db_kp1[d] = db_k;
+        }
+    }
+    let value = std::array::from_fn(|d| coeffs[d][0] + b_kp1[d] * tau - b_kp2[d]);
+    let deriv = std::array::from_fn(|d| b_kp1[d] + db_kp1[d] * tau - db_kp2[d]);
+    (value, deriv)
+}
+
+// ─────────────────────────────────────────────────────────────────────────
+// ChebyVecSegment — single segment
+// ─────────────────────────────────────────────────────────────────────────
+
+/// A Chebyshev segment with `D` components of `N` coefficients each,
+/// sharing the domain `[mid - half, mid + half]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChebyVecSegment<T: ChebyScalar, const N: usize, const D: usize> {
+    /// Chebyshev coefficients, component-major: `coeffs[d][0..N]`.
+    pub coeffs: [[T; N]; D],
+    /// Midpoint of the segment domain.
+    pub mid: f64,
+    /// Half-width of the segment domain.
+    pub half: f64,
+}
+
+impl<T: ChebyScalar, const N: usize, const D: usize> ChebyVecSegment<T, N, D> {
+    /// Create a segment from pre-computed coefficients and domain.
+    #[inline]
+    pub fn new(coeffs: [[T; N]; D], mid: f64, half: f64) -> Self {
+        Self { coeffs, mid, half }
+    }
+
+    /// Sample `f` once per Chebyshev node on `[start, end]` and fit every
+    /// component.
+    pub fn from_fn(f: impl Fn(f64) -> [T; D], start: f64, end: f64) -> Self {
+        let samples: [[T; D]; N] = nodes::nodes_mapped::<N>(start, end).map(&f);
+        let coeffs = std::array::from_fn(|d| fit::fit_coeffs(&samples.map(|s| s[d])));
+        Self::new(coeffs, 0.5 * (start + end), 0.5 * (end - start))
+    }
+
+    /// Normalise `t` to `τ ∈ [-1, 1]` within this segment.
+    #[inline]
+    pub fn normalise(&self, t: f64) -> f64 {
+        (t - self.mid) / self.half
+    }
+
+    /// Evaluate all components at physical time `t`.
+    #[inline]
+    pub fn eval(&self, t: f64) -> [T; D] {
+        evaluate_vec(&self.coeffs, field::<T>(self.normalise(t)))
+    }
+
+    /// Evaluate the derivative `df/dt` of all components at `t`.
+    #[inline]
+    pub fn eval_derivative(&self, t: f64) -> [T; D] {
+        self.eval_both(t).1
+    }
+
+    /// Evaluate values and derivatives `(f(t), df/dt)` in one pass.
+    #[inline]
+    pub fn eval_both(&self, t: f64) -> ([T; D], [T; D]) {
+        let (v, d) = evaluate_vec_both(&self.coeffs, field::<T>(self.normalise(t)));
+        let half = field::<T>(self.half);
+        (v, d.map(|x| x / half))
+    }
+
+    /// Component `d` as a scalar segment over the same domain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `d >= D`.
+    #[inline]
+    pub fn component(&self, d: usize) -> ChebySegment<T, N> {
+        ChebySegment::new(self.coeffs[d], self.mid, self.half)
+    }
+}
+
+impl<T: ChebyScalar, const N: usize, const D: usize> TryFrom<[ChebySegment<T, N>; D]>
+    for ChebyVecSegment<T, N, D>
+{
+    type Error = ComponentError;
+
+    /// Combine `D` scalar segments, which must all have exactly the same
+    /// `mid` and `half`.
+    fn try_from(components: [ChebySegment<T, N>; D]) -> Result<Self, ComponentError> {
+        let first = components.first().ok_or(ComponentError::Empty)?;
+        let (mid, half) = (first.mid, first.half);
+        if let Some(index) = components
+            .iter()
+            .position(|s| (s.mid, s.half) != (mid, half))
+        {
+            return Err(ComponentError::DomainMismatch { index });
+        }
+        Ok(Self::new(components.map(|s| s.coeffs), mid, half))
+    }
+}
+
+/// Why scalar segments cannot be combined into a [`ChebyVecSegment`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComponentError {
+    /// No components were given (`D = 0`).
+    Empty,
+    /// Component `index` has a different domain from component 0.
+    DomainMismatch {
+        /// Index of the offending component.
+        index: usize,
+    },
+}
+
+impl fmt::Display for ComponentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "no components given"),
+            Self::DomainMismatch { index } => {
+                write!(
+                    f,
+                    "component {index} has a different domain from component 0"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ComponentError {}
+
+// ─────────────────────────────────────────────────────────────────────────
+// ChebyVecSegmentTable — uniform piecewise segments
+// ─────────────────────────────────────────────────────────────────────────
+
+/// A table of uniform-duration vector-valued segments.
+///
+/// The vector counterpart of [`ChebySegmentTable`](crate::ChebySegmentTable):
+/// one O(1) lookup and one normalisation per query, whatever `D` is.
+///
+/// # Example
+///
+/// ```
+/// use cheby::ChebyVecSegmentTable;
+///
+/// let orbit = |t: f64| [t.cos(), t.sin(), 0.1 * t];
+/// let table: ChebyVecSegmentTable<f64, 14, 3> =
+///     ChebyVecSegmentTable::from_fn(orbit, 0.0, 10.0, 1.0);
+/// let ([x, y, z], [vx, _, _]) = table.eval_both(2.5).unwrap();
+/// assert!((x - 2.5_f64.cos()).abs() < 1e-12);
+/// assert!((y - 2.5_f64.sin()).abs() < 1e-12);
+/// assert!((z - 0.25).abs() < 1e-12);
+/// assert!((vx + 2.5_f64.sin()).abs() < 1e-10);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChebyVecSegmentTable<T: ChebyScalar, const N: usize, const D: usize> {
+    /// Start and segment length.
+    grid: UniformGrid,
+    /// Segments, in chronological order.
+    segments: Vec<ChebyVecSegment<T, N, D>>,
+}
+
+impl<T: ChebyScalar, const N: usize, const D: usize> ChebyVecSegmentTable<T, N, D> {
+    /// Build a table by sampling `f` at Chebyshev nodes within each
+    /// segment of `segment_len` covering `[start, end]`.
+    pub fn from_fn(f: impl Fn(f64) -> [T; D], start: f64, end: f64, segment_len: f64) -> Self {
+        let grid = UniformGrid::new(start, segment_len);
+        let segments = grid
+            .cover(end)
+            .map(|(lo, hi)| ChebyVecSegment::from_fn(&f, lo, hi))
+            .collect();
+
+        Self { grid, segments }
+    }
+
+    /// Build from pre-computed segments.
+    pub fn from_segments(
+        segments: Vec<ChebyVecSegment<T, N, D>>,
+        start: f64,
+        segment_len: f64,
+    ) -> Self {
+        Self {
+            grid: UniformGrid::new(start, segment_len),
+            segments,
+        }
+    }
+
+    /// Number of segments in the table.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Whether the table is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Start of the covered domain.
+    #[inline]
+    pub fn start(&self) -> f64 {
+        self.grid.start
+    }
+
+    /// End of the covered domain.
+    #[inline]
+    pub fn end(&self) -> f64 {
+        self.grid.end(self.segments.len())
+    }
+
+    /// Duration of each segment.
+    #[inline]
+    pub fn segment_len(&self) -> f64 {
+        self.grid.segment_len
+    }
+
+    /// Index of the segment containing `t`, or `None` if `t` is outside
+    /// the table range.
+    #[inline]
+    pub fn segment_index(&self, t: f64) -> Option<usize> {
+        self.grid.index(t, self.segments.len())
+    }
+
+    /// Bounds `[lo, hi]` of segment `i`.
+    #[inline]
+    pub(crate) fn bounds(&self, i: usize) -> (f64, f64) {
+        self.grid.bounds(i)
+    }
+
+    /// Look up the segment containing `t`, returning `None` if `t` is
+    /// outside the table range.
+    #[inline]
+    pub fn get_segment(&self, t: f64) -> Option<&ChebyVecSegment<T, N, D>> {
+        self.segment_index(t).map(|idx| &self.segments[idx])
+    }
+
+    /// Evaluate at `t`, returning `None` if outside the table range.
+    #[inline]
+    pub fn eval(&self, t: f64) -> Option<[T; D]> {
+        self.get_segment(t).map(|s| s.eval(t))
+    }
+
+    /// Evaluate derivative at `t`, returning `None` if outside range.
+    #[inline]
+    pub fn eval_derivative(&self, t: f64) -> Option<[T; D]> {
+        self.get_segment(t).map(|s| s.eval_derivative(t))
+    }
+
+    /// Evaluate value and derivative at `t`, returning `None` if outside range.
+    #[inline]
+    pub fn eval_both(&self, t: f64) -> Option<([T; D], [T; D])> {
+        self.get_segment(t).map(|s| s.eval_both(t))
+    }
+
+    /// A [`Cursor`] for fast sequential queries.
+    #[inline]
+    pub fn cursor(&self) -> Cursor<'_, Self> {
+        Cursor::new(self)
+    }
+
+    /// All segments, in chronological order.
+    #[inline]
+    pub fn segments(&self) -> &[ChebyVecSegment<T, N, D>] {
+        &self.segments
+    }
+
+    /// Consume the table, returning its segments.
+    #[inline]
+    pub fn into_segments(self) -> Vec<ChebyVecSegment<T, N, D>> {
+        self.segments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::segment::ChebySegmentTable;
+
+    fn state(t: f64) -> [f64; 6] {
+        let (s, c) = t.sin_cos();
+        [c, s, 0.5 * t, -s, c, 0.5]
+    }
+
+    #[test]
+    fn test_matches_component_tables() {
+        let vec_table: ChebyVecSegmentTable<f64, 13, 6> =
+            ChebyVecSegmentTable::from_fn(state, 0.0, 8.0, 2.0);
+        let scalar: [ChebySegmentTable<f64, 13>; 6] = std::array::from_fn(|d| {
+            ChebySegmentTable::from_fn(move |t| state(t)[d], 0.0, 8.0, 2.0)
+        });
+        assert_eq!(vec_table.len(), 4);
+        assert_eq!(vec_table.end(), 8.0);
+
+        for &t in &[0.0, 1.99, 2.0, 5.3, 7.999] {
+            let (v, dv) = vec_table.eval_both(t).unwrap();
+            assert_eq!(v, vec_table.eval(t).unwrap());
+            assert_eq!(dv, vec_table.eval_derivative(t).unwrap());
+            for d in 0..6 {
+                let (s, ds) = scalar[d].eval_both(t).unwrap();
+                assert!((v[d] - s).abs() < 1e-15, "t={t} d={d}");
+                assert!((dv[d] - ds).abs() < 1e-13, "t={t} d={d}");
+            }
+        }
+        assert!(vec_table.eval(8.0).is_none());
+        assert!(vec_table.eval(f64::NAN).is_none());
+    }
+
+    #[test]
+    fn test_components_roundtrip() {
+        let seg: ChebyVecSegment<f64, 10, 3> =
+            ChebyVecSegment::from_fn(|t| [t.exp(), t * t, 1.0], -1.0, 3.0);
+        let parts = [seg.component(0), seg.component(1), seg.component(2)];
+        assert_eq!(parts[1].eval(2.0), seg.eval(2.0)[1]);
+        assert_eq!(ChebyVecSegment::try_from(parts).as_ref(), Ok(&seg));
+
+        let mut mismatched = [seg.component(0), seg.component(1), seg.component(2)];
+        mismatched[2].mid += 1e-9;
+        assert_eq!(
+            ChebyVecSegment::try_from(mismatched),
+            Err(ComponentError::DomainMismatch { index: 2 })
+        );
+        let none: [ChebySegment<f64, 10>; 0] = [];
+        let err = ChebyVecSegment::try_from(none).unwrap_err();
+        assert_eq!(err, ComponentError::Empty);
+        assert_eq!(err.to_string(), "no components given");
+    }
+
+    #[test]
+    fn test_cursor() {
+        let table: ChebyVecSegmentTable<f64, 9, 2> =
+            ChebyVecSegmentTable::from_fn(|t| [t.sin(), t.cos()], 0.0, 3.0, 0.5);
+        let mut cursor = table.cursor();
+        for i in 0..300 {
+            let t = i as f64 * 0.01;
+            assert_eq!(cursor.eval_both(t), table.eval_both(t));
+        }
+    }
+}
@@ -0,0 +1,350 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+// Copyright (C) 2026 Vallés Puig, Ramon
+
+//! Zero-copy views of segment tables over borrowed buffers.
+//!
+//! A [`ChebySegmentTableView`] evaluates a uniform table straight from
+//! memory it does not own — a memory-mapped or `include_bytes!` table
+//! file, or a slice of `f64`s — without copying the coefficients into
+//! segments. The buffer is validated once, on construction; each lookup
+//! then builds only the `[T; N]` coefficients of the segment it needs, on
+//! the stack.
+//!
+//! Two layouts are accepted:
+//!
+//! - [`from_bytes`](ChebySegmentTableView::from_bytes): a complete table
+//!   file in the [`storage`](crate::storage) format, at any alignment.
+//! - [`from_f64s`](ChebySegmentTableView::from_f64s): `mid`, `half` and
+//!   the `N` values of each segment in turn, every number an `f64`, with
+//!   the table start and segment length given separately.
+//!
+//! # Example
+//!
+//! ```
+//! use cheby::{ChebySegmentTable, ChebySegmentTableView};
+//!
+//! let table = ChebySegmentTable::<f64, 10>::from_fn(f64::cos, 0.0, 4.0, 1.0);
+//! let mut file = Vec::new();
+//! table.write_to(&mut file)?;
+//!
+//! let view = ChebySegmentTableView::<f64, 10>::from_bytes(&file)?;
+//! assert_eq!(view.eval(2.5), table.eval(2.5));
+//! # Ok::<(), cheby::storage::StorageError>(())
+//! ```
+
+use crate::axis::{ChebyCalculus, IntoAxis};
+use crate::grid::UniformGrid;
+use crate::scalar::ChebyScalar;
+use crate::segment::{ChebySegment, ChebySegmentTable};
+use crate::storage::{
+    number, read_header, segment_bytes, Checked, Storable, StorableAxis, StorageError,
+};
+use std::marker::PhantomData;
+
+/// Where the numbers of a view live.
+#[derive(Debug, Clone, Copy)]
+enum Numbers<'a> {
+    /// The segment block of a table file: per segment `mid` and `half` as
+    /// little-endian `f64`s, then the values in `T::WIDTH`-byte numbers.
+    File(&'a [u8]),
+    /// Per segment `mid`, `half` and the values, all `f64`.
+    F64(&'a [f64]),
+}
+
+/// A uniform segment table evaluated in place from a borrowed buffer.
+///
+/// It has the lookup and evaluation methods of
+/// [`ChebySegmentTable`] with the same results, bit for bit;
+/// [`to_table`](Self::to_table) copies it into one.
+#[derive(Debug, Clone, Copy)]
+pub struct ChebySegmentTableView<'a, T: Storable, const N: usize, X: StorableAxis = f64> {
+    numbers: Numbers<'a>,
+    /// Start and segment length, in the unit of `X`.
+    grid: UniformGrid,
+    /// Number of segments.
+    len: usize,
+    _marker: PhantomData<fn() -> (T, X)>,
+}
+
+impl<'a, T: Storable, const N: usize, X: StorableAxis> ChebySegmentTableView<'a, T, N, X> {
+    /// A view of a complete table file written by
+    /// [`ChebySegmentTable::write_to`].
+    ///
+    /// The header must match `T`, `N` and `X`, the checksum the contents,
+    /// and the buffer must end with the checksum. Any alignment is
+    /// accepted.
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, StorageError> {
+        let mut r = Checked::new(bytes);
+        let header = read_header::<T, N, X>(&mut r)?;
+        let block = usize::try_from(header.count)
+            .ok()
+            .and_then(|count| count.checked_mul(segment_bytes::<T, N>()))
+            .ok_or(StorageError::Truncated)?;
+        let block = r.take(block)?;
+        r.verify()?;
+        if r.remaining() != 0 {
+            return Err(StorageError::Invalid(format!(
+                "{} bytes after the checksum",
+                r.remaining()
+            )));
+        }
+        Self::validated(
+            Numbers::File(block),
+            header.start,
+            header.segment_len,
+            header.count as usize,
+        )
+    }
+
+    /// A view of `data`, holding `mid`, `half` and the `N` values of each
+    /// segment in turn, as `f64`s.
+    ///
+    /// `T` must be stored as `f64` numbers (not `f32`), `data` must hold a
+    /// whole number of segments, and segment `i` must cover
+    /// `[start + i·segment_len, start + (i+1)·segment_len]`.
+    pub fn from_f64s(data: &'a [f64], start: X, segment_len: X) -> Result<Self, StorageError> {
+        if T::WIDTH != 8 {
+            return Err(StorageError::Shape {
+                what: "bytes per number",
+                stored: 8,
+                requested: T::WIDTH as u64,
+            });
+        }
+        let stride = 2 + N * T::COMPONENTS;
+        if !data.len().is_multiple_of(stride) {
+            return Err(StorageError::Invalid(format!(
+                "{} numbers do not make whole segments of {stride}",
+                data.len()
+            )));
+        }
+        Self::validated(
+            Numbers::F64(data),
+            start.value(),
+            segment_len.value(),
+            data.len() / stride,
+        )
+    }
+
+    /// A view of `data` in the layout of [`from_f64s`](Self::from_f64s),
+    /// without validation, for use in `const` and `static` items.
+    ///
+    /// `start` and `segment_len` are in the unit of `X`. The caller
+    /// guarantees what `from_f64s` would check; otherwise lookups give
+    /// meaningless results or panic. The [`codegen`](crate::codegen)
+    /// module emits calls to it for data it has validated.
+    pub const fn from_f64s_unchecked(data: &'a [f64], start: f64, segment_len: f64) -> Self {
+        Self {
+            numbers: Numbers::F64(data),
+            grid: UniformGrid::new(start, segment_len),
+            len: data.len() / (2 + N * T::COMPONENTS),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Check the domain, and that every segment covers its slot.
+    fn validated(
+        numbers: Numbers<'a>,
+        start: f64,
+        segment_len: f64,
+        len: usize,
+    ) -> Result<Self, StorageError> {
+        let view = Self {
+            numbers,
+            grid: UniformGrid::new(start, segment_len),
+            len,
+            _marker: PhantomData,
+        };
+        view.grid.check_slots((0..len).map(|i| view.domain(i)))?;
+        Ok(view)
+    }
+
+    /// `(mid, half)` of segment `i`.
+    #[inline]
+    fn domain(&self, i: usize) -> (f64, f64) {
+        match self.numbers {
+            Numbers::File(bytes) => {
+                let at = i * segment_bytes::<T, N>();
+                (number(&bytes[at..], 8, 0), number(&bytes[at..], 8, 1))
+            }
+            Numbers::F64(data) => {
+                let at = i * (2 + N * T::COMPONENTS);
+                (data[at], data[at + 1])
+            }
+        }
+    }
+
+    /// Number of segments in the table.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether the table is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Start of the covered domain.
+    #[inline]
+    pub fn start(&self) -> X {
+        X::from_value(self.grid.start)
+    }
+
+    /// End of the covered domain.
+    #[inline]
+    pub fn end(&self) -> X {
+        X::from_value(self.grid.end(self.len))
+    }
+
+    /// Duration of each segment.
+    #[inline]
+    pub fn segment_len(&self) -> X {
+        X::from_value(self.grid.segment_len)
+    }
+
+    /// Index of the segment containing `t`, or `None` if `t` is outside
+    /// the table range.
+    #[inline]
+    pub fn segment_index(&self, t: impl IntoAxis<X>) -> Option<usize> {
+        self.grid.index(t.into_axis().value(), self.len)
+    }
+
+    /// Segment `i`, built from the buffer.
+    ///
+    /// # Panics
+    ///
+    /// If `i >= self.len()`.
+    pub fn segment(&self, i: usize) -> ChebySegment<T, N, X> {
+        assert!(i < self.len, "segment {i} out of range");
+        let (mid, half) = self.domain(i);
+        let mut k = 0;
+        let coeffs = match self.numbers {
+            Numbers::File(bytes) => {
+                let values = &bytes[i * segment_bytes::<T, N>() + 16..];
+                let mut next = || {
+                    k += 1;
+                    number(values, T::WIDTH, k - 1)
+                };
+                std::array::from_fn(|_| T::from_numbers(&mut next))
+            }
+            Numbers::F64(data) => {
+                let values = &data[i * (2 + N * T::COMPONENTS) + 2..];
+                let mut next = || {
+                    k += 1;
+                    values[k - 1]
+                };
+                std::array::from_fn(|_| T::from_numbers(&mut next))
+            }
+        };
+        ChebySegment::new(coeffs, X::from_value(mid), X::from_value(half))
+    }
+
+    /// The segment containing `t`, or `None` if `t` is outside the table
+    /// range.
+    #[inline]
+    pub fn get_segment(&self, t: impl IntoAxis<X>) -> Option<ChebySegment<T, N, X>> {
+        self.segment_index(t).map(|i| self.segment(i))
+    }
+
+    /// Evaluate at `t`, returning `None` if outside the table range.
+    #[inline]
+    pub fn eval(&self, t: impl IntoAxis<X>) -> Option<T> {
+        self.get_segment(t).map(|s| s.eval(t))
+    }
+
+    /// Evaluate derivative at `t`, returning `None` if outside range.
+    #[inline]
+    pub fn eval_derivative(&self, t: impl IntoAxis<X>) -> Option<T::Derivative>
+    where
+        T: ChebyCalculus<X>,
+    {
+        self.get_segment(t).map(|s| s.eval_derivative(t))
+    }
+
+    /// Evaluate value and derivative at `t`, returning `None` if outside range.
+    #[inline]
+    pub fn eval_both(&self, t: impl IntoAxis<X>) -> Option<(T, T::Derivative)>
+    where
+        T: ChebyCalculus<X>,
+    {
+        self.get_segment(t).map(|s| s.eval_both(t))
+    }
+
+    /// Evaluate at `t` in the wider type `W`, returning `None` if outside
+    /// the table range.
+    #[inline]
+    pub fn eval_widened<W: ChebyScalar>(&self, t: impl IntoAxis<X>) -> Option<W>
+    where
+        T: Into<W>,
+    {
+        self.get_segment(t).map(|s| s.eval_widened(t))
+    }
+
+    /// Copy the view into an owned table.
+    pub fn to_table(&self) -> ChebySegmentTable<T, N, X> {
+        ChebySegmentTable::from_segments(
+            (0..self.len).map(|i| self.segment(i)).collect(),
+            self.start(),
+            self.segment_len(),
+        )
+    }
+}
+
+impl<T: Storable, const N: usize, X: StorableAxis> ChebySegmentTable<T, N, X> {
+    /// The table in the layout of [`ChebySegmentTableView::from_f64s`]:
+    /// `mid`, `half` and the values of each segment, as `f64`s.
+    ///
+    /// `f32` values are widened exactly.
+    pub fn to_f64s(&self) -> Vec<f64> {
+        let mut out = Vec::with_capacity(self.len() * (2 + N * T::COMPONENTS));
+        for segment in self.segments() {
+            out.extend([segment.mid.value(), segment.half.value()]);
+            for c in &segment.coeffs {
+                c.to_numbers(&mut |x| out.push(x));
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_views_agree_with_table() {
+        let table = ChebySegmentTable::<f64, 8>::from_fn(|t| t.exp(), 0.0, 3.0, 0.75);
+        let mut file = Vec::new();
+        table.write_to(&mut file).unwrap();
+        let data = table.to_f64s();
+        assert_eq!(data.len(), 4 * 10);
+
+        let from_file = ChebySegmentTableView::<f64, 8>::from_bytes(&file).unwrap();
+        let from_f64s = ChebySegmentTableView::<f64, 8>::from_f64s(&data, 0.0, 0.75).unwrap();
+        for view in [from_file, from_f64s] {
+            assert_eq!((view.len(), view.start(), view.end()), (4, 0.0, 3.0));
+            assert_eq!(view.to_table(), table);
+            for t in [0.0, 0.3, 1.5, 2.99, 3.0, -0.1] {
+                assert_eq!(view.segment_index(t), table.segment_index(t));
+                assert_eq!(view.eval(t), table.eval(t));
+                assert_eq!(view.eval_both(t), table.eval_both(t));
+            }
+        }
+    }
+
+    #[test]
+    fn test_f64s_validation() {
+        let data = [0.5, 0.5, 1.0, 2.0];
+        assert!(ChebySegmentTableView::<f64, 2>::from_f64s(&data, 0.0, 1.0).is_ok());
+        let err = ChebySegmentTableView::<f64, 3>::from_f64s(&data, 0.0, 1.0).unwrap_err();
+        assert!(matches!(err, StorageError::Invalid(_)), "{err}");
+        let err = ChebySegmentTableView::<f64, 2>::from_f64s(&data, 0.0, 0.0).unwrap_err();
+        assert!(matches!(err, StorageError::Invalid(_)), "{err}");
+        let err = ChebySegmentTableView::<f64, 2>::from_f64s(&[0.5, -0.5, 1.0, 2.0], 0.0, 1.0)
+            .unwrap_err();
+        assert!(matches!(err, StorageError::Invalid(_)), "{err}");
+        let err = ChebySegmentTableView::<f32, 2>::from_f64s(&data, 0.0, 1.0).unwrap_err();
+        assert!(matches!(err, StorageError::Shape { .. }), "{err}");
+    }
+}
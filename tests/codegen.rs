@@ -0,0 +1,74 @@
+//! Generated table modules: the checked-in output in `tests/data/codegen`
+//! matches the generator, compiles, and evaluates exactly like the tables
+//! it was generated from.
+//!
+//! Run with `CHEBY_REGENERATE=1` to rewrite the expected output.
+
+use cheby::codegen::RustModule;
+use cheby::{ChebySegmentTable, Vec3};
+use qtty::{Days, Kilometers};
+
+mod generated {
+    include!("data/codegen/tables.rs");
+}
+
+const EXPECTED: &str = "tests/data/codegen/tables.rs";
+
+fn orbit() -> ChebySegmentTable<Vec3<Kilometers>, 6, Days> {
+    ChebySegmentTable::from_fn(
+        |t: Days| {
+            let (s, c) = t.value().sin_cos();
+            Vec3::new([
+                Kilometers::new(7000.0 * c),
+                Kilometers::new(7000.0 * s),
+                Kilometers::new(0.5 * t.value()),
+            ])
+        },
+        Days::new(0.0),
+        Days::new(3.0),
+        Days::new(1.5),
+    )
+}
+
+fn decay() -> ChebySegmentTable<f64, 5> {
+    ChebySegmentTable::from_fn(|t| (-t).exp(), 0.25, 1.0, 0.25)
+}
+
+fn module() -> RustModule {
+    let mut module = RustModule::new("tables")
+        .with_doc("Tables for the codegen tests.")
+        .with_unit::<qtty::Kilometer>("::qtty::Kilometer")
+        .with_unit::<qtty::Day>("::qtty::Day");
+    module.push_table("ORBIT", &orbit()).unwrap();
+    module.push_table("DECAY", &decay()).unwrap();
+    module
+}
+
+#[test]
+fn codegen_output_is_current() {
+    let module = module();
+    assert_eq!(module.len(), 2);
+    if std::env::var_os("CHEBY_REGENERATE").is_some() {
+        module.write(EXPECTED).unwrap();
+    }
+    assert_eq!(
+        module.to_source().unwrap(),
+        std::fs::read_to_string(EXPECTED).unwrap()
+    );
+}
+
+#[test]
+fn codegen_tables_evaluate_exactly() {
+    let (orbit, decay) = (orbit(), decay());
+    let (orbit_view, decay_view) = (&generated::tables::ORBIT, &generated::tables::DECAY);
+    assert_eq!(orbit_view.to_table(), orbit);
+    assert_eq!(decay_view.to_table(), decay);
+    assert_eq!((decay_view.start(), decay_view.end()), (0.25, 1.0));
+    for i in 0..=60 {
+        let t = Days::new(0.05 * i as f64);
+        assert_eq!(orbit_view.eval(t), orbit.eval(t));
+        assert_eq!(orbit_view.eval_derivative(t), orbit.eval_derivative(t));
+        let t = 0.25 + 0.0125 * i as f64;
+        assert_eq!(decay_view.eval(t), decay.eval(t));
+    }
+}
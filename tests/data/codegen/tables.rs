@@ -0,0 +1,52 @@
+// @generated by cheby::codegen. Do not edit.
+
+/// Tables for the codegen tests.
+pub mod tables {
+    /// `mid`, `half` and the 6 values of each of the 2 segments of [`ORBIT`].
+    pub static ORBIT_DATA: [f64; 40] = [
+        0.75, 0.75,
+        4426.495169247195, 4123.707229630224, 0.375,
+        -3332.8116630606473, 3577.5271873196366, 0.37500000000000006,
+        -687.0821608571125, -640.0833087452088, -1.0986582014519778e-17,
+        80.96598814951963, -86.91100882283509, -2.7755575615628914e-17,
+        8.205779104970134, 7.644474765275106, 8.876001785414663e-17,
+        -0.5779919007902663, 0.6204316199552811, -1.1564823173178712e-16,
+        2.25, 0.75,
+        -3800.2594063108913, 4707.10624947206, 1.1250000000000002,
+        -3804.3192045214346, -3071.3986630773966, 0.3750000000000001,
+        589.8776221074511, -730.6387129348905, -1.850371707708594e-16,
+        92.42060301343636, 74.61532570634456, -2.220446049250313e-16,
+        -7.044871402194104, 8.725972271502087, 7.401486830834377e-17,
+        -0.6597629600846441, -0.5326564267146902, -2.9605947323337506e-16,
+    ];
+
+    /// 2 segments of 6 coefficients from 0.0 to 3.0.
+    pub static ORBIT: ::cheby::ChebySegmentTableView<'static, ::cheby::Vector<::qtty::Quantity<::qtty::Kilometer>, 3>, 6, ::qtty::Quantity<::qtty::Day>> =
+        ::cheby::ChebySegmentTableView::from_f64s_unchecked(&ORBIT_DATA, 0.0, 1.5);
+
+    /// `mid`, `half` and the 5 values of each of the 3 segments of [`DECAY`].
+    pub static DECAY_DATA: [f64; 21] = [
+        0.375, 0.125,
+        0.689976625474994,
+        -0.08607906436020746,
+        0.002688221186658568,
+        -5.598638586208349e-5,
+        8.745026366496767e-7,
+        0.625, 0.125,
+        0.5373543362208908,
+        -0.06703844272978347,
+        0.0020935887652388185,
+        -4.360224115071976e-5,
+        6.810633382370313e-7,
+        0.875, 0.125,
+        0.41849197783564474,
+        -0.05220959169384292,
+        0.0016304885697975059,
+        -3.39574595518366e-5,
+        5.304126611149763e-7,
+    ];
+
+    /// 3 segments of 5 coefficients from 0.25 to 1.0.
+    pub static DECAY: ::cheby::ChebySegmentTableView<'static, f64, 5, f64> =
+        ::cheby::ChebySegmentTableView::from_f64s_unchecked(&DECAY_DATA, 0.25, 0.25);
+}
@@ -120,3 +120,15 @@ fn segment_table_from_precomputed_segments_and_empty_case() {
     assert!(empty.eval_derivative(10.0).is_none());
     assert!(empty.eval_both(10.0).is_none());
 }
+
+#[test]
+fn functional_table_integral_matches_antiderivative() {
+    let table: ChebySegmentTable<f64, 15> =
+        ChebySegmentTable::from_fn(|t| t.sin() * t, 0.0, 6.0, 0.75);
+    let antiderivative = |t: f64| t.sin() - t * t.cos();
+
+    for &(a, b) in &[(0.0, 6.0), (0.3, 5.1), (4.4, 1.2)] {
+        let area = table.integrate(a, b).unwrap();
+        assert_abs_diff_eq!(area, antiderivative(b) - antiderivative(a), epsilon = 1e-11);
+    }
+}
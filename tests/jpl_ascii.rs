@@ -0,0 +1,135 @@
+//! JPL DE ASCII reader against the synthetic DE999 fixture in
+//! `tests/data/de999`: three 32-day records split over two data files that
+//! share a boundary record. Coefficient `i` (1-based position in the
+//! record) of record `r` is `r + i / 1000`.
+#![cfg(feature = "jpl")]
+
+use approx::assert_abs_diff_eq;
+use cheby::jpl::{DeBody, DeEphemeris, DeError, DeHeader, DeLayout};
+
+const DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/de999");
+
+fn fixture() -> DeEphemeris {
+    DeEphemeris::read_ascii(
+        format!("{DIR}/header.999"),
+        [
+            format!("{DIR}/ascp01999.999"),
+            format!("{DIR}/ascp02000.999"),
+        ],
+    )
+    .unwrap()
+}
+
+fn coeff(record: usize, position: usize) -> f64 {
+    record as f64 + position as f64 / 1000.0
+}
+
+#[test]
+fn jpl_ascii_header_groups() {
+    let text = std::fs::read_to_string(format!("{DIR}/header.999")).unwrap();
+    let header = DeHeader::parse_ascii(&text).unwrap();
+    assert_eq!((header.ksize, header.ncoeff), (82, 41));
+    assert_eq!(header.title.len(), 3);
+    assert!(header.title[0].contains("DE999"));
+    assert_eq!(
+        (header.start, header.end, header.interval),
+        (2_451_536.5, 2_451_632.5, 32.0)
+    );
+    assert_eq!(header.constant("DENUM"), Some(999.0));
+    assert_eq!(header.constant("CLIGHT"), Some(299_792.458));
+    assert_eq!(header.layout.len(), 13);
+    let mercury = DeLayout {
+        offset: 2,
+        ncoeff: 4,
+        nsub: 2,
+    };
+    assert_eq!(header.body_layout(DeBody::Mercury), Some(mercury));
+    assert_eq!(header.body_layout(DeBody::Nutations).unwrap().offset, 35);
+    assert_eq!(header.body_layout(DeBody::Mars), None);
+    assert_eq!(header.body_layout(DeBody::TtMinusTdb), None);
+}
+
+#[test]
+fn jpl_ascii_records_span_both_files() {
+    let de = fixture();
+    assert_eq!(de.len(), 3);
+    assert_eq!(de.start(), Some(de.header().start));
+    assert_eq!(de.end(), Some(de.header().end));
+    for (r, record) in de.records().enumerate() {
+        assert_eq!(record[0], 2_451_536.5 + 32.0 * r as f64);
+        assert_eq!(record[40], coeff(r + 1, 41));
+    }
+}
+
+#[test]
+fn jpl_ascii_body_table_layout() {
+    let de = fixture();
+    let mercury = de.body_table::<4, 3>(DeBody::Mercury).unwrap();
+    assert_eq!(mercury.len(), 6);
+    assert_eq!(mercury.segment_len(), 16.0);
+    assert_eq!(mercury.end(), 2_451_632.5);
+    for (i, seg) in mercury.segments().iter().enumerate() {
+        let (r, j) = (i / 2 + 1, i % 2);
+        assert_eq!(seg.mid, 2_451_536.5 + 16.0 * i as f64 + 8.0);
+        assert_eq!(seg.half, 8.0);
+        for d in 0..3 {
+            for k in 0..4 {
+                assert_eq!(seg.coeffs[d][k], coeff(r, 3 + 12 * j + 4 * d + k));
+            }
+        }
+    }
+
+    // At a segment midpoint τ = 0: f = c0 − c2 and df/dτ = c1 − 3 c3.
+    let t = 2_451_536.5 + 16.0 * 3.0 + 8.0;
+    let (p, v) = mercury.eval_both(t).unwrap();
+    let c = &mercury.segments()[3].coeffs;
+    for d in 0..3 {
+        assert_abs_diff_eq!(p[d], c[d][0] - c[d][2], epsilon = 1e-12);
+        assert_abs_diff_eq!(v[d], (c[d][1] - 3.0 * c[d][3]) / 8.0, epsilon = 1e-12);
+    }
+
+    let venus = de.body_table::<3, 3>(DeBody::Venus).unwrap();
+    assert_eq!((venus.len(), venus.segment_len()), (3, 32.0));
+    assert_eq!(
+        venus.segments()[2].coeffs[2],
+        [coeff(3, 33), coeff(3, 34), coeff(3, 35)]
+    );
+}
+
+#[test]
+fn jpl_ascii_component_tables_match_vector_tables() {
+    let de = fixture();
+    let nutations = de.body_table::<3, 2>(DeBody::Nutations).unwrap();
+    for d in 0..2 {
+        let component = de.component_table(DeBody::Nutations, d).unwrap();
+        assert_eq!(component.len(), nutations.len());
+        for t in [2_451_536.5, 2_451_550.25, 2_451_600.5, 2_451_632.5] {
+            assert_eq!(component.eval(t), nutations.eval(t).map(|v| v[d]));
+        }
+    }
+    assert!(matches!(
+        de.body_table::<3, 3>(DeBody::Nutations),
+        Err(DeError::Shape { stored: 2, .. })
+    ));
+}
+
+#[test]
+fn jpl_ascii_rejects_gaps() {
+    let read = |name: &str| std::fs::read_to_string(format!("{DIR}/{name}")).unwrap();
+    // Each record is a count line plus 14 lines of values. Keep record 1
+    // from the first file and record 3 from the second.
+    let lines = |name: &str, skip: usize| {
+        read(name)
+            .lines()
+            .skip(skip)
+            .take(15)
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let data = [lines("ascp01999.999", 0), lines("ascp02000.999", 15)];
+    let err = DeEphemeris::from_ascii(&read("header.999"), data).unwrap_err();
+    assert!(
+        matches!(err, DeError::NotContiguous { record: 1, found, .. } if found == 2_451_600.5),
+        "{err}"
+    );
+}
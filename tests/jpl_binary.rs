@@ -0,0 +1,284 @@
+//! JPL DE binary reader against synthetic files written here in the
+//! layout of JPL's Fortran tools, in both byte orders, and against a port
+//! of the reference Fortran interpolation (`STATE`/`INTERP` in
+//! `testeph.f`).
+#![cfg(feature = "jpl")]
+
+use approx::assert_relative_eq;
+use cheby::jpl::{DeBinary, DeBody, DeError, Endian};
+
+/// DE405 layout: `(offset, ncoeff, nsub)` for the 13 original columns.
+const DE405: [[u32; 3]; 13] = [
+    [3, 14, 4],
+    [171, 10, 2],
+    [231, 13, 2],
+    [309, 11, 1],
+    [342, 8, 1],
+    [366, 7, 1],
+    [387, 6, 1],
+    [405, 6, 1],
+    [423, 6, 1],
+    [441, 13, 8],
+    [753, 11, 2],
+    [819, 10, 4],
+    [899, 10, 4],
+];
+
+/// Lunar mantle and TT − TDB columns appended after DE405's 1018 values.
+const DE440_EXTRA: [[u32; 3]; 2] = [[1019, 10, 4], [1139, 6, 1]];
+
+const START: f64 = 2_451_536.5;
+const INTERVAL: f64 = 32.0;
+const RECORDS: usize = 4;
+
+/// A binary DE file with `ncon` constants and deterministic coefficients.
+fn write_de(endian: Endian, ncon: usize, extra: &[[u32; 3]]) -> Vec<u8> {
+    let ncoeff = if extra.is_empty() { 1018 } else { 1144 };
+    let mut out = Vec::new();
+    let put = |out: &mut Vec<u8>, b: &[u8]| out.extend_from_slice(b);
+    let f64b = |x: f64| match endian {
+        Endian::Little => x.to_le_bytes(),
+        Endian::Big => x.to_be_bytes(),
+    };
+    let u32b = |x: u32| match endian {
+        Endian::Little => x.to_le_bytes(),
+        Endian::Big => x.to_be_bytes(),
+    };
+    let name = |i: usize| format!("{:<6}", format!("C{i}"));
+
+    for line in [
+        "JPL Planetary Ephemeris DE999/LE999 (synthetic)",
+        "Start",
+        "Final",
+    ] {
+        put(&mut out, format!("{line:<84}").as_bytes());
+    }
+    for i in 0..400 {
+        let n = if i < ncon { name(i) } else { " ".repeat(6) };
+        put(&mut out, n.as_bytes());
+    }
+    let end = START + INTERVAL * RECORDS as f64;
+    for x in [START, end, INTERVAL] {
+        put(&mut out, &f64b(x));
+    }
+    put(&mut out, &u32b(ncon as u32));
+    put(&mut out, &f64b(149_597_870.7));
+    put(&mut out, &f64b(81.300_569_074_190_62));
+    for col in &DE405[..12] {
+        col.iter().for_each(|&w| put(&mut out, &u32b(w)));
+    }
+    put(&mut out, &u32b(999));
+    DE405[12].iter().for_each(|&w| put(&mut out, &u32b(w)));
+    for i in 400..ncon {
+        put(&mut out, name(i).as_bytes());
+    }
+    for col in extra {
+        col.iter().for_each(|&w| put(&mut out, &u32b(w)));
+    }
+    out.resize(8 * ncoeff, 0);
+
+    for i in 0..ncoeff {
+        let value = if i < ncon { i as f64 + 0.5 } else { 0.0 };
+        put(&mut out, &f64b(value));
+    }
+
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    for r in 0..RECORDS {
+        let t0 = START + INTERVAL * r as f64;
+        put(&mut out, &f64b(t0));
+        put(&mut out, &f64b(t0 + INTERVAL));
+        for k in 2..ncoeff {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let unit = (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5;
+            put(&mut out, &f64b(unit * 1.0e6 / (1 + k % 14) as f64));
+        }
+    }
+    out
+}
+
+/// Port of the reference `STATE` + `INTERP`: position and velocity of a
+/// body at `et` from the raw record values.
+fn reference_state(de: &DeBinary<Vec<u8>>, body: DeBody, et: f64) -> (Vec<f64>, Vec<f64>) {
+    let header = de.header();
+    let (ss1, ss2, ss3) = (de.start(), de.end(), header.interval);
+    // STATE: split the epoch into a half-integer and a fraction.
+    let pjd1 = (et - 0.5).floor() + 0.5;
+    let pjd4 = et - pjd1;
+    let mut nr = ((pjd1 - ss1) / ss3) as usize;
+    if pjd1 == ss2 {
+        nr -= 1;
+    }
+    let t1 = ((pjd1 - (nr as f64 * ss3 + ss1)) + pjd4) / ss3;
+    let record = de.record(nr);
+
+    let layout = header.body_layout(body).unwrap();
+    let (ncf, ncm, na) = (layout.ncoeff, body.components(), layout.nsub);
+    let buf = |i: usize| record.get(layout.offset + i);
+
+    // INTERP.
+    let dna = na as f64;
+    let dt1 = t1.trunc();
+    let temp = dna * t1;
+    let l = (temp - dt1) as usize;
+    let tc = 2.0 * (temp % 1.0 + dt1) - 1.0;
+    let mut pc = vec![1.0, tc];
+    for i in 2..ncf {
+        pc.push(2.0 * tc * pc[i - 1] - pc[i - 2]);
+    }
+    let mut vc = vec![0.0, 1.0, 4.0 * tc];
+    for i in 3..ncf {
+        vc.push(2.0 * tc * vc[i - 1] + 2.0 * pc[i - 1] - vc[i - 2]);
+    }
+    let vfac = (dna + dna) / ss3;
+    let at = |j: usize, i: usize| buf(j + i * ncf + l * ncf * ncm);
+    let pos = (0..ncm)
+        .map(|i| (0..ncf).rev().map(|j| pc[j] * at(j, i)).sum())
+        .collect();
+    let vel = (0..ncm)
+        .map(|i| vfac * (1..ncf).rev().map(|j| vc[j] * at(j, i)).sum::<f64>())
+        .collect();
+    (pos, vel)
+}
+
+fn epochs() -> impl Iterator<Item = f64> {
+    let end = START + INTERVAL * RECORDS as f64;
+    (0..=64)
+        .map(|i| START + 2.0 * i as f64 + 0.123_456_789 * (i % 3) as f64)
+        .chain([START, START + 16.0, START + INTERVAL, end])
+        .filter(move |&t| t <= end)
+}
+
+fn check_against_reference<const N: usize, const D: usize>(de: &DeBinary<Vec<u8>>, body: DeBody) {
+    let view = de.body::<N, D>(body).unwrap();
+    for t in epochs() {
+        let (p, v) = view.eval_both(t).unwrap();
+        let (p_ref, v_ref) = reference_state(de, body, t);
+        for d in 0..D {
+            // Coefficients reach 5e5, so the sums carry rounding errors of
+            // order 1e-10 (positions) and 1e-8 (velocities) either way.
+            assert_relative_eq!(p[d], p_ref[d], epsilon = 1e-8, max_relative = 1e-13);
+            assert_relative_eq!(v[d], v_ref[d], epsilon = 1e-6, max_relative = 1e-13);
+        }
+        assert_eq!(view.eval(t), Some(p));
+        assert_eq!(view.eval_derivative(t), Some(v));
+    }
+}
+
+#[test]
+fn jpl_binary_detects_byte_order() {
+    for endian in [Endian::Little, Endian::Big] {
+        let de = DeBinary::from_bytes(write_de(endian, 3, &[])).unwrap();
+        assert_eq!(de.endian(), endian);
+        let header = de.header();
+        assert_eq!((header.ncoeff, header.ksize), (1018, 2036));
+        assert_eq!(
+            header.title[0],
+            "JPL Planetary Ephemeris DE999/LE999 (synthetic)"
+        );
+        assert_eq!(header.layout.len(), 13);
+        assert_eq!(header.constant("C2"), Some(2.5));
+        assert_eq!(header.body_layout(DeBody::Moon).unwrap().offset, 440);
+        assert_eq!(header.body_layout(DeBody::TtMinusTdb), None);
+        assert_eq!((de.len(), de.start()), (RECORDS, START));
+        assert_eq!(de.end(), START + INTERVAL * RECORDS as f64);
+    }
+    let le = DeBinary::from_bytes(write_de(Endian::Little, 3, &[])).unwrap();
+    let be = DeBinary::from_bytes(write_de(Endian::Big, 3, &[])).unwrap();
+    let (a, b) = (le.record(2), be.record(2));
+    assert_eq!(a.len(), 1018);
+    assert!(a.iter().eq(b.iter()));
+}
+
+#[test]
+fn jpl_binary_reads_extended_header() {
+    let de = DeBinary::from_bytes(write_de(Endian::Little, 402, &DE440_EXTRA)).unwrap();
+    let header = de.header();
+    assert_eq!(header.ncoeff, 1144);
+    assert_eq!(header.layout.len(), 15);
+    assert_eq!(header.constants.len(), 402);
+    assert_eq!(header.constant("C401"), Some(401.5));
+    let tdb = header.body_layout(DeBody::TtMinusTdb).unwrap();
+    assert_eq!((tdb.offset, tdb.ncoeff, tdb.nsub), (1138, 6, 1));
+    check_against_reference::<6, 1>(&de, DeBody::TtMinusTdb);
+    check_against_reference::<10, 3>(&de, DeBody::LunarMantleVelocity);
+}
+
+#[test]
+fn jpl_binary_matches_reference_interpolation() {
+    for endian in [Endian::Little, Endian::Big] {
+        let de = DeBinary::from_bytes(write_de(endian, 3, &[])).unwrap();
+        check_against_reference::<14, 3>(&de, DeBody::Mercury);
+        check_against_reference::<13, 3>(&de, DeBody::EarthMoonBarycenter);
+        check_against_reference::<13, 3>(&de, DeBody::Moon);
+        check_against_reference::<6, 3>(&de, DeBody::Pluto);
+        check_against_reference::<10, 2>(&de, DeBody::Nutations);
+        check_against_reference::<10, 3>(&de, DeBody::Librations);
+    }
+}
+
+#[test]
+fn jpl_binary_views_match_loaded_tables() {
+    let de = DeBinary::from_bytes(write_de(Endian::Big, 3, &[])).unwrap();
+    let loaded = de.to_ephemeris().unwrap();
+    assert_eq!(loaded.len(), RECORDS);
+    let table = loaded.body_table::<13, 3>(DeBody::Moon).unwrap();
+    let view = de.body::<13, 3>(DeBody::Moon).unwrap();
+    assert_eq!(view.layout().nsub, 8);
+    // Tables are half-open; like the reference readers, the view also
+    // accepts the end of the last record.
+    for t in epochs().filter(|&t| t < de.end()) {
+        assert_eq!(view.eval_both(t), table.eval_both(t));
+        let seg = view.segment(t).unwrap();
+        assert_eq!(view.component(1, t).unwrap().coeffs, seg.coeffs[1]);
+    }
+    assert!(view.eval(de.end()).is_some());
+    assert_eq!(view.eval(START - 1.0), None);
+    assert_eq!(view.eval(de.end() + 1.0e-6), None);
+}
+
+#[test]
+fn jpl_binary_rejects_bad_files() {
+    let good = write_de(Endian::Little, 3, &[]);
+    let err = DeBinary::from_bytes(&good[..1000]).unwrap_err();
+    assert!(matches!(err, DeError::Format(_)), "{err}");
+    let err = DeBinary::from_bytes(&good[..good.len() - 8]).unwrap_err();
+    assert!(matches!(err, DeError::Format(_)), "{err}");
+    let err = DeBinary::from_bytes(&good[..2 * 8 * 1018]).unwrap_err();
+    assert!(matches!(err, DeError::NoRecords), "{err}");
+
+    let de = DeBinary::from_bytes(&good[..]).unwrap();
+    assert!(matches!(
+        de.body::<13, 3>(DeBody::Mercury),
+        Err(DeError::Shape { stored: 14, .. })
+    ));
+    assert!(matches!(
+        de.body::<10, 1>(DeBody::TtMinusTdb),
+        Err(DeError::MissingBody(DeBody::TtMinusTdb))
+    ));
+
+    let mut scrambled = good.clone();
+    scrambled[2840..2844].copy_from_slice(&[0xff; 4]);
+    let err = DeBinary::from_bytes(scrambled).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "malformed binary ephemeris: cannot detect byte order"
+    );
+}
+
+#[test]
+fn jpl_binary_memory_maps_files() {
+    let path = std::env::temp_dir().join(format!("cheby-de999-{}.bin", std::process::id()));
+    std::fs::write(&path, write_de(Endian::Big, 3, &[])).unwrap();
+    let mapped = DeBinary::open(&path).unwrap();
+    let in_memory = DeBinary::from_bytes(std::fs::read(&path).unwrap()).unwrap();
+    let t = START + 40.25;
+    assert_eq!(
+        mapped.body::<11, 3>(DeBody::Sun).unwrap().eval_both(t),
+        in_memory.body::<11, 3>(DeBody::Sun).unwrap().eval_both(t)
+    );
+    drop(mapped);
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(DeBinary::open(&path), Err(DeError::Io(_))));
+}
@@ -0,0 +1,153 @@
+//! serde support: exact round trips through JSON and MessagePack for every
+//! serializable type, unit checks, and validation of deserialized tables.
+#![cfg(feature = "serde")]
+
+use cheby::{
+    ChebySegment, ChebySegmentTable, ChebySeries, ChebyVarTable, ChebyVecSegmentTable,
+    DynChebySegmentTable, Vec3,
+};
+use qtty::{Days, Hours, Kilometers, Meters};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Debug;
+
+fn round_trip<V: Serialize + DeserializeOwned + PartialEq + Debug>(value: &V) {
+    let json = serde_json::to_string(value).unwrap();
+    assert_eq!(&serde_json::from_str::<V>(&json).unwrap(), value);
+    let msgpack = rmp_serde::to_vec(value).unwrap();
+    assert_eq!(&rmp_serde::from_slice::<V>(&msgpack).unwrap(), value);
+}
+
+fn orbit() -> ChebySegmentTable<Vec3<Kilometers>, 10, Days> {
+    ChebySegmentTable::from_fn(
+        |t: Days| {
+            let (s, c) = t.value().sin_cos();
+            Vec3::new([
+                Kilometers::new(7000.0 * c),
+                Kilometers::new(7000.0 * s),
+                Kilometers::new(5.0 * t.value()),
+            ])
+        },
+        Days::new(0.0),
+        Days::new(6.0),
+        Days::new(1.0),
+    )
+}
+
+#[test]
+fn serde_round_trips_exactly() {
+    round_trip(&orbit());
+    round_trip(&orbit().segments()[2]);
+    // Past the array sizes serde itself supports.
+    round_trip(&ChebySegmentTable::<f32, 40>::from_fn(
+        |t| t.exp() as f32,
+        -1.0,
+        1.0,
+        0.5,
+    ));
+    round_trip(
+        &ChebyVarTable::<f64, 8>::from_segments(vec![
+            ChebySegment::new([1.0; 8], 0.5, 0.5),
+            ChebySegment::new([2.0; 8], 2.0, 1.0),
+        ])
+        .unwrap(),
+    );
+    let state = ChebyVecSegmentTable::<f64, 9, 6>::from_fn(
+        |t| {
+            let (s, c) = t.sin_cos();
+            [c, s, 0.0, -s, c, 0.0]
+        },
+        0.0,
+        3.0,
+        0.75,
+    );
+    round_trip(&state);
+    round_trip(&state.segments()[0]);
+    let dynamic = DynChebySegmentTable::<f64>::from_fn(f64::cos, 0.0, 2.0, 0.5, 13);
+    round_trip(&dynamic);
+    round_trip(&dynamic.segments()[1]);
+    round_trip(&ChebySeries::new(vec![1.0, 0.5, 0.25]));
+
+    let json = serde_json::to_value(orbit()).unwrap();
+    assert_eq!(json["value_unit"]["symbol"], "km");
+    assert_eq!(json["axis_unit"]["symbol"], "d");
+    assert_eq!(json["segments"].as_array().unwrap().len(), 6);
+    assert_eq!(
+        json["segments"][0]["coeffs"][0].as_array().unwrap().len(),
+        3
+    );
+}
+
+#[test]
+fn serde_checks_units_and_shape() {
+    let json = serde_json::to_string(&orbit()).unwrap();
+    let err = serde_json::from_str::<ChebySegmentTable<Vec3<Meters>, 10, Days>>(&json).unwrap_err();
+    assert!(err.to_string().contains("table value is in km"), "{err}");
+    let err =
+        serde_json::from_str::<ChebySegmentTable<Vec3<Kilometers>, 10, Hours>>(&json).unwrap_err();
+    assert!(err.to_string().contains("table axis is in d"), "{err}");
+    let err =
+        serde_json::from_str::<ChebySegmentTable<Vec3<Kilometers>, 9, Days>>(&json).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("table has 10 coefficients, requested 9"),
+        "{err}"
+    );
+    assert!(serde_json::from_str::<ChebySegmentTable<Kilometers, 10, Days>>(&json).is_err());
+
+    // Plain-number tables of either degree type read each other.
+    let table = ChebySegmentTable::<f64, 7>::from_fn(f64::sin, 0.0, 2.0, 1.0);
+    let json = serde_json::to_string(&table).unwrap();
+    let dynamic: DynChebySegmentTable<f64> = serde_json::from_str(&json).unwrap();
+    assert_eq!(dynamic.eval(1.3), table.eval(1.3));
+    let err = serde_json::from_str::<ChebySegmentTable<f64, 7, Days>>(&json).unwrap_err();
+    assert!(
+        err.to_string().contains("table axis is in no unit"),
+        "{err}"
+    );
+}
+
+#[test]
+fn serde_validates_tables() {
+    let table = ChebySegmentTable::<f64, 3>::from_fn(|t| t * t, 0.0, 3.0, 1.0);
+    let json = serde_json::to_value(&table).unwrap();
+    let read = |edit: &dyn Fn(&mut serde_json::Value)| {
+        let mut json = json.clone();
+        edit(&mut json);
+        serde_json::from_value::<ChebySegmentTable<f64, 3>>(json)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    };
+    assert_eq!(read(&|_| {}), Ok(()));
+
+    let err = read(&|j| j["segments"][1]["half"] = (-0.5).into()).unwrap_err();
+    assert_eq!(err, "invalid table: segment 1 has mid 1.5, half-width -0.5");
+    let err = read(&|j| j["segment_len"] = 0.0.into()).unwrap_err();
+    assert_eq!(err, "invalid table: domain start 0, segment length 0");
+    let err = read(&|j| j["segment_len"] = 1.5.into()).unwrap_err();
+    assert_eq!(
+        err,
+        "invalid table: segment 0 covers [0, 1], expected [0, 1.5]"
+    );
+    let err = read(&|j| {
+        j["segments"].as_array_mut().unwrap().remove(1);
+    })
+    .unwrap_err();
+    assert_eq!(
+        err,
+        "invalid table: segment 1 covers [2, 3], expected [1, 2]"
+    );
+
+    let var = ChebyVarTable::<f64, 2>::from_segments(vec![
+        ChebySegment::new([1.0, 0.0], 0.5, 0.5),
+        ChebySegment::new([1.0, 0.0], 1.5, 0.5),
+    ])
+    .unwrap();
+    let mut json = serde_json::to_value(&var).unwrap();
+    json["breakpoints"][1] = 3.0.into();
+    let err = serde_json::from_value::<ChebyVarTable<f64, 2>>(json).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid table: segment 1 has a non-positive width"
+    );
+}
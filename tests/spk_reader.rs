@@ -0,0 +1,361 @@
+//! SPK reader against synthetic kernels written here in the DAF layout, in
+//! both byte orders, and against a direct Chebyshev sum.
+#![cfg(feature = "spk")]
+
+use approx::assert_relative_eq;
+use cheby::spk::{Endian, Spk, SpkError};
+
+const RECORD: usize = 1024;
+/// Address of the first data word: after the file, comment and two
+/// summary/name record pairs.
+const DATA: usize = 6 * RECORD / 8 + 1;
+
+/// A type 2 or 3 segment: ids, `ncoeff`, and the `(INIT, INTLEN, N)`
+/// directory.
+struct Seg {
+    name: &'static str,
+    target: i32,
+    center: i32,
+    data_type: i32,
+    ncoeff: usize,
+    init: f64,
+    intlen: f64,
+    n: usize,
+}
+
+const SEGMENTS: [Seg; 3] = [
+    Seg {
+        name: "MARS BARYCENTER",
+        target: 4,
+        center: 0,
+        data_type: 2,
+        ncoeff: 5,
+        init: -1000.0,
+        intlen: 500.0,
+        n: 4,
+    },
+    Seg {
+        name: "MOON (TYPE 3)",
+        target: 301,
+        center: 3,
+        data_type: 3,
+        ncoeff: 4,
+        init: 0.0,
+        intlen: 200.0,
+        n: 3,
+    },
+    Seg {
+        name: "MARS BARYCENTER PATCH",
+        target: 4,
+        center: 0,
+        data_type: 2,
+        ncoeff: 3,
+        init: -200.0,
+        intlen: 400.0,
+        n: 1,
+    },
+];
+
+fn components(seg: &Seg) -> usize {
+    if seg.data_type == 2 {
+        3
+    } else {
+        6
+    }
+}
+
+/// Coefficient `k` of component `d` in record `r` of segment `s`.
+fn coeff(s: usize, r: usize, d: usize, k: usize) -> f64 {
+    (1 + s + r + d) as f64 * 0.5_f64.powi(k as i32) + 0.1 * k as f64 - 0.01 * d as f64
+}
+
+/// Values of segment `s`: records then directory.
+fn segment_words(s: usize) -> Vec<f64> {
+    let seg = &SEGMENTS[s];
+    let mut words = Vec::new();
+    for r in 0..seg.n {
+        words.push(seg.init + (r as f64 + 0.5) * seg.intlen);
+        words.push(seg.intlen / 2.0);
+        for d in 0..components(seg) {
+            words.extend((0..seg.ncoeff).map(|k| coeff(s, r, d, k)));
+        }
+    }
+    let rsize = 2 + components(seg) * seg.ncoeff;
+    words.extend([seg.init, seg.intlen, rsize as f64, seg.n as f64]);
+    words
+}
+
+/// A summary record holding `entries` (segment, target, data type and
+/// address span) and the name record after it.
+fn summary_pair(
+    endian: Endian,
+    next: f64,
+    prev: f64,
+    entries: &[(&Seg, i32, i32, usize, usize)],
+) -> Vec<u8> {
+    let f64b = |x: f64| match endian {
+        Endian::Little => x.to_le_bytes(),
+        Endian::Big => x.to_be_bytes(),
+    };
+    let i32b = |x: i32| match endian {
+        Endian::Little => x.to_le_bytes(),
+        Endian::Big => x.to_be_bytes(),
+    };
+    let mut out = Vec::new();
+    for x in [next, prev, entries.len() as f64] {
+        out.extend(f64b(x));
+    }
+    for &(seg, target, data_type, first, last) in entries {
+        out.extend(f64b(seg.init));
+        out.extend(f64b(seg.init + seg.n as f64 * seg.intlen));
+        for x in [target, seg.center, 1, data_type, first as i32, last as i32] {
+            out.extend(i32b(x));
+        }
+    }
+    out.resize(RECORD, 0);
+    for &(seg, ..) in entries {
+        out.extend(format!("{:<40}", seg.name).bytes());
+    }
+    out.resize(2 * RECORD, b' ');
+    out
+}
+
+/// A kernel with the three segments in two summary records, followed by
+/// a fourth summary labelling the first segment's data as type 1 for
+/// target 5.
+fn write_spk(endian: Endian) -> Vec<u8> {
+    let f64b = |x: f64| match endian {
+        Endian::Little => x.to_le_bytes(),
+        Endian::Big => x.to_be_bytes(),
+    };
+    let i32b = |x: i32| match endian {
+        Endian::Little => x.to_le_bytes(),
+        Endian::Big => x.to_be_bytes(),
+    };
+
+    let mut data = Vec::new();
+    let mut spans = Vec::new();
+    for s in 0..SEGMENTS.len() {
+        let first = DATA + data.len();
+        data.extend(segment_words(s));
+        spans.push((first, DATA + data.len() - 1));
+    }
+
+    let mut out = Vec::new();
+    out.extend(b"DAF/SPK ");
+    out.extend(i32b(2));
+    out.extend(i32b(6));
+    out.extend(format!("{:<60}", "SYNTHETIC TEST KERNEL").bytes());
+    for x in [3, 5, (DATA + data.len()) as i32] {
+        out.extend(i32b(x));
+    }
+    out.extend(match endian {
+        Endian::Little => b"LTL-IEEE",
+        Endian::Big => b"BIG-IEEE",
+    });
+    out.resize(RECORD, 0);
+
+    out.extend(b"first line\0second line\x04");
+    out.resize(2 * RECORD, 0);
+
+    let entry = |s: usize| {
+        let seg = &SEGMENTS[s];
+        (seg, seg.target, seg.data_type, spans[s].0, spans[s].1)
+    };
+    let relabelled = (&SEGMENTS[0], 5, 1, spans[0].0, spans[0].1);
+    out.extend(summary_pair(endian, 5.0, 0.0, &[entry(0), entry(1)]));
+    out.extend(summary_pair(endian, 0.0, 3.0, &[entry(2), relabelled]));
+
+    for x in data {
+        out.extend(f64b(x));
+    }
+    out.resize(out.len().div_ceil(RECORD) * RECORD, 0);
+    out
+}
+
+/// `Σ c_k T_k(τ)` and `Σ c_k T_k'(τ)` by the three-term recurrences.
+fn reference(c: &[f64], tau: f64) -> (f64, f64) {
+    let (mut t, mut dt) = (vec![1.0, tau], vec![0.0, 1.0]);
+    for k in 2..c.len() {
+        t.push(2.0 * tau * t[k - 1] - t[k - 2]);
+        dt.push(2.0 * t[k - 1] + 2.0 * tau * dt[k - 1] - dt[k - 2]);
+    }
+    c.iter()
+        .zip(t.iter().zip(&dt))
+        .fold((0.0, 0.0), |(f, df), (c, (t, dt))| (f + c * t, df + c * dt))
+}
+
+/// Component `d` of segment `s` at `t` by direct summation, and its rate.
+fn reference_state(s: usize, d: usize, t: f64) -> (f64, f64) {
+    let seg = &SEGMENTS[s];
+    let r = (((t - seg.init) / seg.intlen) as usize).min(seg.n - 1);
+    let half = seg.intlen / 2.0;
+    let tau = (t - (seg.init + (r as f64 + 0.5) * seg.intlen)) / half;
+    let c: Vec<f64> = (0..seg.ncoeff).map(|k| coeff(s, r, d, k)).collect();
+    let (f, df) = reference(&c, tau);
+    (f, df / half)
+}
+
+fn samples(seg: &Seg) -> impl Iterator<Item = f64> + '_ {
+    let span = seg.n as f64 * seg.intlen;
+    (0..=40).map(move |i| seg.init + span * i as f64 / 40.0)
+}
+
+#[test]
+fn spk_reader_summaries_and_comments() {
+    for endian in [Endian::Little, Endian::Big] {
+        let spk = Spk::from_bytes(write_spk(endian)).unwrap();
+        assert_eq!(spk.endian(), endian);
+        assert_eq!(spk.internal_name(), "SYNTHETIC TEST KERNEL");
+        assert_eq!(spk.comments(), "first line\nsecond line");
+
+        let names: Vec<_> = spk.summaries().iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "MARS BARYCENTER",
+                "MOON (TYPE 3)",
+                "MARS BARYCENTER PATCH",
+                "MARS BARYCENTER"
+            ]
+        );
+        let moon = &spk.summaries()[1];
+        assert_eq!(
+            (moon.target, moon.center, moon.frame, moon.data_type),
+            (301, 3, 1, 3)
+        );
+        assert_eq!((moon.start, moon.end), (0.0, 600.0));
+        assert_eq!(spk.summaries()[0].start_address, DATA);
+    }
+}
+
+#[test]
+fn spk_reader_type2_matches_reference() {
+    for endian in [Endian::Little, Endian::Big] {
+        let spk = Spk::from_bytes(write_spk(endian)).unwrap();
+        let segment = spk.segment(&spk.summaries()[0]).unwrap();
+        assert_eq!((segment.init(), segment.segment_len()), (-1000.0, 500.0));
+        assert_eq!(
+            (segment.len(), segment.rsize(), segment.ncoeff()),
+            (4, 17, 5)
+        );
+        assert_eq!(segment.components(), 3);
+        assert_eq!(segment.record_domain(1), (-250.0, 250.0));
+        // The end of the last interval belongs to the last record.
+        assert_eq!(segment.segment_index(1000.0), Some(3));
+        assert_eq!(segment.segment_index(1000.5), None);
+        assert_eq!(segment.segment_index(-1000.5), None);
+
+        for t in samples(&SEGMENTS[0]) {
+            let p = segment.eval(t).unwrap();
+            let (p2, v) = segment.eval_both(t).unwrap();
+            assert_eq!(p, p2);
+            for d in 0..3 {
+                let (f, df) = reference_state(0, d, t);
+                assert_relative_eq!(p[d], f, epsilon = 1e-12);
+                assert_relative_eq!(v[d], df, epsilon = 1e-14);
+            }
+        }
+    }
+}
+
+#[test]
+fn spk_reader_type3_uses_velocity_coefficients() {
+    let spk = Spk::from_bytes(write_spk(Endian::Big)).unwrap();
+    let segment = spk.segment(&spk.summaries()[1]).unwrap();
+    assert_eq!((segment.components(), segment.ncoeff()), (6, 4));
+    for t in samples(&SEGMENTS[1]) {
+        let (p, v) = segment.eval_both(t).unwrap();
+        assert_eq!(segment.eval(t), Some(p));
+        for d in 0..3 {
+            assert_relative_eq!(p[d], reference_state(1, d, t).0, epsilon = 1e-12);
+            assert_relative_eq!(v[d], reference_state(1, d + 3, t).0, epsilon = 1e-12);
+        }
+    }
+}
+
+#[test]
+fn spk_reader_later_segments_take_precedence() {
+    let spk = Spk::from_bytes(write_spk(Endian::Little)).unwrap();
+    let patch = Some(&spk.summaries()[2]);
+    assert_eq!(spk.find(4, 0, 1), patch);
+    assert_eq!(spk.find_at(4, 0, 1, 0.0), patch);
+    assert_eq!(spk.find_at(4, 0, 1, 200.0), patch);
+    assert_eq!(spk.find_at(4, 0, 1, 200.5), Some(&spk.summaries()[0]));
+    assert_eq!(spk.find_at(4, 0, 1, -1000.0), Some(&spk.summaries()[0]));
+    assert_eq!(spk.find_at(5, 0, 1, 0.0), Some(&spk.summaries()[3]));
+    assert_eq!(spk.find_at(301, 3, 1, 600.0), Some(&spk.summaries()[1]));
+    assert_eq!(spk.find_at(301, 3, 1, 601.0), None);
+    assert_eq!(spk.find(301, 0, 1), None);
+
+    let patch = spk.segment(&spk.summaries()[2]).unwrap();
+    assert_eq!(patch.eval(-201.0), None);
+    assert_eq!(
+        patch.eval(0.0).map(|p| p[0]),
+        Some(reference_state(2, 0, 0.0).0)
+    );
+}
+
+#[test]
+fn spk_reader_tables_match_segments() {
+    let spk = Spk::from_bytes(write_spk(Endian::Little)).unwrap();
+    let segment = spk.segment(&spk.summaries()[0]).unwrap();
+    let table = segment.to_table::<5, 3>().unwrap();
+    assert_eq!(
+        (table.len(), table.start(), table.end()),
+        (4, -1000.0, 1000.0)
+    );
+    // Tables are half-open, so the final epoch is left out.
+    for t in samples(&SEGMENTS[0]).filter(|&t| t < 1000.0) {
+        assert_eq!(table.eval(t), segment.eval(t));
+        let (p, v) = table.eval_both(t).unwrap();
+        let (sp, sv) = segment.eval_both(t).unwrap();
+        for d in 0..3 {
+            assert_relative_eq!(p[d], sp[d], epsilon = 1e-13);
+            assert_relative_eq!(v[d], sv[d], epsilon = 1e-15);
+        }
+    }
+    assert!(matches!(
+        segment.to_table::<4, 3>(),
+        Err(SpkError::Shape {
+            what: "coefficients",
+            stored: 5,
+            requested: 4
+        })
+    ));
+
+    let moon = spk.segment(&spk.summaries()[1]).unwrap();
+    let table = moon.to_table::<4, 6>().unwrap();
+    let (p, v) = moon.eval_both(250.0).unwrap();
+    let state = table.eval(250.0).unwrap();
+    assert_eq!(state[..3], p);
+    assert_eq!(state[3..], v);
+    assert!(matches!(
+        moon.to_table::<4, 3>(),
+        Err(SpkError::Shape {
+            what: "components",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn spk_reader_rejects_bad_segments() {
+    let spk = Spk::from_bytes(write_spk(Endian::Little)).unwrap();
+    assert!(matches!(
+        spk.segment(&spk.summaries()[3]),
+        Err(SpkError::UnsupportedType(1))
+    ));
+
+    let mut summary = spk.summaries()[0].clone();
+    summary.end_address += 1;
+    let err = spk.segment(&summary).unwrap_err();
+    assert!(matches!(err, SpkError::Format(_)), "{err}");
+    summary.end_address = usize::MAX / 16;
+    let err = spk.segment(&summary).unwrap_err();
+    assert!(err.to_string().contains("outside the file"), "{err}");
+
+    let mut bytes = write_spk(Endian::Little);
+    bytes[..8].copy_from_slice(b"DAF/PCK ");
+    assert!(matches!(Spk::from_bytes(bytes), Err(SpkError::Format(_))));
+}
@@ -0,0 +1,134 @@
+//! SPK writer round trips through the SPK reader: summaries, comments and
+//! coefficients come back bit for bit, in both byte orders and across
+//! several summary and comment records.
+#![cfg(feature = "spk")]
+
+use cheby::spk::{Endian, Spk, SpkError, SpkWriter};
+use cheby::ChebyVecSegmentTable;
+
+const DAY: f64 = 86_400.0;
+
+/// A slightly eccentric orbit of radius `a` km and period `p` days.
+fn orbit(a: f64, p: f64) -> impl Fn(f64) -> [f64; 6] {
+    let n = std::f64::consts::TAU / (p * DAY);
+    move |t| {
+        let r = a * (1.0 + 0.01 * (n * t).cos());
+        let (s, c) = (n * t).sin_cos();
+        [
+            r * c,
+            r * s,
+            0.1 * r * s,
+            -a * n * s,
+            a * n * c,
+            0.1 * a * n * c,
+        ]
+    }
+}
+
+fn position(f: impl Fn(f64) -> [f64; 6]) -> impl Fn(f64) -> [f64; 3] {
+    move |t| {
+        let s = f(t);
+        [s[0], s[1], s[2]]
+    }
+}
+
+#[test]
+fn spk_writer_round_trip() {
+    let comments = (0..120)
+        .map(|i| format!("Line {i}: synthetic kernel written by the round-trip test."))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let moon = ChebyVecSegmentTable::<f64, 11, 3>::from_fn(
+        position(orbit(384_400.0, 27.3)),
+        -20.0 * DAY,
+        20.0 * DAY,
+        4.0 * DAY,
+    );
+    let earth = ChebyVecSegmentTable::<f64, 9, 6>::from_fn(
+        orbit(1.496e8, 365.25),
+        -20.0 * DAY,
+        20.0 * DAY,
+        8.0 * DAY,
+    );
+
+    for endian in [Endian::Little, Endian::Big] {
+        let mut writer = SpkWriter::new("ROUND TRIP")
+            .with_endian(endian)
+            .with_comments(comments.as_str());
+        writer.push_type2("MOON", 301, 399, 1, &moon).unwrap();
+        writer.push_type3("EARTH", 399, 3, 1, &earth).unwrap();
+        // Enough segments to fill a second summary record.
+        for k in 0..30 {
+            writer
+                .push_type2(&format!("ASTEROID {k}"), 2_000_000 + k, 10, 1, &moon)
+                .unwrap();
+        }
+        let bytes = writer.to_bytes().unwrap();
+        assert_eq!(bytes.len() % 1024, 0);
+
+        let spk = Spk::from_bytes(bytes).unwrap();
+        assert_eq!(spk.endian(), endian);
+        assert_eq!(spk.internal_name(), "ROUND TRIP");
+        assert_eq!(spk.comments(), comments);
+        assert_eq!(spk.summaries(), writer.summaries());
+        assert_eq!(spk.summaries().len(), 32);
+
+        let summary = spk.find(301, 399, 1).unwrap();
+        assert_eq!((summary.name.as_str(), summary.data_type), ("MOON", 2));
+        assert_eq!((summary.start, summary.end), (moon.start(), moon.end()));
+        let read = spk.segment(summary).unwrap().to_table::<11, 3>().unwrap();
+        assert_eq!(read.segments(), moon.segments());
+        assert_eq!(
+            (read.start(), read.segment_len()),
+            (moon.start(), moon.segment_len())
+        );
+
+        let segment = spk.segment(spk.find(399, 3, 1).unwrap()).unwrap();
+        assert_eq!(segment.summary().data_type, 3);
+        assert_eq!(
+            segment.to_table::<9, 6>().unwrap().segments(),
+            earth.segments()
+        );
+        let t = 3.3 * DAY;
+        let (p, v) = segment.eval_both(t).unwrap();
+        let state = earth.eval(t).unwrap();
+        assert_eq!((p.as_slice(), v.as_slice()), (&state[..3], &state[3..]));
+
+        let last = spk.find(2_000_029, 10, 1).unwrap();
+        let read = spk.segment(last).unwrap().to_table::<11, 3>().unwrap();
+        assert_eq!(read.segments(), moon.segments());
+    }
+}
+
+#[test]
+fn spk_writer_files() {
+    let table = ChebyVecSegmentTable::<f64, 6, 3>::from_fn(
+        position(orbit(7000.0, 0.07)),
+        0.0,
+        DAY,
+        DAY / 8.0,
+    );
+    let mut writer = SpkWriter::new("FILE");
+    writer.push_type2("SATELLITE", -99, 399, 1, &table).unwrap();
+    assert_eq!(writer.len(), 1);
+
+    let path = std::env::temp_dir().join(format!("cheby-spk-{}.bsp", std::process::id()));
+    writer.write(&path).unwrap();
+    let spk = Spk::open(&path).unwrap();
+    let mut buffer = Vec::new();
+    writer.write_to(&mut buffer).unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), buffer);
+    let segment = spk.segment(&spk.summaries()[0]).unwrap();
+    for t in (0..64).map(|i| DAY * i as f64 / 64.0) {
+        assert_eq!(segment.eval(t), table.eval(t));
+    }
+    // Unlike the half-open table, the segment includes its final epoch.
+    assert!(segment.eval(DAY).is_some());
+    drop(spk);
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(matches!(
+        SpkWriter::new("N".repeat(61)).to_bytes(),
+        Err(SpkError::Invalid(_))
+    ));
+}
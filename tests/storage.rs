@@ -0,0 +1,169 @@
+//! Table files: exact round trips for each storable scalar and axis, and
+//! the error reported for each kind of damaged or mismatched file.
+
+use cheby::storage::{Storable, StorableAxis, StorageError, UnitTag};
+use cheby::{ChebySegmentTable, Vec3};
+use qtty::{Days, Hours, Kilometers, Meters};
+
+fn bytes<T: Storable, const N: usize, X: StorableAxis>(
+    table: &ChebySegmentTable<T, N, X>,
+) -> Vec<u8> {
+    let mut out = Vec::new();
+    table.write_to(&mut out).unwrap();
+    out
+}
+
+fn sine() -> ChebySegmentTable<f64, 12> {
+    ChebySegmentTable::from_fn(f64::sin, -3.0, 5.0, 0.5)
+}
+
+#[test]
+fn storage_round_trips_exactly() {
+    let table = sine();
+    let file = bytes(&table);
+    let read = ChebySegmentTable::<f64, 12>::read_from(file.as_slice()).unwrap();
+    assert_eq!(read, table);
+    assert_eq!(read.eval(1.234), table.eval(1.234));
+
+    let orbit: ChebySegmentTable<Vec3<Kilometers>, 9, Days> = ChebySegmentTable::from_fn(
+        |t: Days| {
+            let (s, c) = t.value().sin_cos();
+            Vec3::new([Kilometers::new(c), Kilometers::new(s), Kilometers::new(0.0)])
+        },
+        Days::new(0.0),
+        Days::new(6.0),
+        Days::new(1.5),
+    );
+    let read: ChebySegmentTable<Vec3<Kilometers>, 9, Days> =
+        ChebySegmentTable::read_from(bytes(&orbit).as_slice()).unwrap();
+    assert_eq!(read, orbit);
+
+    let single = ChebySegmentTable::<f32, 6>::from_fn(|t| t.cos() as f32, 0.0, 2.0, 1.0);
+    let read = ChebySegmentTable::<f32, 6>::read_from(bytes(&single).as_slice()).unwrap();
+    assert_eq!(read, single);
+}
+
+#[test]
+fn storage_rejects_mismatched_types() {
+    let file = bytes(&sine());
+    let err = ChebySegmentTable::<f64, 10>::read_from(file.as_slice()).unwrap_err();
+    assert!(
+        matches!(
+            err,
+            StorageError::Shape {
+                what: "coefficients",
+                stored: 12,
+                requested: 10
+            }
+        ),
+        "{err}"
+    );
+    let err = ChebySegmentTable::<f32, 12>::read_from(file.as_slice()).unwrap_err();
+    assert!(
+        matches!(
+            err,
+            StorageError::Shape {
+                what: "bytes per number",
+                ..
+            }
+        ),
+        "{err}"
+    );
+    let err = ChebySegmentTable::<Meters, 12>::read_from(file.as_slice()).unwrap_err();
+    assert!(
+        matches!(err, StorageError::Unit { what: "value", .. }),
+        "{err}"
+    );
+
+    let km: ChebySegmentTable<Kilometers, 4, Days> = ChebySegmentTable::from_fn(
+        |t: Days| Kilometers::new(t.value()),
+        Days::new(0.0),
+        Days::new(1.0),
+        Days::new(1.0),
+    );
+    let file = bytes(&km);
+    let err = ChebySegmentTable::<Meters, 4, Days>::read_from(file.as_slice()).unwrap_err();
+    match err {
+        StorageError::Unit {
+            what: "value",
+            stored,
+            requested,
+        } => {
+            assert_eq!(stored, UnitTag::of::<qtty::Kilometer>());
+            assert_eq!(requested, UnitTag::of::<qtty::Meter>());
+        }
+        err => panic!("{err}"),
+    }
+    let err = ChebySegmentTable::<Kilometers, 4, Hours>::read_from(file.as_slice()).unwrap_err();
+    assert!(
+        matches!(err, StorageError::Unit { what: "axis", .. }),
+        "{err}"
+    );
+    let err = ChebySegmentTable::<Kilometers, 4>::read_from(file.as_slice()).unwrap_err();
+    assert!(
+        matches!(err, StorageError::Unit { what: "axis", .. }),
+        "{err}"
+    );
+}
+
+#[test]
+fn storage_detects_damage() {
+    let file = bytes(&sine());
+    let read = |b: &[u8]| ChebySegmentTable::<f64, 12>::read_from(b).map(|_| ());
+
+    for len in [0, 7, 30, file.len() / 2, file.len() - 1] {
+        let err = read(&file[..len]).unwrap_err();
+        assert!(matches!(err, StorageError::Truncated), "{len}: {err}");
+    }
+
+    // A flipped bit anywhere past the header fields is caught by the CRC.
+    for at in [70, file.len() / 2, file.len() - 5] {
+        let mut damaged = file.clone();
+        damaged[at] ^= 0x10;
+        let err = read(&damaged).unwrap_err();
+        assert!(matches!(err, StorageError::Corrupt { .. }), "{at}: {err}");
+    }
+
+    let mut damaged = file.clone();
+    damaged[0] = b'X';
+    assert!(matches!(read(&damaged), Err(StorageError::NotATable)));
+    let mut damaged = file.clone();
+    damaged[8] = 2;
+    assert!(matches!(
+        read(&damaged),
+        Err(StorageError::UnsupportedVersion(2))
+    ));
+}
+
+#[test]
+fn storage_validates_domain() {
+    // A table whose segments have zero width passes the checksum but is
+    // rejected as invalid.
+    let table = ChebySegmentTable::<f64, 2>::from_segments(
+        vec![cheby::ChebySegment::new([1.0, 0.0], 0.5, 0.0)],
+        0.0,
+        1.0,
+    );
+    let err = ChebySegmentTable::<f64, 2>::read_from(bytes(&table).as_slice()).unwrap_err();
+    assert!(matches!(err, StorageError::Invalid(_)), "{err}");
+    assert_eq!(
+        err.to_string(),
+        "invalid table: segment 0 has mid 0.5, half-width 0"
+    );
+
+    // Valid segments in the wrong slots of the table.
+    let mut segments = sine().into_segments();
+    segments.swap(2, 3);
+    let table = ChebySegmentTable::from_segments(segments, -3.0, 0.5);
+    let err = ChebySegmentTable::<f64, 12>::read_from(bytes(&table).as_slice()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid table: segment 2 covers [-1.5, -1], expected [-2, -1.5]"
+    );
+    let table = ChebySegmentTable::from_segments(sine().into_segments(), -3.0, 0.25);
+    let err = ChebySegmentTable::<f64, 12>::read_from(bytes(&table).as_slice()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid table: segment 0 covers [-3, -2.5], expected [-3, -2.75]"
+    );
+}
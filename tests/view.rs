@@ -0,0 +1,129 @@
+//! Table views: in-place evaluation from table files at any alignment and
+//! from `f64` slices matches the owned table exactly, and damaged buffers
+//! are rejected on construction.
+
+use cheby::storage::{Storable, StorableAxis, StorageError};
+use cheby::{ChebySegmentTable, ChebySegmentTableView, Vec3};
+use qtty::{Days, Hours, Kilometers};
+
+type Orbit = ChebySegmentTable<Vec3<Kilometers>, 10, Days>;
+
+fn orbit() -> Orbit {
+    ChebySegmentTable::from_fn(
+        |t: Days| {
+            let (s, c) = t.value().sin_cos();
+            Vec3::new([
+                Kilometers::new(7000.0 * c),
+                Kilometers::new(7000.0 * s),
+                Kilometers::new(10.0 * t.value()),
+            ])
+        },
+        Days::new(0.0),
+        Days::new(10.0),
+        Days::new(1.25),
+    )
+}
+
+fn file<T: Storable, const N: usize, X: StorableAxis>(
+    table: &ChebySegmentTable<T, N, X>,
+) -> Vec<u8> {
+    let mut out = Vec::new();
+    table.write_to(&mut out).unwrap();
+    out
+}
+
+#[test]
+fn view_matches_owned_table() {
+    let table = orbit();
+    let bytes = file(&table);
+    // Shift the file by one byte so no number is aligned.
+    let mut shifted = vec![0];
+    shifted.extend(&bytes);
+    let data = table.to_f64s();
+
+    let views = [
+        ChebySegmentTableView::<Vec3<Kilometers>, 10, Days>::from_bytes(&bytes).unwrap(),
+        ChebySegmentTableView::from_bytes(&shifted[1..]).unwrap(),
+        ChebySegmentTableView::from_f64s(&data, table.start(), table.segment_len()).unwrap(),
+    ];
+    for view in views {
+        assert_eq!(view.len(), table.len());
+        assert_eq!((view.start(), view.end()), (table.start(), table.end()));
+        for i in 0..=100 {
+            let t = Days::new(0.1 * i as f64);
+            assert_eq!(view.eval(t), table.eval(t));
+            assert_eq!(view.eval_derivative(t), table.eval_derivative(t));
+        }
+        // Lookups accept any time unit, as on the owned table.
+        let t = Hours::new(30.0);
+        assert_eq!(view.segment_index(t), Some(1));
+        assert_eq!(view.eval(t), table.eval(t));
+        assert_eq!(view.get_segment(t).as_ref(), table.get_segment(t));
+        assert_eq!(view.to_table(), table);
+    }
+}
+
+#[test]
+fn view_of_single_precision_file() {
+    let table = ChebySegmentTable::<f32, 7>::from_fn(|t| t.sin() as f32, 0.0, 3.0, 0.5);
+    let bytes = file(&table);
+    let view = ChebySegmentTableView::<f32, 7>::from_bytes(&bytes).unwrap();
+    for t in [0.0, 0.7, 2.2, 2.999] {
+        assert_eq!(view.eval(t), table.eval(t));
+        assert_eq!(view.eval_widened::<f64>(t), table.eval_widened::<f64>(t));
+    }
+    // Widened to `f64` numbers, the same coefficients can be viewed as a
+    // slice, though not as `f32` values.
+    let data = table.to_f64s();
+    assert!(ChebySegmentTableView::<f32, 7>::from_f64s(&data, 0.0, 0.5).is_err());
+    assert_eq!(data[2] as f32, table.segments()[0].coeffs[0]);
+}
+
+#[test]
+fn view_rejects_damaged_buffers() {
+    let bytes = file(&orbit());
+    let view =
+        |b: &[u8]| ChebySegmentTableView::<Vec3<Kilometers>, 10, Days>::from_bytes(b).map(|_| ());
+
+    let err = view(&bytes[..bytes.len() - 1]).unwrap_err();
+    assert!(matches!(err, StorageError::Truncated), "{err}");
+    let err = view(&bytes[..bytes.len() / 2]).unwrap_err();
+    assert!(matches!(err, StorageError::Truncated), "{err}");
+
+    let mut damaged = bytes.clone();
+    damaged[bytes.len() / 2] ^= 1;
+    assert!(matches!(view(&damaged), Err(StorageError::Corrupt { .. })));
+
+    let mut padded = bytes.clone();
+    padded.push(0);
+    assert!(matches!(view(&padded), Err(StorageError::Invalid(_))));
+
+    let err = ChebySegmentTableView::<Vec3<Kilometers>, 9, Days>::from_bytes(&bytes).unwrap_err();
+    assert!(
+        matches!(
+            err,
+            StorageError::Shape {
+                what: "coefficients",
+                ..
+            }
+        ),
+        "{err}"
+    );
+
+    // Segments out of place in their table, from a file or a slice.
+    let table = ChebySegmentTable::<f64, 4>::from_fn(f64::sin, 0.0, 3.0, 1.0);
+    let mut segments = table.clone().into_segments();
+    segments.swap(0, 1);
+    let swapped = ChebySegmentTable::from_segments(segments, 0.0, 1.0);
+    let err = ChebySegmentTableView::<f64, 4>::from_bytes(&file(&swapped)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid table: segment 0 covers [1, 2], expected [0, 1]"
+    );
+    let data = table.to_f64s();
+    let err = ChebySegmentTableView::<f64, 4>::from_f64s(&data, 0.5, 1.0).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid table: segment 0 covers [0, 1], expected [0.5, 1.5]"
+    );
+}